@@ -8,15 +8,63 @@ use codespan_reporting::{
 };
 use structopt::StructOpt;
 
-use pijama_driver::{LangError, LangErrorKind};
+use pijama_driver::{
+    doc::DocFormat,
+    testing::{TestFailure, TestReport},
+    LangError, LangErrorKind,
+};
+use pijama_machine::trace::Trace;
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "pijama", about = "The Pijama compiler")]
 pub struct Options {
     #[structopt(name = "INPUT", help = "Path to the input file.")]
-    pub path: String,
+    pub path: Option<String>,
     #[structopt(flatten)]
     pub machine_opts: MachineOptions,
+    #[structopt(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Command {
+    #[structopt(about = "Prints a detailed explanation of an error code")]
+    Explain {
+        #[structopt(name = "CODE", help = "Error code to explain, e.g. E0001.")]
+        code: String,
+    },
+    #[structopt(about = "Generates the documentation of the top-level functions of a program")]
+    Doc {
+        #[structopt(name = "INPUT", help = "Path to the input file.")]
+        path: String,
+        #[structopt(
+            long = "--format",
+            default_value = "markdown",
+            help = "Format of the documentation, either `markdown` or `html`."
+        )]
+        format: DocFormat,
+        #[structopt(
+            short = "o",
+            long = "--output",
+            help = "Path to the output file, the documentation is printed if missing."
+        )]
+        output: Option<String>,
+    },
+    #[structopt(about = "Runs the tests declared in a program")]
+    Test {
+        #[structopt(name = "INPUT", help = "Path to the input file.")]
+        path: String,
+        #[structopt(
+            name = "FILTER",
+            help = "Only run the tests whose name contains this string."
+        )]
+        filter: Option<String>,
+    },
+    #[structopt(about = "Runs a program step by step in an interactive debugger")]
+    Debug {
+        #[structopt(name = "INPUT", help = "Path to the input file.")]
+        path: String,
+    },
 }
 
 #[derive(Debug, StructOpt)]
@@ -27,6 +75,54 @@ pub struct MachineOptions {
     )]
     // If the flag is not passed, the default value is `false`.
     pub overflow_check: bool,
+    #[structopt(
+        long = "--trace",
+        help = "Logs every reduction performed during the execution to the standard error"
+    )]
+    pub trace: bool,
+    #[structopt(
+        long = "--trace-depth",
+        name = "DEPTH",
+        help = "Only logs the reductions nested at most DEPTH evaluations deep, implies `--trace`"
+    )]
+    pub trace_depth: Option<usize>,
+    #[structopt(
+        long = "--trace-steps",
+        name = "STEPS",
+        help = "Stops logging after STEPS reductions, implies `--trace`"
+    )]
+    pub trace_steps: Option<usize>,
+    #[structopt(
+        long = "--trace-locations",
+        help = "Shows the location of the source code of each reduction, implies `--trace`"
+    )]
+    pub trace_locations: bool,
+}
+
+impl MachineOptions {
+    /// Returns the trace of the reductions requested by these options for the program `input`,
+    /// if any.
+    pub fn trace(&self, input: &str) -> Option<Trace> {
+        if !(self.trace
+            || self.trace_depth.is_some()
+            || self.trace_steps.is_some()
+            || self.trace_locations)
+        {
+            return None;
+        }
+
+        let mut trace = Trace::default();
+        if let Some(depth) = self.trace_depth {
+            trace = trace.with_max_depth(depth);
+        }
+        if let Some(steps) = self.trace_steps {
+            trace = trace.with_max_steps(steps);
+        }
+        if self.trace_locations {
+            trace = trace.with_locations(input);
+        }
+        Some(trace)
+    }
 }
 
 pub fn display_error(input: &str, path: &str, error: &LangError) {
@@ -42,12 +138,83 @@ pub fn display_error(input: &str, path: &str, error: &LangError) {
         LangErrorKind::Parse(_) => "Parsing error",
         LangErrorKind::Lower(_) => "Lowering error",
     };
+    let code = error.kind().code();
+
+    let mut labels =
+        vec![Label::primary(file_id, loc.start..loc.end).with_message(error.to_string())];
+    for label in error.labels() {
+        labels.push(
+            Label::secondary(file_id, label.loc.start..label.loc.end)
+                .with_message(label.content.clone()),
+        );
+    }
+
+    let mut notes = error.notes().to_vec();
+    if let Some(help) = error.kind().help() {
+        notes.push(format!("help: {}", help));
+    }
+    notes.push(format!(
+        "for more information about this error, try `pijama explain {}`",
+        code
+    ));
 
     let diagnostic = Diagnostic::error()
         .with_message(msg)
-        .with_labels(vec![
-            Label::primary(file_id, loc.start..loc.end).with_message(error.to_string())
-        ]);
+        .with_code(code)
+        .with_labels(labels)
+        .with_notes(notes);
 
     emit(&mut writer.lock(), &config, &files, &diagnostic).unwrap();
 }
+
+/// Prints the results of running the tests of a program in the same style as `cargo test`.
+///
+/// Returns whether every test passed.
+pub fn display_test_reports(input: &str, path: &str, reports: &[TestReport]) -> bool {
+    let plural = if reports.len() == 1 { "" } else { "s" };
+    println!("\nrunning {} test{}", reports.len(), plural);
+    for report in reports {
+        let status = if report.result.is_ok() {
+            "ok"
+        } else {
+            "FAILED"
+        };
+        println!("test {} ... {}", report.test.name, status);
+    }
+
+    let failures = reports
+        .iter()
+        .filter_map(|report| Some((report, report.result.as_ref().err()?)))
+        .collect::<Vec<(&TestReport, &TestFailure)>>();
+
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for (report, failure) in &failures {
+            let (line, column) = report.test.loc.line_column(input);
+            println!(
+                "\n---- {} at {}:{}:{} ----",
+                report.test.name, path, line, column
+            );
+            match failure.loc {
+                Some(loc) => {
+                    let (line, column) = loc.line_column(input);
+                    println!("{}:{}:{}: {}", path, line, column, failure.message);
+                }
+                None => println!("{}", failure.message),
+            }
+            if !report.output.is_empty() {
+                print!("output:\n{}", report.output);
+            }
+        }
+    }
+
+    let status = if failures.is_empty() { "ok" } else { "FAILED" };
+    println!(
+        "\ntest result: {}. {} passed; {} failed\n",
+        status,
+        reports.len() - failures.len(),
+        failures.len()
+    );
+
+    failures.is_empty()
+}
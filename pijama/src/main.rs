@@ -1,23 +1,107 @@
 use structopt::StructOpt;
 
-use std::fs::read_to_string;
+use std::{
+    fs::{read_to_string, write},
+    io::{stdin, stdout, BufReader},
+    process::exit,
+};
 
-use pijama::{display_error, Options};
-use pijama_driver::run;
+use pijama::{display_error, display_test_reports, Command, Options};
+use pijama_driver::{
+    debug::debug, doc::generate_doc, explain::explain, run, run_traced, testing::run_tests,
+};
 
 fn main() {
     let options = Options::from_args();
+    let overflow_check = options.machine_opts.overflow_check;
 
-    let input = match read_to_string(&options.path) {
+    match &options.command {
+        Some(Command::Explain { code }) => match explain(code) {
+            Some(explanation) => print!("{}", explanation),
+            None => {
+                eprintln!("error: `{}` is not a valid error code", code);
+                exit(1);
+            }
+        },
+        Some(Command::Doc {
+            path,
+            format,
+            output,
+        }) => {
+            let input = read_input(path);
+
+            let doc = match generate_doc(&input, path, *format) {
+                Ok(doc) => doc,
+                Err(err) => {
+                    display_error(&input, path, &err);
+                    exit(1);
+                }
+            };
+
+            match output {
+                Some(output) => {
+                    if let Err(err) = write(output, doc) {
+                        eprintln!("{}", err);
+                        exit(1);
+                    }
+                }
+                None => print!("{}", doc),
+            }
+        }
+        Some(Command::Test { path, filter }) => {
+            let input = read_input(path);
+
+            match run_tests(&input, filter.as_deref(), overflow_check) {
+                Ok(reports) => {
+                    if !display_test_reports(&input, path, &reports) {
+                        exit(1);
+                    }
+                }
+                Err(err) => {
+                    display_error(&input, path, &err);
+                    exit(1);
+                }
+            }
+        }
+        Some(Command::Debug { path }) => {
+            let input = read_input(path);
+
+            let commands = BufReader::new(stdin());
+            if let Err(err) = debug(&input, commands, stdout(), overflow_check) {
+                display_error(&input, path, &err);
+                exit(1);
+            }
+        }
+        None => {
+            let path = match &options.path {
+                Some(path) => path,
+                None => {
+                    eprintln!("error: an input file is required");
+                    exit(1);
+                }
+            };
+
+            let input = read_input(path);
+
+            let result = match options.machine_opts.trace(&input) {
+                Some(trace) => run_traced(&input, overflow_check, trace),
+                None => run(&input, overflow_check),
+            };
+
+            if let Err(err) = result {
+                display_error(&input, path, &err);
+            }
+        }
+    }
+}
+
+/// Reads the program at `path`, exiting if it cannot be read.
+fn read_input(path: &str) -> String {
+    match read_to_string(path) {
         Ok(input) => input,
         Err(err) => {
             eprintln!("{}", err);
-            return;
+            exit(1);
         }
-    };
-
-    match run(&input, options.machine_opts.overflow_check) {
-        Ok(()) => (),
-        Err(err) => display_error(&input, &options.path, &err),
     }
 }
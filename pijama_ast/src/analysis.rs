@@ -24,6 +24,70 @@ pub fn is_fn_def_recursive<'a>(name: Local<'a>, body: &Block<'a>) -> bool {
     checker.is_rec
 }
 
+/// Splits a sequence of adjacent function definitions into groups of mutually recursive functions.
+///
+/// Each definition is given by the name of the function and its body. Two functions belong to the
+/// same group if each one of them uses the other, either directly or through other functions in
+/// the sequence. The groups are returned in an order such that the functions of a group only use
+/// functions in the same group or in previous groups. Definitions that do not depend on each other
+/// keep the order in which they were given.
+///
+/// A function only uses a function defined after it if the name of the latter is not bound before
+/// the sequence, as checked by `is_bound`. Otherwise such name refers to the outer binding.
+pub fn fn_def_groups<'a>(
+    defs: &[(Local<'a>, &Block<'a>)],
+    is_bound: impl Fn(Local<'a>) -> bool,
+) -> Vec<Vec<usize>> {
+    let len = defs.len();
+    // `reaches[i][j]` stores if the `i`-th function uses the `j`-th function, either directly or
+    // through other functions.
+    let mut reaches: Vec<Vec<bool>> = defs
+        .iter()
+        .enumerate()
+        .map(|(i, (_, body))| {
+            defs.iter()
+                .enumerate()
+                .map(|(j, (name, _))| {
+                    (j <= i || !is_bound(*name)) && is_fn_def_recursive(*name, body)
+                })
+                .collect()
+        })
+        .collect();
+
+    for k in 0..len {
+        let reaches_k = reaches[k].clone();
+        for reaches_i in reaches.iter_mut() {
+            if reaches_i[k] {
+                for (reach, &reach_k) in reaches_i.iter_mut().zip(&reaches_k) {
+                    *reach |= reach_k;
+                }
+            }
+        }
+    }
+
+    let mut done = vec![false; len];
+    let mut groups = Vec::new();
+
+    while groups.iter().map(Vec::len).sum::<usize>() < len {
+        // Find the first function that only uses functions in its own group or in previous groups.
+        let first = (0..len)
+            .find(|&i| !done[i] && (0..len).all(|j| done[j] || !reaches[i][j] || reaches[j][i]))
+            .expect("the dependencies between functions cannot be ordered");
+
+        let group: Vec<usize> = (0..len)
+            .filter(|&j| j == first || (reaches[first][j] && reaches[j][first]))
+            .collect();
+
+        for &i in &group {
+            done[i] = true;
+        }
+
+        groups.push(group);
+    }
+
+    groups
+}
+
 /// Visitor that checks if a function is recursive or not.
 struct RecursionChecker<'a> {
     /// Local of the target function
@@ -83,6 +147,24 @@ impl<'a> NodeVisitor<'a> for RecursionChecker<'a> {
         self.super_assign(annotation, expr);
     }
 
+    fn visit_let(
+        &mut self,
+        annotation: &TyAnnotation<Located<Local<'a>>>,
+        expr: &Located<Expression<'a>>,
+        body: &Located<Expression<'a>>,
+    ) {
+        // The bound expression is visited in the current scope.
+        self.visit_expression(expr);
+        // The binding only exists inside the body, so it is visited in a new scope where the
+        // target name is shadowed if the binding binds it.
+        self.push_scope();
+        if annotation.item.content == self.name {
+            self.is_shadowed = true;
+        }
+        self.visit_expression(body);
+        self.pop_scope();
+    }
+
     fn visit_fn_def(
         &mut self,
         name: &Located<Local<'a>>,
@@ -9,7 +9,7 @@ use pijama_common::{
     BinOp, Literal, Local, Primitive, UnOp,
 };
 
-use crate::ty::TyAnnotation;
+use crate::ty::{Ty, TyAnnotation};
 
 /// A block is a sequence of nodes terminating in an expression.
 #[derive(Debug, Eq, PartialEq)]
@@ -42,14 +42,29 @@ impl<'a> Node<'a> {
 /// An AST node that performs an action.
 #[derive(Debug, Eq, PartialEq)]
 pub enum Statement<'a> {
-    /// Statement containing an assignment.
-    Assign(TyAnnotation<Located<Local<'a>>>, Located<Expression<'a>>),
-    /// Statement containing a function definition.
+    /// Statement containing an assignment and its documentation.
+    Assign(
+        TyAnnotation<Located<Local<'a>>>,
+        Located<Expression<'a>>,
+        Option<Doc<'a>>,
+    ),
+    /// Statement containing a function definition and its documentation.
     FnDef(
         Located<Local<'a>>,
         Vec<TyAnnotation<Located<Local<'a>>>>,
         TyAnnotation<Block<'a>>,
+        Option<Doc<'a>>,
     ),
+    /// Statement containing a type alias declaration.
+    TyAlias(Located<&'a str>, Located<Ty>),
+    /// Statement containing a type class declaration.
+    Class(Located<&'a str>, Vec<MethodSig<'a>>),
+    /// Statement containing an instance of a type class for a type.
+    Instance(Located<&'a str>, Located<Ty>, Vec<Located<Method<'a>>>),
+    /// Statement containing the declaration of an infix operator and the function it stands for.
+    Infix(Fixity, Located<&'a str>, Located<Expression<'a>>),
+    /// Statement containing a test with its name, only run by the test runner.
+    Test(Located<&'a str>, Block<'a>),
 }
 
 /// An AST node that produces a value.
@@ -61,6 +76,22 @@ pub enum Expression<'a> {
         Box<Located<Expression<'a>>>,
         Box<Located<Expression<'a>>>,
     ),
+    /// Expression containing an application of an infix operator declared by the user.
+    Infix(
+        Located<&'a str>,
+        Box<Located<Expression<'a>>>,
+        Box<Located<Expression<'a>>>,
+    ),
+    /// Expression containing an operator section, a function taking the missing operands of an
+    /// operator.
+    ///
+    /// At most one of the operands is present: `(+)` has none, `(2 *)` has the left one and
+    /// `(< 0)` has the right one.
+    Section(
+        Located<InfixOp<'a>>,
+        Option<Box<Located<Expression<'a>>>>,
+        Option<Box<Located<Expression<'a>>>>,
+    ),
     /// Expression containing a unary operation.
     UnaryOp(UnOp, Box<Located<Expression<'a>>>),
     /// Expression containing a conditional.
@@ -72,12 +103,70 @@ pub enum Expression<'a> {
     ),
     /// Expression containing a function call.
     Call(Box<Located<Expression<'a>>>, Vec<Located<Expression<'a>>>),
+    /// Expression containing a local binding that is only visible inside the last expression.
+    Let(
+        TyAnnotation<Located<Local<'a>>>,
+        Box<Located<Expression<'a>>>,
+        Box<Located<Expression<'a>>>,
+    ),
+    /// Expression containing a pipe, passing the first expression as the first argument of the
+    /// second one.
+    Pipe(Box<Located<Expression<'a>>>, Box<Located<Expression<'a>>>),
     /// Expression containing a literal.
     Literal(Literal),
     /// Expression containing a local.
     Local(Local<'a>),
     /// Expression containing a primitive function.
     PrimFn(Primitive),
+    /// Expression containing a type ascription.
+    Ascription(Box<Located<Expression<'a>>>, Located<Ty>),
+    /// Expression standing for a missing expression.
+    Hole,
+}
+
+/// Documentation written with `##` comments right before a statement.
+///
+/// Doc comments do not change the meaning of a program, they are kept for tooling.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct Doc<'a> {
+    /// Each line of the comment without the leading `##` and the space following it.
+    pub lines: Vec<&'a str>,
+}
+
+impl<'a> Doc<'a> {
+    /// Returns the text of the comment, with one line per line of the comment.
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+}
+
+/// An operator that can be used between two operands.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum InfixOp<'a> {
+    /// A built-in binary operator.
+    BinOp(BinOp),
+    /// An operator declared by the user.
+    Custom(&'a str),
+}
+
+/// The associativity of an infix operator.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Assoc {
+    /// `a op b op c` stands for `(a op b) op c`.
+    Left,
+    /// `a op b op c` stands for `a op (b op c)`.
+    Right,
+    /// `a op b op c` is not allowed.
+    None,
+}
+
+/// How tightly an infix operator binds its operands.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct Fixity {
+    /// The associativity of the operator.
+    pub assoc: Assoc,
+    /// The precedence of the operator, operators with higher precedence bind tighter.
+    pub prec: u8,
 }
 
 /// Encapsulates a conditional statement in Pijama's syntax. It is used to represent both `if` and
@@ -89,3 +178,28 @@ pub struct Branch<'a> {
     /// The body of the branch that is evaluated if the condition is true.
     pub body: Block<'a>,
 }
+
+/// The signature of a method in a type class declaration.
+///
+/// Every argument and the return type must have a type annotation, which can use `Self` to refer
+/// to the type of the instance.
+#[derive(Debug, Eq, PartialEq)]
+pub struct MethodSig<'a> {
+    /// The name of the method.
+    pub name: Located<Local<'a>>,
+    /// The arguments of the method.
+    pub args: Vec<TyAnnotation<Located<Local<'a>>>>,
+    /// The return type of the method.
+    pub ty: Located<Ty>,
+}
+
+/// The definition of a method in an instance of a type class.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Method<'a> {
+    /// The name of the method.
+    pub name: Located<Local<'a>>,
+    /// The arguments of the method.
+    pub args: Vec<TyAnnotation<Located<Local<'a>>>>,
+    /// The body of the method.
+    pub body: TyAnnotation<Block<'a>>,
+}
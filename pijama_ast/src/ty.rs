@@ -1,7 +1,7 @@
 //! The AST representation of types.
 use std::fmt::Debug;
 
-use pijama_common::location::Located;
+use pijama_common::{location::Located, IntTy};
 
 /// A type in the AST.
 ///
@@ -13,10 +13,24 @@ pub enum Ty {
     Bool,
     /// The type of (signed) integers.
     Int,
+    /// The type of integers with a fixed width.
+    Fixed(IntTy),
+    /// The type of integers of arbitrary precision.
+    BigInt,
+    /// The type of double-precision floating-point numbers.
+    Float,
+    /// The type of Unicode scalar values.
+    Char,
     /// The [unit type](https://en.wikipedia.org/wiki/Unit_type).
     Unit,
     /// The type of functions between two types.
     Arrow(Box<Ty>, Box<Ty>),
+    /// The type of values that may be missing.
+    Option(Box<Ty>),
+    /// The type of values that are either successful or an error.
+    Result(Box<Ty>, Box<Ty>),
+    /// A type referred by the name of a type alias.
+    Alias(String),
     /// A missing type. Used when an item in the AST did not have a type annotation.
     Missing,
 }
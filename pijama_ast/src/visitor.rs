@@ -2,8 +2,8 @@
 use pijama_common::{location::Located, BinOp, Literal, Local, Primitive, UnOp};
 
 use crate::{
-    node::{Block, Branch, Expression, Node, Statement},
-    ty::TyAnnotation,
+    node::{Block, Branch, Expression, Fixity, InfixOp, Method, MethodSig, Node, Statement},
+    ty::{Ty, TyAnnotation},
 };
 
 /// Trait for the node visitor pattern.
@@ -44,8 +44,13 @@ pub trait NodeVisitor<'a> {
     /// Destructures a statement to visit its children.
     fn super_statement(&mut self, stat: &Located<Statement<'a>>) {
         match &stat.content {
-            Statement::Assign(annotation, expr) => self.visit_assign(annotation, expr),
-            Statement::FnDef(name, args, body) => self.visit_fn_def(name, args, body),
+            Statement::Assign(annotation, expr, _) => self.visit_assign(annotation, expr),
+            Statement::FnDef(name, args, body, _) => self.visit_fn_def(name, args, body),
+            Statement::TyAlias(name, ty) => self.visit_ty_alias(name, ty),
+            Statement::Class(name, methods) => self.visit_class(name, methods),
+            Statement::Instance(class, ty, methods) => self.visit_instance(class, ty, methods),
+            Statement::Infix(fixity, op, expr) => self.visit_infix_decl(*fixity, op, expr),
+            Statement::Test(name, body) => self.visit_test(name, body),
         }
     }
     /// Destructures an expression to visit its children.
@@ -54,15 +59,27 @@ pub trait NodeVisitor<'a> {
             Expression::BinaryOp(op, expr1, expr2) => {
                 self.visit_binary_op(*op, expr1.as_ref(), expr2.as_ref())
             }
+            Expression::Infix(op, expr1, expr2) => {
+                self.visit_infix(op, expr1.as_ref(), expr2.as_ref())
+            }
+            Expression::Section(op, lhs, rhs) => {
+                self.visit_section(op, lhs.as_deref(), rhs.as_deref())
+            }
             Expression::UnaryOp(op, expr) => self.visit_unary_op(*op, expr.as_ref()),
             Expression::Cond(if_branch, branches, el_blk) => {
                 self.visit_cond(if_branch, branches, el_blk)
             }
             Expression::AnonFn(args, body) => self.visit_anon_fn(args, body),
             Expression::Call(func, args) => self.visit_call(func.as_ref(), &args),
+            Expression::Let(annotation, expr, body) => {
+                self.visit_let(annotation, expr.as_ref(), body.as_ref())
+            }
+            Expression::Pipe(arg, func) => self.visit_pipe(arg.as_ref(), func.as_ref()),
             Expression::Literal(literal) => self.visit_literal(literal),
             Expression::Local(name) => self.visit_local(name),
             Expression::PrimFn(primitive) => self.visit_prim_fn(*primitive),
+            Expression::Ascription(expr, ty) => self.visit_ascription(expr.as_ref(), ty),
+            Expression::Hole => self.visit_hole(),
         }
     }
     /// Destructures a binary operation to visit its children.
@@ -75,6 +92,27 @@ pub trait NodeVisitor<'a> {
         self.visit_expression(expr1);
         self.visit_expression(expr2);
     }
+    /// Destructures an application of an infix operator to visit its children.
+    fn super_infix(
+        &mut self,
+        _op: &Located<&'a str>,
+        expr1: &Located<Expression<'a>>,
+        expr2: &Located<Expression<'a>>,
+    ) {
+        self.visit_expression(expr1);
+        self.visit_expression(expr2);
+    }
+    /// Destructures an operator section to visit its children.
+    fn super_section(
+        &mut self,
+        _op: &Located<InfixOp<'a>>,
+        lhs: Option<&Located<Expression<'a>>>,
+        rhs: Option<&Located<Expression<'a>>>,
+    ) {
+        for expr in lhs.into_iter().chain(rhs) {
+            self.visit_expression(expr);
+        }
+    }
     /// Destructures a unary operation to visit its children.
     fn super_unary_op(&mut self, _op: UnOp, expr: &Located<Expression<'a>>) {
         self.visit_expression(expr);
@@ -116,6 +154,37 @@ pub trait NodeVisitor<'a> {
         }
         self.visit_block(&body.item);
     }
+    /// Destructures a type alias declaration to visit its children.
+    fn super_ty_alias(&mut self, _name: &Located<&'a str>, _ty: &Located<Ty>) {}
+    /// Destructures a type class declaration to visit its children.
+    fn super_class(&mut self, _name: &Located<&'a str>, _methods: &[MethodSig<'a>]) {}
+    /// Destructures an instance of a type class to visit its children.
+    ///
+    /// The methods of the instance are visited as anonymous functions because their names are not
+    /// bound in any scope.
+    fn super_instance(
+        &mut self,
+        _class: &Located<&'a str>,
+        _ty: &Located<Ty>,
+        methods: &[Located<Method<'a>>],
+    ) {
+        for method in methods {
+            self.visit_anon_fn(&method.content.args, &method.content.body);
+        }
+    }
+    /// Destructures an infix operator declaration to visit its children.
+    fn super_infix_decl(
+        &mut self,
+        _fixity: Fixity,
+        _op: &Located<&'a str>,
+        expr: &Located<Expression<'a>>,
+    ) {
+        self.visit_expression(expr);
+    }
+    /// Destructures a test to visit its children.
+    fn super_test(&mut self, _name: &Located<&'a str>, body: &Block<'a>) {
+        self.visit_block(body);
+    }
     /// Destructures an anonymous function to visit its children.
     fn super_anon_fn(
         &mut self,
@@ -134,12 +203,34 @@ pub trait NodeVisitor<'a> {
             self.visit_expression(expr);
         }
     }
+    /// Destructures a local binding to visit its children.
+    fn super_let(
+        &mut self,
+        annotation: &TyAnnotation<Located<Local<'a>>>,
+        expr: &Located<Expression<'a>>,
+        body: &Located<Expression<'a>>,
+    ) {
+        self.visit_local(&annotation.item.content);
+        self.visit_expression(expr);
+        self.visit_expression(body);
+    }
+    /// Destructures a pipe to visit its children.
+    fn super_pipe(&mut self, arg: &Located<Expression<'a>>, func: &Located<Expression<'a>>) {
+        self.visit_expression(arg);
+        self.visit_expression(func);
+    }
     /// Destructures a literal to visit its children.
     fn super_literal(&mut self, _literal: &Literal) {}
     /// Destructures a name to visit its children.
     fn super_local(&mut self, _name: &Local<'a>) {}
     /// Destructures a primitive function to visit its children.
     fn super_prim_fn(&mut self, _prim: Primitive) {}
+    /// Destructures a type ascription to visit its children.
+    fn super_ascription(&mut self, expr: &Located<Expression<'a>>, _ty: &Located<Ty>) {
+        self.visit_expression(expr);
+    }
+    /// Destructures a hole to visit its children.
+    fn super_hole(&mut self) {}
     /// Specifies how blocks should be visited.
     fn visit_block(&mut self, block: &Block<'a>) {
         self.super_block(block);
@@ -165,6 +256,24 @@ pub trait NodeVisitor<'a> {
     ) {
         self.super_binary_op(op, expr1, expr2);
     }
+    /// Specifies how applications of infix operators should be visited.
+    fn visit_infix(
+        &mut self,
+        op: &Located<&'a str>,
+        expr1: &Located<Expression<'a>>,
+        expr2: &Located<Expression<'a>>,
+    ) {
+        self.super_infix(op, expr1, expr2);
+    }
+    /// Specifies how operator sections should be visited.
+    fn visit_section(
+        &mut self,
+        op: &Located<InfixOp<'a>>,
+        lhs: Option<&Located<Expression<'a>>>,
+        rhs: Option<&Located<Expression<'a>>>,
+    ) {
+        self.super_section(op, lhs, rhs);
+    }
     /// Specifies how unary operations should be visited.
     fn visit_unary_op(&mut self, op: UnOp, expr: &Located<Expression<'a>>) {
         self.super_unary_op(op, expr);
@@ -194,6 +303,36 @@ pub trait NodeVisitor<'a> {
     ) {
         self.super_fn_def(name, args, body);
     }
+    /// Specifies how type alias declarations should be visited.
+    fn visit_ty_alias(&mut self, name: &Located<&'a str>, ty: &Located<Ty>) {
+        self.super_ty_alias(name, ty);
+    }
+    /// Specifies how type class declarations should be visited.
+    fn visit_class(&mut self, name: &Located<&'a str>, methods: &[MethodSig<'a>]) {
+        self.super_class(name, methods);
+    }
+    /// Specifies how instances of type classes should be visited.
+    fn visit_instance(
+        &mut self,
+        class: &Located<&'a str>,
+        ty: &Located<Ty>,
+        methods: &[Located<Method<'a>>],
+    ) {
+        self.super_instance(class, ty, methods);
+    }
+    /// Specifies how infix operator declarations should be visited.
+    fn visit_infix_decl(
+        &mut self,
+        fixity: Fixity,
+        op: &Located<&'a str>,
+        expr: &Located<Expression<'a>>,
+    ) {
+        self.super_infix_decl(fixity, op, expr);
+    }
+    /// Specifies how tests should be visited.
+    fn visit_test(&mut self, name: &Located<&'a str>, body: &Block<'a>) {
+        self.super_test(name, body);
+    }
     /// Specifies how anonymous functions should be visited.
     fn visit_anon_fn(
         &mut self,
@@ -206,6 +345,19 @@ pub trait NodeVisitor<'a> {
     fn visit_call(&mut self, func: &Located<Expression<'a>>, args: &[Located<Expression<'a>>]) {
         self.super_call(func, args)
     }
+    /// Specifies how local bindings should be visited.
+    fn visit_let(
+        &mut self,
+        annotation: &TyAnnotation<Located<Local<'a>>>,
+        expr: &Located<Expression<'a>>,
+        body: &Located<Expression<'a>>,
+    ) {
+        self.super_let(annotation, expr, body);
+    }
+    /// Specifies how pipes should be visited.
+    fn visit_pipe(&mut self, arg: &Located<Expression<'a>>, func: &Located<Expression<'a>>) {
+        self.super_pipe(arg, func)
+    }
     /// Specifies how literals should be visited.
     fn visit_literal(&mut self, literal: &Literal) {
         self.super_literal(literal);
@@ -218,4 +370,12 @@ pub trait NodeVisitor<'a> {
     fn visit_prim_fn(&mut self, prim: Primitive) {
         self.super_prim_fn(prim);
     }
+    /// Specifies how type ascriptions should be visited.
+    fn visit_ascription(&mut self, expr: &Located<Expression<'a>>, ty: &Located<Ty>) {
+        self.super_ascription(expr, ty);
+    }
+    /// Specifies how holes should be visited.
+    fn visit_hole(&mut self) {
+        self.super_hole();
+    }
 }
@@ -2,6 +2,8 @@ pub mod location;
 
 use std::fmt::{Debug, Display, Formatter, Result};
 
+use num_bigint::BigInt;
+
 /// The different binary operators that Pijama's syntax supports.
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub enum BinOp {
@@ -88,8 +90,117 @@ impl<'a> Display for UnOp {
     }
 }
 
+/// The integer types with a fixed width.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum IntTy {
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+}
+
+impl IntTy {
+    /// All the integer types with a fixed width.
+    pub const ALL: &'static [IntTy] = &[
+        IntTy::I8,
+        IntTy::I16,
+        IntTy::I32,
+        IntTy::I64,
+        IntTy::U8,
+        IntTy::U16,
+        IntTy::U32,
+        IntTy::U64,
+    ];
+
+    /// Returns the number of bits of this type.
+    pub fn bits(self) -> u32 {
+        match self {
+            IntTy::I8 | IntTy::U8 => 8,
+            IntTy::I16 | IntTy::U16 => 16,
+            IntTy::I32 | IntTy::U32 => 32,
+            IntTy::I64 | IntTy::U64 => 64,
+        }
+    }
+
+    /// Checks if this type can represent negative integers.
+    pub fn is_signed(self) -> bool {
+        match self {
+            IntTy::I8 | IntTy::I16 | IntTy::I32 | IntTy::I64 => true,
+            IntTy::U8 | IntTy::U16 | IntTy::U32 | IntTy::U64 => false,
+        }
+    }
+
+    /// Returns the smallest integer of this type.
+    pub fn min(self) -> i128 {
+        if self.is_signed() {
+            -(1 << (self.bits() - 1))
+        } else {
+            0
+        }
+    }
+
+    /// Returns the largest integer of this type.
+    pub fn max(self) -> i128 {
+        if self.is_signed() {
+            (1 << (self.bits() - 1)) - 1
+        } else {
+            (1 << self.bits()) - 1
+        }
+    }
+
+    /// Checks if `value` is an integer of this type.
+    pub fn fits(self, value: i128) -> bool {
+        self.min() <= value && value <= self.max()
+    }
+
+    /// Returns the integer of this type stored in `bits`.
+    ///
+    /// Integers of every width are stored in an `i64`. Integers of type `U64` that are larger than
+    /// `i64::MAX` are stored using their two's complement representation.
+    pub fn value(self, bits: i64) -> i128 {
+        match self {
+            IntTy::U64 => i128::from(bits as u64),
+            _ => i128::from(bits),
+        }
+    }
+
+    /// Returns the bits storing `value` after wrapping it around the range of this type.
+    pub fn wrap(self, value: i128) -> i64 {
+        let shift = 128 - self.bits();
+        if self.is_signed() {
+            ((value << shift) >> shift) as i64
+        } else {
+            (((value as u128) << shift) >> shift) as i64
+        }
+    }
+
+    /// Returns the suffix used by the literals of this type.
+    pub fn suffix(self) -> &'static str {
+        match self {
+            IntTy::I8 => "i8",
+            IntTy::I16 => "i16",
+            IntTy::I32 => "i32",
+            IntTy::I64 => "i64",
+            IntTy::U8 => "u8",
+            IntTy::U16 => "u16",
+            IntTy::U32 => "u32",
+            IntTy::U64 => "u64",
+        }
+    }
+}
+
+impl Display for IntTy {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self.suffix().to_uppercase())
+    }
+}
+
 /// The literal values that Pijama's syntax supports.
-#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone)]
 pub enum Literal {
     /// Boolean Literal.
     Bool(bool),
@@ -97,6 +208,14 @@ pub enum Literal {
     Unit,
     /// Numeric Literal.
     Number(i64),
+    /// Numeric Literal of an integer type with a fixed width.
+    Fixed(i64, IntTy),
+    /// Numeric Literal of arbitrary precision.
+    Big(BigInt),
+    /// Floating-point Literal, stored using `f64::to_bits`.
+    Float(u64),
+    /// Character Literal.
+    Char(char),
 }
 
 impl From<i64> for Literal {
@@ -118,6 +237,10 @@ impl<'a> Display for Literal {
             Bool(b) => write!(f, "{}", b),
             Unit => write!(f, "unit"),
             Number(num) => write!(f, "{}", num),
+            Fixed(bits, ty) => write!(f, "{}{}", ty.value(*bits), ty.suffix()),
+            Big(num) => write!(f, "{}n", num),
+            Float(bits) => write!(f, "{:?}", f64::from_bits(*bits)),
+            Char(c) => write!(f, "{:?}", c),
         }
     }
 }
@@ -127,6 +250,58 @@ impl<'a> Display for Literal {
 pub enum Primitive {
     /// Built-in Print primitive.
     Print,
+    /// Built-in primitive converting integers to `Int`.
+    ToInt,
+    /// Built-in primitive converting integers to an integer type with a fixed width.
+    ToFixed(IntTy),
+    /// Built-in primitive converting integers to `BigInt`.
+    ToBigInt,
+    /// Built-in primitive converting numbers to `Float`.
+    ToFloat,
+    /// Built-in primitive converting a `Float` to `Int` by discarding its fractional part.
+    Truncate,
+    /// Built-in primitive returning the code point of a `Char`.
+    Ord,
+    /// Built-in primitive returning the `Char` with a code point.
+    Chr,
+    /// Built-in primitive constructing or eliminating `Option` and `Result` values.
+    Sum(SumFn),
+    /// Built-in primitive failing if its argument is `false`.
+    Assert,
+    /// Built-in primitive failing if its arguments are not equal.
+    AssertEq,
+}
+
+impl Primitive {
+    /// All the primitives, used to enumerate them by name.
+    pub const ALL: &'static [Primitive] = &[
+        Primitive::Print,
+        Primitive::ToInt,
+        Primitive::ToFixed(IntTy::I8),
+        Primitive::ToFixed(IntTy::I16),
+        Primitive::ToFixed(IntTy::I32),
+        Primitive::ToFixed(IntTy::I64),
+        Primitive::ToFixed(IntTy::U8),
+        Primitive::ToFixed(IntTy::U16),
+        Primitive::ToFixed(IntTy::U32),
+        Primitive::ToFixed(IntTy::U64),
+        Primitive::ToBigInt,
+        Primitive::ToFloat,
+        Primitive::Truncate,
+        Primitive::Ord,
+        Primitive::Chr,
+        Primitive::Sum(SumFn::Some),
+        Primitive::Sum(SumFn::None),
+        Primitive::Sum(SumFn::Ok),
+        Primitive::Sum(SumFn::Err),
+        Primitive::Sum(SumFn::IsSome),
+        Primitive::Sum(SumFn::IsOk),
+        Primitive::Sum(SumFn::UnwrapOr),
+        Primitive::Sum(SumFn::Map),
+        Primitive::Sum(SumFn::AndThen),
+        Primitive::Assert,
+        Primitive::AssertEq,
+    ];
 }
 
 impl<'a> Display for Primitive {
@@ -135,6 +310,59 @@ impl<'a> Display for Primitive {
 
         match self {
             Print => write!(f, "print"),
+            ToInt => write!(f, "to_int"),
+            ToFixed(ty) => write!(f, "to_{}", ty.suffix()),
+            ToBigInt => write!(f, "to_bigint"),
+            ToFloat => write!(f, "to_float"),
+            Truncate => write!(f, "truncate"),
+            Ord => write!(f, "ord"),
+            Chr => write!(f, "chr"),
+            Sum(sum_fn) => write!(f, "{}", sum_fn),
+            Assert => write!(f, "assert"),
+            AssertEq => write!(f, "assert_eq"),
+        }
+    }
+}
+
+/// The primitives working over `Option` and `Result` values.
+///
+/// The `unwrap_or`, `map` and `and_then` primitives work over both kinds of values.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SumFn {
+    /// Wraps a value in an `Option`.
+    Some,
+    /// The empty `Option`.
+    None,
+    /// Wraps a successful value in a `Result`.
+    Ok,
+    /// Wraps an error in a `Result`.
+    Err,
+    /// Checks if an `Option` has a value.
+    IsSome,
+    /// Checks if a `Result` is successful.
+    IsOk,
+    /// Returns the value of an `Option` or `Result`, or a default one if there is none.
+    UnwrapOr,
+    /// Applies a function to the value of an `Option` or `Result`.
+    Map,
+    /// Applies a function returning an `Option` or `Result` to the value of another one.
+    AndThen,
+}
+
+impl Display for SumFn {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        use SumFn::*;
+
+        match self {
+            Some => write!(f, "some"),
+            None => write!(f, "none"),
+            Ok => write!(f, "ok"),
+            Err => write!(f, "err"),
+            IsSome => write!(f, "is_some"),
+            IsOk => write!(f, "is_ok"),
+            UnwrapOr => write!(f, "unwrap_or"),
+            Map => write!(f, "map"),
+            AndThen => write!(f, "and_then"),
         }
     }
 }
@@ -6,7 +6,7 @@ use thiserror::Error;
 /// Represents a location in the source code file.
 ///
 /// Both the start and end correspond to locations reported by `nom_locate`.
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct Location {
     /// Start of the location.
     pub start: usize,
@@ -23,6 +23,16 @@ impl Location {
     pub fn with_content<T>(self, content: T) -> Located<T> {
         Located::new(content, self)
     }
+    /// Returns the line and column where this location starts inside `source`, both starting at
+    /// one.
+    pub fn line_column(&self, source: &str) -> (usize, usize) {
+        let before = &source[..self.start];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        (
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+        )
+    }
 }
 
 /// Adding two locations `l1` and `l2` returns a location starting in `l1.start` and ending in `l2.end`.
@@ -112,16 +122,39 @@ impl<T: Clone> Clone for Located<T> {
 
 impl<T: Copy + Debug> Copy for Located<T> {}
 
+/// An error with a location in the source code.
+///
+/// Besides its primary location, an error can have secondary labels pointing to other relevant
+/// parts of the source code and notes with additional information.
 #[derive(Error, Debug)]
 #[error("{kind}")]
 pub struct LocatedError<K: Debug + Display> {
     kind: K,
     loc: Location,
+    labels: Vec<Located<String>>,
+    notes: Vec<String>,
 }
 
 impl<K: Debug + Display> LocatedError<K> {
     pub fn new(kind: K, loc: Location) -> Self {
-        Self { loc, kind }
+        Self {
+            loc,
+            kind,
+            labels: Vec::default(),
+            notes: Vec::default(),
+        }
+    }
+
+    /// Adds a secondary label with a message to the error.
+    pub fn with_label(mut self, loc: Location, msg: impl Into<String>) -> Self {
+        self.labels.push(loc.with_content(msg.into()));
+        self
+    }
+
+    /// Adds a note to the error.
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
     }
 
     pub fn loc(&self) -> Location {
@@ -132,8 +165,21 @@ impl<K: Debug + Display> LocatedError<K> {
         &self.kind
     }
 
+    pub fn labels(&self) -> &[Located<String>] {
+        &self.labels
+    }
+
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
     pub fn kind_into<L: Debug + Display + From<K>>(self) -> LocatedError<L> {
-        LocatedError::new(self.kind.into(), self.loc)
+        LocatedError {
+            kind: self.kind.into(),
+            loc: self.loc,
+            labels: self.labels,
+            notes: self.notes,
+        }
     }
 }
 
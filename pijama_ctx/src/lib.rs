@@ -1,18 +1,26 @@
 mod generator;
 mod store;
 
-use std::{collections::HashMap, fmt::Debug, hash::Hash};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    hash::Hash,
+};
 
-use pijama_common::{location::Location, Local};
-use pijama_ty::Ty;
+use pijama_common::{location::Location, BinOp, Local, Primitive};
+use pijama_ty::{Scheme, Ty};
 
 use generator::Generator;
 use store::Store;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+/// Ids are generated in increasing order, so comparing two of them compares when they were
+/// generated.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub struct LocalId(usize);
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+/// Ids are generated in increasing order, so comparing two of them compares when they were
+/// generated.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub struct TermId(usize);
 
 #[derive(Debug, Clone)]
@@ -21,10 +29,72 @@ pub struct TypeInfo {
     pub loc: Location,
 }
 
+/// A use of a method of a type class.
+#[derive(Debug, Clone)]
+pub struct MethodUse {
+    /// Name of the class of the method.
+    pub class: String,
+    /// Index of the method in the class.
+    pub method: usize,
+    /// Number of methods of the class.
+    pub methods: usize,
+    /// Type standing for `Self` in the signature of the method.
+    pub self_ty: Ty,
+    /// Instances of the class in scope where the method is used, from outermost to innermost,
+    /// with the dictionary of each one of them.
+    ///
+    /// The type of an instance may quantify type variables, which must be instantiated with new
+    /// type variables for each use.
+    pub instances: Vec<(Scheme, Dict)>,
+}
+
+/// The dictionary of an instance of a type class.
+///
+/// A dictionary holds the implementation of each method of the class, in the same order as the
+/// class declares them. Each use of a method is elaborated into a selection of the method from the
+/// dictionary chosen by the type-checker.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Dict {
+    /// The dictionary of a built-in instance. Its methods are primitive functions and operators, so
+    /// the methods are selected from it during lowering.
+    Builtin(Vec<BuiltinMethod>),
+    /// The dictionary of an instance declared by the user, bound to a local where the instance is
+    /// declared.
+    Local(LocalId),
+}
+
+/// A method of a built-in instance.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BuiltinMethod {
+    /// The method is implemented by a primitive function.
+    Prim(Primitive),
+    /// The method is implemented by a binary operator.
+    BinOp(BinOp),
+}
+
+/// A method selected from the dictionary chosen for one of its uses.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Selection {
+    /// A method of a built-in instance.
+    Builtin(BuiltinMethod),
+    /// The `index`-th of the `len` methods of the dictionary bound to `dict`.
+    Dict {
+        dict: LocalId,
+        index: usize,
+        len: usize,
+    },
+}
+
 pub struct Context<'ast> {
     local_store: Store<LocalId>,
     term_store: Store<TermId>,
     locals: HashMap<LocalId, Local<'ast>>,
+    annotations: HashSet<Location>,
+    pipes: HashSet<TermId>,
+    ty_aliases: HashMap<Location, String>,
+    method_uses: HashMap<TermId, MethodUse>,
+    dicts: HashMap<TermId, Dict>,
+    holes: HashMap<TermId, Vec<LocalId>>,
     ty_gen: Generator<Ty>,
     local_gen: Generator<Local<'ast>>,
 }
@@ -43,6 +113,12 @@ impl<'ast> Context<'ast> {
                 type_info: HashMap::default(),
             },
             locals: HashMap::default(),
+            annotations: HashSet::default(),
+            pipes: HashSet::default(),
+            ty_aliases: HashMap::default(),
+            method_uses: HashMap::default(),
+            dicts: HashMap::default(),
+            holes: HashMap::default(),
             ty_gen: Generator::new(Ty::Var),
             local_gen: Generator::new(Local::Temp),
         }
@@ -81,6 +157,90 @@ impl<'ast> Context<'ast> {
     pub fn get_local(&self, id: LocalId) -> Option<Local<'ast>> {
         self.locals.get(&id).copied()
     }
+
+    /// Stores that there is a type annotation written at `loc`.
+    pub fn save_annotation(&mut self, loc: Location) {
+        self.annotations.insert(loc);
+    }
+
+    /// Returns whether there is a type annotation written at `loc`.
+    ///
+    /// Bindings without annotations have the location of their name as the location of their
+    /// type, so this is used to tell both cases apart.
+    pub fn is_annotation(&self, loc: Location) -> bool {
+        self.annotations.contains(&loc)
+    }
+
+    /// Stores that the application `id` comes from passing the left side of a pipe to its right
+    /// side.
+    pub fn save_pipe(&mut self, id: TermId) {
+        self.pipes.insert(id);
+    }
+
+    /// Returns whether the application `id` comes from a pipe.
+    pub fn is_pipe(&self, id: TermId) -> bool {
+        self.pipes.contains(&id)
+    }
+
+    /// Stores that the type annotation at `loc` was written using the type alias `name`.
+    pub fn save_ty_alias(&mut self, loc: Location, name: String) {
+        self.ty_aliases.insert(loc, name);
+    }
+
+    /// Returns the name of the type alias used by the type annotation at `loc`, if any.
+    pub fn get_ty_alias(&self, loc: Location) -> Option<&str> {
+        self.ty_aliases.get(&loc).map(String::as_str)
+    }
+
+    /// Stores that the term `id` uses a method of a type class.
+    pub fn save_method_use(&mut self, id: TermId, method_use: MethodUse) {
+        self.method_uses.insert(id, method_use);
+    }
+
+    /// Returns the use of a method of a type class done by the term `id`, if any.
+    pub fn get_method_use(&self, id: TermId) -> Option<&MethodUse> {
+        self.method_uses.get(&id)
+    }
+
+    /// Returns the ids of all the terms using a method of a type class.
+    pub fn method_use_ids(&self) -> Vec<TermId> {
+        self.method_uses.keys().copied().collect()
+    }
+
+    /// Stores the dictionary chosen for the use of a method by the term `id`.
+    pub fn save_dict(&mut self, id: TermId, dict: Dict) {
+        self.dicts.insert(id, dict);
+    }
+
+    /// Returns the method used by the term `id` selected from the dictionary chosen for such use,
+    /// if it has been chosen.
+    pub fn get_selection(&self, id: TermId) -> Option<Selection> {
+        let method_use = self.method_uses.get(&id)?;
+        let selection = match self.dicts.get(&id)? {
+            Dict::Builtin(methods) => Selection::Builtin(methods[method_use.method]),
+            Dict::Local(dict) => Selection::Dict {
+                dict: *dict,
+                index: method_use.method,
+                len: method_use.methods,
+            },
+        };
+        Some(selection)
+    }
+
+    /// Stores that the term `id` is a hole with the given locals in scope.
+    pub fn save_hole(&mut self, id: TermId, locals: Vec<LocalId>) {
+        self.holes.insert(id, locals);
+    }
+
+    /// Returns the locals in scope of the hole `id`, if `id` is a hole.
+    pub fn get_hole(&self, id: TermId) -> Option<&[LocalId]> {
+        self.holes.get(&id).map(Vec::as_slice)
+    }
+
+    /// Returns the ids of all the holes.
+    pub fn hole_ids(&self) -> Vec<TermId> {
+        self.holes.keys().copied().collect()
+    }
 }
 
 pub trait ContextExt<Id: Debug + Hash + Eq + Copy> {
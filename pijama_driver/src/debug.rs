@@ -0,0 +1,366 @@
+//! Interactive debugger for programs.
+//!
+//! The debugger stops before the first reduction performed by the machine and then reads
+//! commands to set breakpoints on lines or functions, to go on with the evaluation and to inspect
+//! the current redex and the values bound to the arguments of the last called function. Use the
+//! `help` command to list every command.
+use std::io::{BufRead, Write};
+
+use pijama_ast::{
+    node::{Block, Statement},
+    visitor::NodeVisitor,
+};
+use pijama_common::location::{Located, LocatedError, Location};
+use pijama_lir::Term;
+use pijama_machine::{
+    arithmetic::{Arithmetic, CheckedArithmetic, OverflowArithmetic},
+    env::Env,
+    observer::{Control, Observer, Reduction, Step},
+    MachineBuilder,
+};
+use pijama_parser::parse;
+
+use crate::{run_ast_with_machine, LangResult};
+
+const HELP: &str = "\
+break LINE | break FUNCTION  stop when the evaluation reaches a line or calls a function (b)
+delete N                     delete the N-th breakpoint (d)
+breakpoints                  list the breakpoints
+step                         stop at the next reduction (s)
+next                         stop at the next reduction not nested deeper than this one (n)
+continue                     stop at the next breakpoint (c)
+redex                        show the term about to be reduced (r)
+bindings                     show the values bound to the arguments of the last called function
+where                        show the location of the current reduction (w)
+quit                         stop the evaluation (q)";
+
+/// Runs `input` under the debugger, reading the commands from `commands` and writing both the
+/// output of the program and the output of the debugger to `output`.
+///
+/// The evaluation is stopped if the debugger reads the `quit` command or if it runs out of
+/// commands.
+pub fn debug<R: BufRead + 'static, W: Write>(
+    input: &str,
+    commands: R,
+    output: W,
+    overflow_check: bool,
+) -> LangResult<()> {
+    let ast = parse(input).map_err(LocatedError::kind_into)?;
+    let debugger = Debugger::new(input, &ast, commands);
+    if overflow_check {
+        debug_with(ast, debugger, output, CheckedArithmetic)
+    } else {
+        debug_with(ast, debugger, output, OverflowArithmetic)
+    }
+}
+
+fn debug_with<R: BufRead + 'static, W: Write, A: Arithmetic>(
+    ast: Block,
+    debugger: Debugger<R>,
+    output: W,
+    arithmetic: A,
+) -> LangResult<()> {
+    let machine = MachineBuilder::default()
+        .with_arithmetic(arithmetic)
+        .with_env(Env::new(output))
+        .with_observer(debugger)
+        .build();
+
+    run_ast_with_machine(ast, machine)
+}
+
+/// A function defined in the program.
+struct Function {
+    name: String,
+    /// Location of the definition.
+    loc: Location,
+    params: Vec<String>,
+}
+
+/// Collects every function defined in a program.
+#[derive(Default)]
+struct Functions(Vec<Function>);
+
+impl<'a> NodeVisitor<'a> for Functions {
+    fn visit_statement(&mut self, stat: &Located<Statement<'a>>) {
+        if let Statement::FnDef(name, args, _, _) = &stat.content {
+            self.0.push(Function {
+                name: name.content.to_string(),
+                loc: stat.loc,
+                params: args
+                    .iter()
+                    .map(|arg| arg.item.content.to_string())
+                    .collect(),
+            });
+        }
+        self.super_statement(stat);
+    }
+}
+
+enum Breakpoint {
+    Line(usize),
+    Function(String),
+}
+
+/// When the debugger stops if no breakpoint is reached.
+enum Mode {
+    /// Stop at every reduction.
+    Step,
+    /// Stop at the reductions nested at most this deep.
+    Next(usize),
+    /// Only stop at breakpoints.
+    Continue,
+}
+
+struct Debugger<R: BufRead> {
+    commands: R,
+    source: String,
+    functions: Vec<Function>,
+    /// Deleted breakpoints are kept as `None` so the other ones keep their numbers.
+    breakpoints: Vec<Option<Breakpoint>>,
+    mode: Mode,
+    /// Line of the previous reduction, used to stop only when a line is reached.
+    last_line: Option<usize>,
+    /// Location of the function whose body started to be evaluated after the previous reduction.
+    called: Option<Location>,
+    /// Arguments bound by the last called function.
+    bindings: Vec<(String, Term)>,
+    /// Arguments bound so far by the function being applied.
+    args: Vec<(String, Term)>,
+}
+
+impl<R: BufRead> Debugger<R> {
+    fn new(input: &str, ast: &Block, commands: R) -> Self {
+        let mut functions = Functions::default();
+        functions.visit_block(ast);
+
+        Debugger {
+            commands,
+            source: input.to_owned(),
+            functions: functions.0,
+            breakpoints: Vec::new(),
+            mode: Mode::Step,
+            last_line: None,
+            called: None,
+            bindings: Vec::new(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Records the argument bound by the beta reduction of `redex`, if it belongs to a function
+    /// of the program.
+    ///
+    /// Each function is lowered as one abstraction for each argument with the body of the
+    /// function inside the last one, so the number of abstractions between the one being applied
+    /// and the body tells which argument is bound.
+    fn bind(&mut self, redex: Term) {
+        let (mut term, arg) = match redex {
+            Term::App(abs, arg) => match *abs {
+                Term::Abs(body) => (body, arg),
+                _ => return,
+            },
+            _ => return,
+        };
+
+        let mut nested = 0;
+        while let Term::Abs(body) = *term {
+            term = body;
+            nested += 1;
+        }
+
+        if let Term::Body(loc, _) = *term {
+            if let Some(function) = self.functions.iter().find(|function| function.loc == loc) {
+                if nested < function.params.len() {
+                    let index = function.params.len() - 1 - nested;
+                    if index == 0 {
+                        self.args.clear();
+                    }
+                    self.args.push((function.params[index].clone(), *arg));
+                }
+            }
+        }
+    }
+
+    /// Returns the number of the breakpoint reached by a reduction in `line`, if any.
+    fn reached_breakpoint(&self, line: Option<usize>) -> Option<usize> {
+        let index = self
+            .breakpoints
+            .iter()
+            .position(|breakpoint| match breakpoint {
+                Some(Breakpoint::Line(bp_line)) => line == Some(*bp_line) && self.last_line != line,
+                Some(Breakpoint::Function(name)) => matches!(self.called, Some(loc) if {
+                    self.functions
+                        .iter()
+                        .any(|function| function.loc == loc && function.name == *name)
+                }),
+                None => false,
+            })?;
+        Some(index + 1)
+    }
+
+    /// Describes where the evaluation stopped.
+    fn position(&self, step: &Step) -> String {
+        let mut text = match step.loc {
+            Some(loc) => {
+                let (line, column) = loc.line_column(&self.source);
+                let code = self.source.lines().nth(line - 1).unwrap_or_default();
+                format!("at {}:{}\n{:>4} | {}\n", line, column, line, code)
+            }
+            None => "at an unknown location\n".to_owned(),
+        };
+        text.push_str(&format!("{} {}", step.reduction, step.redex()));
+        text
+    }
+
+    fn add_breakpoint(&mut self, target: &str) -> String {
+        let breakpoint = match target.parse::<usize>() {
+            Ok(line) if line > 0 => Breakpoint::Line(line),
+            Ok(_) => return "lines start at 1".to_owned(),
+            Err(_)
+                if self
+                    .functions
+                    .iter()
+                    .any(|function| function.name == target) =>
+            {
+                Breakpoint::Function(target.to_owned())
+            }
+            Err(_) => return format!("there is no function named `{}`", target),
+        };
+        self.breakpoints.push(Some(breakpoint));
+        format!(
+            "breakpoint {} at {}",
+            self.breakpoints.len(),
+            describe(self.breakpoints.last().unwrap().as_ref().unwrap())
+        )
+    }
+
+    fn delete_breakpoint(&mut self, number: &str) -> String {
+        match number
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| self.breakpoints.get_mut(number.checked_sub(1)?))
+        {
+            Some(breakpoint @ Some(_)) => {
+                *breakpoint = None;
+                format!("deleted breakpoint {}", number)
+            }
+            _ => format!("there is no breakpoint {}", number),
+        }
+    }
+
+    fn list_breakpoints(&self) -> String {
+        let list = self
+            .breakpoints
+            .iter()
+            .enumerate()
+            .filter_map(|(index, breakpoint)| {
+                let breakpoint = breakpoint.as_ref()?;
+                Some(format!("{}: {}", index + 1, describe(breakpoint)))
+            })
+            .collect::<Vec<_>>();
+
+        if list.is_empty() {
+            "there are no breakpoints".to_owned()
+        } else {
+            list.join("\n")
+        }
+    }
+
+    fn list_bindings(&self) -> String {
+        if self.bindings.is_empty() {
+            "there are no bindings".to_owned()
+        } else {
+            self.bindings
+                .iter()
+                .map(|(name, value)| format!("{} = {}", name, value))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+
+    /// Reads and runs commands until one of them resumes or stops the evaluation.
+    fn prompt(&mut self, step: &Step, output: &mut dyn Write) -> Control {
+        loop {
+            write!(output, "(pjdb) ").expect("Debugger output failed");
+            output.flush().expect("Debugger output failed");
+
+            let mut line = String::new();
+            if self.commands.read_line(&mut line).unwrap_or(0) == 0 {
+                writeln!(output).expect("Debugger output failed");
+                return Control::Stop;
+            }
+
+            let mut words = line.split_whitespace();
+            let reply = match (words.next(), words.next(), words.next()) {
+                (None, _, _) => continue,
+                (Some("step") | Some("s"), None, _) => {
+                    self.mode = Mode::Step;
+                    return Control::Continue;
+                }
+                (Some("next") | Some("n"), None, _) => {
+                    self.mode = Mode::Next(step.depth);
+                    return Control::Continue;
+                }
+                (Some("continue") | Some("c"), None, _) => {
+                    self.mode = Mode::Continue;
+                    return Control::Continue;
+                }
+                (Some("quit") | Some("q"), None, _) => return Control::Stop,
+                (Some("break") | Some("b"), Some(target), None) => self.add_breakpoint(target),
+                (Some("delete") | Some("d"), Some(number), None) => self.delete_breakpoint(number),
+                (Some("breakpoints"), None, _) => self.list_breakpoints(),
+                (Some("redex") | Some("r"), None, _) => step.redex().to_string(),
+                (Some("bindings"), None, _) => self.list_bindings(),
+                (Some("where") | Some("w"), None, _) => self.position(step),
+                (Some("help") | Some("h"), None, _) => HELP.to_owned(),
+                _ => format!(
+                    "unknown command `{}`, use `help` to list the commands",
+                    line.trim()
+                ),
+            };
+            writeln!(output, "{}", reply).expect("Debugger output failed");
+        }
+    }
+}
+
+impl<R: BufRead> Observer for Debugger<R> {
+    fn reduce(&mut self, step: &Step, output: &mut dyn Write) -> Control {
+        if step.reduction == Reduction::Beta {
+            self.bind(step.redex());
+        }
+
+        let line = step.loc.map(|loc| loc.line_column(&self.source).0);
+        let breakpoint = self.reached_breakpoint(line);
+        self.last_line = line;
+        self.called = None;
+
+        let stop = breakpoint.is_some()
+            || match self.mode {
+                Mode::Step => true,
+                Mode::Next(depth) => step.depth <= depth,
+                Mode::Continue => false,
+            };
+        if !stop {
+            return Control::Continue;
+        }
+
+        let header = match breakpoint {
+            Some(number) => format!("breakpoint {}, stopped ", number),
+            None => "stopped ".to_owned(),
+        };
+        writeln!(output, "{}{}", header, self.position(step)).expect("Debugger output failed");
+        self.prompt(step, output)
+    }
+
+    fn call(&mut self, loc: Location) {
+        self.called = Some(loc);
+        self.bindings = std::mem::take(&mut self.args);
+    }
+}
+
+fn describe(breakpoint: &Breakpoint) -> String {
+    match breakpoint {
+        Breakpoint::Line(line) => format!("line {}", line),
+        Breakpoint::Function(name) => format!("function `{}`", name),
+    }
+}
@@ -0,0 +1,289 @@
+//! Documentation generator for the top-level functions of a program.
+//!
+//! Every top-level function is listed with its signature, using the types found by the type
+//! checker, the text of its doc comment, a link to its definition and links to the other
+//! top-level functions it calls or is called by. The output only depends on the input, so it can
+//! be compared against a snapshot.
+use std::{collections::HashMap, fmt::Write, str::FromStr};
+
+use pijama_ast::{
+    node::{Node, Statement},
+    visitor::NodeVisitor,
+};
+use pijama_common::{
+    location::{LocatedError, Location},
+    Local,
+};
+use pijama_ctx::{Context, ContextExt, LocalId};
+use pijama_parser::parse;
+use pijama_ty::Ty;
+use pijama_tycheck::ty_check;
+
+use crate::LangResult;
+
+/// The format of the generated documentation.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DocFormat {
+    Html,
+    Markdown,
+}
+
+impl FromStr for DocFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "html" => Ok(DocFormat::Html),
+            "markdown" | "md" => Ok(DocFormat::Markdown),
+            _ => Err(format!("`{}` is not a documentation format", s)),
+        }
+    }
+}
+
+/// The documentation of a top-level function.
+struct FnDoc<'a> {
+    name: &'a str,
+    /// Unique identifier of the function inside the generated document.
+    anchor: String,
+    signature: String,
+    text: String,
+    line: usize,
+    /// Indices of the top-level functions called by this function.
+    calls: Vec<usize>,
+    /// Indices of the top-level functions calling this function.
+    called_by: Vec<usize>,
+}
+
+/// Generates the documentation of the program `input` stored in `path`.
+///
+/// The program must type-check because the signatures use the inferred types.
+pub fn generate_doc(input: &str, path: &str, format: DocFormat) -> LangResult<String> {
+    let ast = parse(input).map_err(LocatedError::kind_into)?;
+
+    let mut defs = Vec::new();
+    for node in &ast.nodes {
+        if let Node::Stat(stat) = node {
+            if let Statement::FnDef(name, args, body, doc) = &stat.content {
+                let name_str = match name.content {
+                    Local::Name(name) => name,
+                    _ => continue,
+                };
+                let args = args
+                    .iter()
+                    .map(|arg| arg.item.content.to_string())
+                    .collect::<Vec<_>>();
+                let mut names = UsedNames::default();
+                names.visit_block(&body.item);
+                names.0.retain(|used| !args.iter().any(|arg| arg == used));
+                let text = doc.as_ref().map(|doc| doc.text()).unwrap_or_default();
+                defs.push((name_str, name.loc, args, names.0, text));
+            }
+        }
+    }
+
+    let mut ctx = Context::new();
+    let hir = pijama_hir::lower_ast(&mut ctx, ast).map_err(LocatedError::kind_into)?;
+    ty_check(&hir, &mut ctx).map_err(LocatedError::kind_into)?;
+    let tys = local_tys(&mut ctx);
+
+    let mut fns = Vec::with_capacity(defs.len());
+    for (index, (name, loc, args, _, text)) in defs.iter().enumerate() {
+        let repeated = defs[..index].iter().filter(|def| def.0 == *name).count();
+        let anchor = if repeated == 0 {
+            name.to_string()
+        } else {
+            format!("{}-{}", name, repeated + 1)
+        };
+        let signature = match tys.get(loc) {
+            Some(ty) => signature(name, args, ty),
+            None => format!("fn {}({})", name, args.join(", ")),
+        };
+        fns.push(FnDoc {
+            name,
+            anchor,
+            signature,
+            text: text.clone(),
+            line: input[..loc.start].matches('\n').count() + 1,
+            calls: Vec::new(),
+            called_by: Vec::new(),
+        });
+    }
+
+    for (index, (_, _, _, used, _)) in defs.iter().enumerate() {
+        for used in used {
+            if let Some(callee) = resolve(&fns, used, index) {
+                if callee != index && !fns[index].calls.contains(&callee) {
+                    fns[index].calls.push(callee);
+                    fns[callee].called_by.push(index);
+                }
+            }
+        }
+    }
+    for fn_doc in &mut fns {
+        fn_doc.calls.sort_unstable();
+        fn_doc.called_by.sort_unstable();
+    }
+
+    Ok(match format {
+        DocFormat::Html => render_html(&fns, path),
+        DocFormat::Markdown => render_markdown(&fns, path),
+    })
+}
+
+/// Returns the type of every local that has a location, indexed by its location.
+fn local_tys(ctx: &mut Context) -> HashMap<Location, Ty> {
+    let ids = ctx
+        .iter_mut_local_types()
+        .map(|(id, _)| id)
+        .collect::<Vec<LocalId>>();
+
+    ids.into_iter()
+        .filter_map(|id| Some((ctx.get_location(id)?, ctx.get_type_info(id)?.ty.clone())))
+        .collect()
+}
+
+/// Returns the index of the top-level function that `name` refers to inside the body of the
+/// `index`-th function.
+///
+/// This is the closest previous function with such name or, if there is none, the first
+/// following one, which can only be used if both are defined next to each other.
+fn resolve(fns: &[FnDoc], name: &str, index: usize) -> Option<usize> {
+    (0..index)
+        .rev()
+        .chain(index..fns.len())
+        .find(|&other| fns[other].name == name)
+}
+
+/// Writes the signature of a function using the types of its arguments and return value.
+fn signature(name: &str, args: &[String], mut ty: &Ty) -> String {
+    let mut params = Vec::with_capacity(args.len());
+    for arg in args {
+        match ty {
+            Ty::Arrow(arg_ty, ret_ty) => {
+                params.push(format!("{}: {}", arg, arg_ty));
+                ty = ret_ty;
+            }
+            _ => params.push(arg.clone()),
+        }
+    }
+    format!("fn {}({}): {}", name, params.join(", "), ty)
+}
+
+/// Collects the names used inside a function body in order of appearance.
+#[derive(Default)]
+struct UsedNames<'a>(Vec<&'a str>);
+
+impl<'a> NodeVisitor<'a> for UsedNames<'a> {
+    fn visit_local(&mut self, name: &Local<'a>) {
+        if let Local::Name(name) = name {
+            if !self.0.contains(name) {
+                self.0.push(name);
+            }
+        }
+    }
+}
+
+fn render_markdown(fns: &[FnDoc], path: &str) -> String {
+    let link = |index: usize| format!("[`{}`](#{})", fns[index].name, fns[index].anchor);
+    let links = |indices: &[usize]| indices.iter().map(|&i| link(i)).collect::<Vec<_>>();
+
+    let mut out = String::new();
+    writeln!(out, "# Documentation for `{}`\n", path).unwrap();
+    writeln!(out, "## Functions\n").unwrap();
+    for index in 0..fns.len() {
+        writeln!(out, "- {}", link(index)).unwrap();
+    }
+
+    for fn_doc in fns {
+        writeln!(out, "\n<a id=\"{}\"></a>\n", fn_doc.anchor).unwrap();
+        writeln!(out, "### `{}`\n", fn_doc.name).unwrap();
+        writeln!(out, "```elixir\n{}\n```\n", fn_doc.signature).unwrap();
+        if !fn_doc.text.is_empty() {
+            writeln!(out, "{}\n", fn_doc.text).unwrap();
+        }
+        if !fn_doc.calls.is_empty() {
+            writeln!(out, "Calls: {}\n", links(&fn_doc.calls).join(", ")).unwrap();
+        }
+        if !fn_doc.called_by.is_empty() {
+            writeln!(out, "Called by: {}\n", links(&fn_doc.called_by).join(", ")).unwrap();
+        }
+        writeln!(out, "[Source]({}#L{})", path, fn_doc.line).unwrap();
+    }
+
+    out
+}
+
+fn render_html(fns: &[FnDoc], path: &str) -> String {
+    let link = |index: usize| {
+        format!(
+            "<a href=\"#{}\"><code>{}</code></a>",
+            escape(&fns[index].anchor),
+            escape(fns[index].name)
+        )
+    };
+    let links = |indices: &[usize]| indices.iter().map(|&i| link(i)).collect::<Vec<_>>();
+
+    let mut out = String::new();
+    writeln!(out, "<!DOCTYPE html>\n<html>\n<head>").unwrap();
+    writeln!(out, "<meta charset=\"utf-8\">").unwrap();
+    writeln!(out, "<title>Documentation for {}</title>", escape(path)).unwrap();
+    writeln!(out, "</head>\n<body>").unwrap();
+    writeln!(
+        out,
+        "<h1>Documentation for <code>{}</code></h1>",
+        escape(path)
+    )
+    .unwrap();
+    writeln!(out, "<h2>Functions</h2>\n<ul>").unwrap();
+    for index in 0..fns.len() {
+        writeln!(out, "<li>{}</li>", link(index)).unwrap();
+    }
+    writeln!(out, "</ul>").unwrap();
+
+    for fn_doc in fns {
+        writeln!(out, "<section id=\"{}\">", escape(&fn_doc.anchor)).unwrap();
+        writeln!(out, "<h3><code>{}</code></h3>", escape(fn_doc.name)).unwrap();
+        writeln!(out, "<pre><code>{}</code></pre>", escape(&fn_doc.signature)).unwrap();
+        // Paragraphs are separated by empty lines like in Markdown.
+        for paragraph in fn_doc.text.split("\n\n").filter(|p| !p.trim().is_empty()) {
+            writeln!(out, "<p>{}</p>", escape(paragraph.trim())).unwrap();
+        }
+        if !fn_doc.calls.is_empty() {
+            writeln!(out, "<p>Calls: {}</p>", links(&fn_doc.calls).join(", ")).unwrap();
+        }
+        if !fn_doc.called_by.is_empty() {
+            writeln!(
+                out,
+                "<p>Called by: {}</p>",
+                links(&fn_doc.called_by).join(", ")
+            )
+            .unwrap();
+        }
+        writeln!(
+            out,
+            "<p><a href=\"{}#L{}\">Source</a></p>",
+            escape(path),
+            fn_doc.line
+        )
+        .unwrap();
+        writeln!(out, "</section>").unwrap();
+    }
+
+    writeln!(out, "</body>\n</html>").unwrap();
+    out
+}
+
+/// Escapes the characters with a special meaning in HTML.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,193 @@
+//! Expectations about the result of running a program, written as comments in its header.
+//!
+//! The header of a program is made of the comment lines at its start. The following comments in
+//! the header state the expected result:
+//!
+//! - `# expect-output: <line>` states a line of the output. The program must not fail unless
+//!   there is also an `# expect-panic` comment. A single `# expect-output` comment without a
+//!   colon states that the program prints nothing.
+//! - `# expect-error: <Stage>::<Kind> at <line>:<column>` states that the program cannot
+//!   be compiled because of an error, like `Ty::Mismatch at 3:5`.
+//! - `# expect-panic` states that the program panics, `# expect-panic: <message>` also states
+//!   the message of the panic.
+use std::{
+    fmt::Debug,
+    panic::{self, AssertUnwindSafe},
+};
+
+use pijama_machine::{arithmetic::CheckedArithmetic, env::Env, MachineBuilder};
+
+use crate::{run_with_machine, LangErrorKind};
+
+const OUTPUT: &str = "# expect-output";
+const ERROR: &str = "# expect-error";
+const PANIC: &str = "# expect-panic";
+
+/// The expected or actual result of running a program.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    /// The program printed this output.
+    Output(String),
+    /// The program could not be compiled because of an error, written as
+    /// `<Stage>::<Kind> at <line>:<column>`.
+    Error(String),
+    /// The program printed some output and then panicked with a message. The expected message
+    /// may be missing to match any message.
+    Panic {
+        output: String,
+        message: Option<String>,
+    },
+}
+
+impl Outcome {
+    /// Checks if the `actual` outcome of a program fulfills this expected outcome.
+    pub fn matches(&self, actual: &Outcome) -> bool {
+        match (self, actual) {
+            (
+                Outcome::Panic { output, message },
+                Outcome::Panic {
+                    output: actual_output,
+                    message: actual_message,
+                },
+            ) => output == actual_output && (message.is_none() || message == actual_message),
+            _ => self == actual,
+        }
+    }
+
+    /// Returns the header comments stating this outcome.
+    fn header(&self) -> Vec<String> {
+        let output_lines = |output: &str| {
+            output
+                .lines()
+                .map(|line| format!("{}: {}", OUTPUT, line).trim_end().to_owned())
+                .collect::<Vec<_>>()
+        };
+
+        match self {
+            Outcome::Output(output) if output.is_empty() => vec![OUTPUT.to_owned()],
+            Outcome::Output(output) => output_lines(output),
+            Outcome::Error(error) => vec![format!("{}: {}", ERROR, error)],
+            Outcome::Panic { output, message } => {
+                let mut header = output_lines(output);
+                header.push(match message {
+                    Some(message) => format!("{}: {}", PANIC, message),
+                    None => PANIC.to_owned(),
+                });
+                header
+            }
+        }
+    }
+}
+
+/// Returns the lines of the header of `input`.
+fn header_lines(input: &str) -> impl Iterator<Item = &str> {
+    input.lines().take_while(|line| line.starts_with('#'))
+}
+
+/// Returns the outcome expected by the header of `input`, if the header states any expectation.
+pub fn expectation(input: &str) -> Option<Outcome> {
+    let mut output = String::new();
+    let mut has_output = false;
+    let mut error = None;
+    let mut panic = None;
+
+    for line in header_lines(input) {
+        if line == OUTPUT {
+            has_output = true;
+        } else if let Some(line) = line.strip_prefix(OUTPUT).and_then(value) {
+            has_output = true;
+            output.push_str(line);
+            output.push('\n');
+        } else if let Some(kind) = line.strip_prefix(ERROR).and_then(value) {
+            error = Some(kind.to_owned());
+        } else if line == PANIC {
+            panic = Some(None);
+        } else if let Some(message) = line.strip_prefix(PANIC).and_then(value) {
+            panic = Some(Some(message.to_owned()));
+        }
+    }
+
+    match (error, panic) {
+        (Some(error), _) => Some(Outcome::Error(error)),
+        (None, Some(message)) => Some(Outcome::Panic { output, message }),
+        (None, None) if has_output => Some(Outcome::Output(output)),
+        (None, None) => None,
+    }
+}
+
+/// Returns the value of a header comment after its colon.
+fn value(rest: &str) -> Option<&str> {
+    let value = rest.strip_prefix(':')?;
+    Some(value.strip_prefix(' ').unwrap_or(value))
+}
+
+/// Compiles and runs `input`, panicking on integer overflow, and returns its outcome.
+///
+/// The panic hook is still called if the program panics.
+pub fn run_program(input: &str) -> Outcome {
+    let mut output = Vec::new();
+    let machine = MachineBuilder::default()
+        .with_arithmetic(CheckedArithmetic)
+        .with_env(Env::new(&mut output));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_with_machine(input, machine.build())
+    }));
+    let output = String::from_utf8_lossy(&output).into_owned();
+
+    match result {
+        Ok(Ok(())) => Outcome::Output(output),
+        Ok(Err(err)) => {
+            let (line, column) = err.loc().line_column(input);
+            let kind = kind_name(err.kind());
+            Outcome::Error(format!("{} at {}:{}", kind, line, column))
+        }
+        Err(payload) => {
+            let message = match payload.downcast::<String>() {
+                Ok(message) => Some(*message),
+                Err(payload) => payload.downcast::<&str>().ok().map(|m| (*m).to_owned()),
+            };
+            Outcome::Panic { output, message }
+        }
+    }
+}
+
+/// Returns `input` with its expectations replaced by the ones stating `outcome`.
+///
+/// Other comments in the header are kept after the expectations.
+pub fn bless(input: &str, outcome: &Outcome) -> String {
+    let header_len = header_lines(input).count();
+    let mut blessed = outcome.header().join("\n");
+    blessed.push('\n');
+    for (index, line) in input.split_inclusive('\n').enumerate() {
+        if index >= header_len || !is_expectation(line.trim_end()) {
+            blessed.push_str(line);
+        }
+    }
+    blessed
+}
+
+/// Checks if a line of the header is an expectation.
+fn is_expectation(line: &str) -> bool {
+    [OUTPUT, ERROR, PANIC].iter().any(|prefix| {
+        matches!(line.strip_prefix(prefix), Some(rest) if rest.is_empty() || rest.starts_with(':'))
+    })
+}
+
+/// Returns the name of an error kind, like `Ty::Mismatch`.
+fn kind_name(kind: &LangErrorKind) -> String {
+    fn variant(kind: &impl Debug) -> String {
+        let debug = format!("{:?}", kind);
+        debug
+            .split(|c: char| !c.is_alphanumeric())
+            .next()
+            .unwrap()
+            .to_owned()
+    }
+
+    match kind {
+        LangErrorKind::Ty(kind) => format!("Ty::{}", variant(kind)),
+        LangErrorKind::Parse(kind) => format!("Parse::{}", variant(kind)),
+        LangErrorKind::Lower(kind) => format!("Lower::{}", variant(kind)),
+    }
+}
@@ -0,0 +1,45 @@
+//! Long-form explanations for the error codes.
+//!
+//! Each error kind has a stable code (like `E0001`) that is shown alongside its diagnostic. Codes
+//! are grouped by the stage raising them: `E00xx` for parsing, `E01xx` for lowering and `E02xx`
+//! for type-checking. Codes must never be reused, even if the error they belonged to is removed.
+
+/// Explanations for every error code, sorted by code.
+const EXPLANATIONS: &[(&str, &str)] = &[
+    ("E0001", include_str!("E0001.md")),
+    ("E0002", include_str!("E0002.md")),
+    ("E0003", include_str!("E0003.md")),
+    ("E0004", include_str!("E0004.md")),
+    ("E0005", include_str!("E0005.md")),
+    ("E0006", include_str!("E0006.md")),
+    ("E0007", include_str!("E0007.md")),
+    ("E0101", include_str!("E0101.md")),
+    ("E0102", include_str!("E0102.md")),
+    ("E0103", include_str!("E0103.md")),
+    ("E0104", include_str!("E0104.md")),
+    ("E0105", include_str!("E0105.md")),
+    ("E0106", include_str!("E0106.md")),
+    ("E0107", include_str!("E0107.md")),
+    ("E0108", include_str!("E0108.md")),
+    // E0109 is no longer raised.
+    ("E0201", include_str!("E0201.md")),
+    ("E0202", include_str!("E0202.md")),
+    ("E0203", include_str!("E0203.md")),
+    ("E0204", include_str!("E0204.md")),
+    ("E0205", include_str!("E0205.md")),
+];
+
+/// Returns the explanation for an error code, if such code exists.
+///
+/// Codes are case-insensitive, so `e0001` and `E0001` have the same explanation.
+pub fn explain(code: &str) -> Option<&'static str> {
+    EXPLANATIONS
+        .iter()
+        .find(|(code2, _)| code2.eq_ignore_ascii_case(code))
+        .map(|(_, explanation)| *explanation)
+}
+
+/// Returns an iterator over all the error codes with an explanation.
+pub fn codes() -> impl Iterator<Item = &'static str> {
+    EXPLANATIONS.iter().map(|(code, _)| *code)
+}
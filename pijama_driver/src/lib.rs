@@ -2,17 +2,25 @@ use thiserror::Error;
 
 use std::io::Write;
 
+use pijama_ast::node::Block;
 use pijama_common::location::LocatedError;
 use pijama_ctx::Context;
 use pijama_hir::LowerErrorKind;
 use pijama_lir::Term as LirTerm;
 use pijama_machine::{
     arithmetic::{Arithmetic, CheckedArithmetic, OverflowArithmetic},
-    Machine, MachineBuilder,
+    trace::Trace,
+    Failure, Machine, MachineBuilder,
 };
 use pijama_parser::{parse, ParsingErrorKind};
 use pijama_tycheck::{ty_check, TyErrorKind};
 
+pub mod debug;
+pub mod doc;
+pub mod expect;
+pub mod explain;
+pub mod testing;
+
 pub type LangResult<T> = Result<T, LangError>;
 
 pub type LangError = LocatedError<LangErrorKind>;
@@ -27,18 +35,54 @@ pub enum LangErrorKind {
     Lower(#[from] LowerErrorKind),
 }
 
+impl LangErrorKind {
+    /// Returns the stable code identifying this kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LangErrorKind::Ty(kind) => kind.code(),
+            LangErrorKind::Parse(kind) => kind.code(),
+            LangErrorKind::Lower(kind) => kind.code(),
+        }
+    }
+
+    /// Returns a hint on how to fix this error, if there is one.
+    pub fn help(&self) -> Option<String> {
+        match self {
+            LangErrorKind::Lower(kind) => kind.help(),
+            LangErrorKind::Ty(kind) => kind.help(),
+            LangErrorKind::Parse(kind) => kind.help(),
+        }
+    }
+}
+
 pub fn run_with_machine<W: Write, A: Arithmetic>(
     input: &str,
-    mut machine: Machine<W, A>,
+    machine: Machine<W, A>,
 ) -> LangResult<()> {
     let ast = parse(input).map_err(LocatedError::kind_into)?;
+    run_ast_with_machine(ast, machine)
+}
+
+/// Runs the already parsed program `ast` like `run_with_machine`.
+pub(crate) fn run_ast_with_machine<W: Write, A: Arithmetic>(
+    ast: Block,
+    mut machine: Machine<W, A>,
+) -> LangResult<()> {
     let mut ctx = Context::new();
     let hir = pijama_hir::lower_ast(&mut ctx, ast).map_err(LocatedError::kind_into)?;
     let _ty = ty_check(&hir, &mut ctx).map_err(LocatedError::kind_into)?;
     let _mir = pijama_mir::Term::from_hir(&hir, &mut ctx);
-    let lir = LirTerm::from_hir(&ctx, hir);
-    let _res = machine.evaluate(lir);
-    Ok(())
+    // Observed machines can show where each reduction comes from.
+    let lir = if machine.is_observed() {
+        LirTerm::from_hir_with_locations(&ctx, hir)
+    } else {
+        LirTerm::from_hir(&ctx, hir)
+    };
+    match machine.evaluate(lir) {
+        Ok(_) | Err(Failure::Stopped) => Ok(()),
+        // Failed assertions outside of tests stop the program like any other runtime error.
+        Err(failure) => panic!("{}", failure),
+    }
 }
 
 pub fn run(input: &str, overflow_check: bool) -> LangResult<()> {
@@ -54,3 +98,20 @@ pub fn run(input: &str, overflow_check: bool) -> LangResult<()> {
         run_with_machine(input, machine)
     }
 }
+
+/// Runs `input` logging every reduction performed by the machine as explained in `Trace`.
+pub fn run_traced(input: &str, overflow_check: bool, trace: Trace) -> LangResult<()> {
+    if overflow_check {
+        let machine = MachineBuilder::default()
+            .with_arithmetic(CheckedArithmetic)
+            .with_trace(trace)
+            .build();
+        run_with_machine(input, machine)
+    } else {
+        let machine = MachineBuilder::default()
+            .with_arithmetic(OverflowArithmetic)
+            .with_trace(trace)
+            .build();
+        run_with_machine(input, machine)
+    }
+}
@@ -0,0 +1,177 @@
+//! Runner for the tests declared in a program.
+//!
+//! Each test runs in isolation with its own machine. The program used to run a test is made of
+//! every node before the test followed by the body of the test, so the test can use any
+//! definition above it.
+use std::panic::{self, AssertUnwindSafe};
+
+use pijama_ast::node::{Block, Node, Statement};
+use pijama_common::location::{LocatedError, Location};
+use pijama_ctx::Context;
+use pijama_lir::Term as LirTerm;
+use pijama_machine::{
+    arithmetic::{Arithmetic, CheckedArithmetic, OverflowArithmetic},
+    env::Env,
+    Failure, MachineBuilder,
+};
+use pijama_parser::parse;
+use pijama_tycheck::ty_check;
+
+use crate::LangResult;
+
+/// A test declared in a program.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Test {
+    pub name: String,
+    /// Location of the name of the test.
+    pub loc: Location,
+}
+
+/// The reason why a test failed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TestFailure {
+    /// Location of the assertion that failed, if the test failed because of an assertion.
+    pub loc: Option<Location>,
+    pub message: String,
+}
+
+/// The result of running a test.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TestReport {
+    pub test: Test,
+    pub result: Result<(), TestFailure>,
+    /// Everything printed by the test.
+    pub output: String,
+}
+
+/// Returns the tests declared in `input` in the same order as in the source code.
+///
+/// The whole program is type-checked so the errors are reported before running any test.
+pub fn find_tests(input: &str) -> LangResult<Vec<Test>> {
+    let ast = parse(input).map_err(LocatedError::kind_into)?;
+    let tests = ast
+        .nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Stat(stat) => match &stat.content {
+                Statement::Test(name, _) => Some(Test {
+                    name: name.content.to_owned(),
+                    loc: name.loc,
+                }),
+                _ => None,
+            },
+            Node::Expr(_) => None,
+        })
+        .collect();
+
+    let mut ctx = Context::new();
+    let hir = pijama_hir::lower_ast(&mut ctx, ast).map_err(LocatedError::kind_into)?;
+    ty_check(&hir, &mut ctx).map_err(LocatedError::kind_into)?;
+
+    Ok(tests)
+}
+
+/// Runs the tests declared in `input` whose name contains `filter`, or every test if there is no
+/// filter.
+pub fn run_tests(
+    input: &str,
+    filter: Option<&str>,
+    overflow_check: bool,
+) -> LangResult<Vec<TestReport>> {
+    let mut reports = Vec::new();
+
+    for (index, test) in find_tests(input)?.into_iter().enumerate() {
+        if let Some(filter) = filter {
+            if !test.name.contains(filter) {
+                continue;
+            }
+        }
+
+        let lir = lower_test(input, index)?;
+        let mut output = Vec::new();
+        let result = if overflow_check {
+            evaluate(lir, CheckedArithmetic, &mut output)
+        } else {
+            evaluate(lir, OverflowArithmetic, &mut output)
+        };
+
+        reports.push(TestReport {
+            test,
+            result,
+            output: String::from_utf8_lossy(&output).into_owned(),
+        });
+    }
+
+    Ok(reports)
+}
+
+/// Lowers the program running the `index`-th test of `input`.
+fn lower_test(input: &str, index: usize) -> LangResult<LirTerm> {
+    let mut ast = parse(input).map_err(LocatedError::kind_into)?;
+
+    let position = ast
+        .nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| {
+            matches!(
+                node,
+                Node::Stat(stat) if matches!(stat.content, Statement::Test(..))
+            )
+        })
+        .nth(index)
+        .map(|(position, _)| position)
+        .unwrap();
+
+    ast.nodes.truncate(position + 1);
+    let body = match ast.nodes.pop_back() {
+        Some(Node::Stat(stat)) => match stat.content {
+            Statement::Test(_, body) => body,
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    };
+    let ast = Block {
+        nodes: ast.nodes.into_iter().chain(body.nodes).collect(),
+        expr: body.expr,
+    };
+
+    let mut ctx = Context::new();
+    let hir = pijama_hir::lower_ast(&mut ctx, ast).map_err(LocatedError::kind_into)?;
+    ty_check(&hir, &mut ctx).map_err(LocatedError::kind_into)?;
+    Ok(LirTerm::from_hir(&ctx, hir))
+}
+
+/// Evaluates a test in a new machine writing to `output`.
+///
+/// Panics during the evaluation, like the ones caused by overflows, are also failures. The panic
+/// hook is still called if the test panics.
+fn evaluate<A: Arithmetic>(
+    lir: LirTerm,
+    arithmetic: A,
+    output: &mut Vec<u8>,
+) -> Result<(), TestFailure> {
+    let mut machine = MachineBuilder::default()
+        .with_arithmetic(arithmetic)
+        .with_env(Env::new(output))
+        .build();
+
+    match panic::catch_unwind(AssertUnwindSafe(|| machine.evaluate(lir))) {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(Failure::Assertion { loc, message })) => Err(TestFailure {
+            loc: Some(loc),
+            message,
+        }),
+        Ok(Err(Failure::Stopped)) => unreachable!("tests are evaluated without observers"),
+        Err(payload) => {
+            let message = match payload.downcast::<String>() {
+                Ok(message) => *message,
+                Err(payload) => match payload.downcast::<&str>() {
+                    Ok(message) => (*message).to_owned(),
+                    Err(_) => "the test panicked".to_owned(),
+                },
+            };
+            Err(TestFailure { loc: None, message })
+        }
+    }
+}
@@ -2,23 +2,83 @@ use crate::test_type;
 
 use pijama_driver::LangErrorKind;
 use pijama_hir::LowerErrorKind;
+use pijama_ty::Ty;
+use pijama_tycheck::TyErrorKind;
+
+// `foo` only calls itself, so its return type cannot be inferred.
+#[test]
+fn detect_recursion_after_shadowing() {
+    let input = include_str!("detect_recursion_after_shadowing.pj");
+    let ty = crate::type_check::type_check(input);
+    match ty.as_ref().map_err(|err| err.kind()) {
+        Err(LangErrorKind::Ty(TyErrorKind::NotConcrete {
+            ty: Ty::Arrow(arg, ret),
+            name: Some(name),
+        })) if **arg == Ty::Int && matches!(**ret, Ty::Var(_)) && name == "foo" => (),
+        _ => panic!("{:#?}", ty),
+    }
+}
+
+test_type!(
+    anon_fn_wrong_return_ty,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Bool,
+        found: Ty::Int,
+    }))
+);
+
+test_type!(
+    unbounded_suggests_fn,
+    Err(&LangErrorKind::Lower(LowerErrorKind::Unbounded {
+        name: "fatc".to_owned(),
+        suggestion: Some("fact".to_owned()),
+    }))
+);
+
+test_type!(
+    unbounded_suggests_arg,
+    Err(&LangErrorKind::Lower(LowerErrorKind::Unbounded {
+        name: "rigth".to_owned(),
+        suggestion: Some("right".to_owned()),
+    }))
+);
+
+test_type!(
+    unbounded_suggests_print,
+    Err(&LangErrorKind::Lower(LowerErrorKind::Unbounded {
+        name: "prnt".to_owned(),
+        suggestion: Some("print".to_owned()),
+    }))
+);
 
 test_type!(
-    detect_indirect_recursion,
-    Err(&LangErrorKind::Lower(LowerErrorKind::RequiredTy))
+    unbounded_suggests_innermost,
+    Err(&LangErrorKind::Lower(LowerErrorKind::Unbounded {
+        name: "fo".to_owned(),
+        suggestion: Some("fon".to_owned()),
+    }))
 );
 
 test_type!(
-    detect_recursion_after_shadowing,
-    Err(&LangErrorKind::Lower(LowerErrorKind::RequiredTy))
+    unbounded_no_suggestion,
+    Err(&LangErrorKind::Lower(LowerErrorKind::Unbounded {
+        name: "factorial".to_owned(),
+        suggestion: None,
+    }))
 );
 
 test_type!(
-    detect_recursion_after_shadowing_2,
-    Err(&LangErrorKind::Lower(LowerErrorKind::RequiredTy))
+    unbounded_out_of_scope,
+    Err(&LangErrorKind::Lower(LowerErrorKind::Unbounded {
+        name: "valeu".to_owned(),
+        suggestion: None,
+    }))
 );
 
 test_type!(
-    detect_recursion_inside_functions,
-    Err(&LangErrorKind::Lower(LowerErrorKind::RequiredTy))
+    non_adjacent_fn_forward_use,
+    Err(&LangErrorKind::Lower(LowerErrorKind::Unbounded {
+        name: "bar".to_owned(),
+        suggestion: None,
+    }))
 );
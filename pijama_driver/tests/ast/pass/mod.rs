@@ -18,3 +18,21 @@ test_type!(
     binding_persists_whole_block,
     Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
 );
+test_type!(detect_indirect_recursion, Ok(&Ty::Int));
+test_type!(
+    detect_recursion_after_shadowing_2,
+    Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+);
+test_type!(detect_recursion_inside_functions, Ok(&Ty::Int));
+test_type!(
+    anon_fn_with_ty,
+    Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+);
+test_type!(
+    adjacent_fn_forward_use,
+    Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+);
+test_type!(
+    redefinition_ends_fn_group,
+    Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+);
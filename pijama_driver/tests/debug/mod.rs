@@ -0,0 +1,47 @@
+use std::{include_str, io::Cursor};
+
+use pijama_driver::{debug::debug, LangResult};
+
+fn run_debug(input: &str, commands: &'static str) -> LangResult<String> {
+    let mut output = Vec::default();
+    debug(input, Cursor::new(commands), &mut output, true)?;
+    Ok(String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn function_breakpoint() -> LangResult<()> {
+    let input = include_str!("fact.pj");
+    let commands = "break add\nbreak fact\ncontinue\nbindings\ncontinue\nbindings\nredex\n\
+                    continue\nbindings\nquit\n";
+    let output = run_debug(input, commands)?;
+    assert_eq!(include_str!("function_breakpoint.txt"), output);
+    Ok(())
+}
+
+#[test]
+fn line_breakpoint() -> LangResult<()> {
+    let input = include_str!("fact.pj");
+    let commands = "break 8\nbreakpoints\ncontinue\nstep\nstep\nnext\ndelete 1\nbreakpoints\n\
+                    continue\n";
+    let output = run_debug(input, commands)?;
+    assert_eq!(include_str!("line_breakpoint.txt"), output);
+    Ok(())
+}
+
+#[test]
+fn commands() -> LangResult<()> {
+    let input = include_str!("fact.pj");
+    let commands = "help\nbreak 0\nbreak nope\ndelete 3\nfoo bar\nwhere\n";
+    let output = run_debug(input, commands)?;
+    assert_eq!(include_str!("commands.txt"), output);
+    Ok(())
+}
+
+#[test]
+fn quit() -> LangResult<()> {
+    let input = include_str!("fact.pj");
+    let output = run_debug(input, "quit\n")?;
+    assert!(output.ends_with("(pjdb) "));
+    assert!(!output.contains('3'));
+    Ok(())
+}
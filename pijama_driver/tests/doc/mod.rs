@@ -0,0 +1,33 @@
+use std::include_str;
+
+use pijama_driver::{
+    doc::{generate_doc, DocFormat},
+    LangErrorKind, LangResult,
+};
+use pijama_tycheck::TyErrorKind;
+
+#[test]
+fn markdown() -> LangResult<()> {
+    let input = include_str!("functions.pj");
+    let output = generate_doc(input, "functions.pj", DocFormat::Markdown)?;
+    assert_eq!(include_str!("functions.md"), output);
+    Ok(())
+}
+
+#[test]
+fn html() -> LangResult<()> {
+    let input = include_str!("functions.pj");
+    let output = generate_doc(input, "functions.pj", DocFormat::Html)?;
+    assert_eq!(include_str!("functions.html"), output);
+    Ok(())
+}
+
+#[test]
+fn untyped() {
+    let input = include_str!("untyped.pj");
+    let err = generate_doc(input, "untyped.pj", DocFormat::Markdown).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        LangErrorKind::Ty(TyErrorKind::NotConcrete { .. })
+    ));
+}
@@ -2,7 +2,7 @@ use std::{include_str, time::Duration};
 
 use pijama_driver::{LangErrorKind, LangResult};
 
-use crate::{panic_after, run};
+use crate::{panic_after, run, run_wrapping};
 
 #[test]
 fn arithmetic() -> LangResult<()> {
@@ -36,6 +36,62 @@ fn factorial_tail() -> LangResult<()> {
     Ok(())
 }
 
+#[test]
+fn factorial_inferred() -> LangResult<()> {
+    let input = include_str!("factorial_inferred.pj");
+    let output = run(input)?;
+    assert_eq!("3628800\n", output);
+    Ok(())
+}
+
+#[test]
+fn mutual_recursion() -> LangResult<()> {
+    let input = include_str!("mutual_recursion.pj");
+    let output = run(input)?;
+    assert_eq!("true\n", output);
+    Ok(())
+}
+
+#[test]
+fn mutual_recursion_group() -> LangResult<()> {
+    let input = include_str!("mutual_recursion_group.pj");
+    let output = run(input)?;
+    assert_eq!("13\n", output);
+    Ok(())
+}
+
+#[test]
+fn shadow_primitives() -> LangResult<()> {
+    let input = include_str!("shadow_primitives.pj");
+    let output = run(input)?;
+    assert_eq!("3\na\n", output);
+    Ok(())
+}
+
+#[test]
+fn shadow_sum_fns() -> LangResult<()> {
+    let input = include_str!("shadow_sum_fns.pj");
+    let output = run(input)?;
+    assert_eq!("3\n5\n", output);
+    Ok(())
+}
+
+#[test]
+fn shadow_test_names() -> LangResult<()> {
+    let input = include_str!("shadow_test_names.pj");
+    let output = run(input)?;
+    assert_eq!("3\n", output);
+    Ok(())
+}
+
+#[test]
+fn fn_forward_use_shadowing() -> LangResult<()> {
+    let input = include_str!("fn_forward_use_shadowing.pj");
+    let output = run(input)?;
+    assert_eq!("101\n", output);
+    Ok(())
+}
+
 #[test]
 fn fancy_max() -> LangResult<()> {
     let input = include_str!("fancy_max.pj");
@@ -184,6 +240,14 @@ fn print_complex_fn() -> LangResult<()> {
     Ok(())
 }
 
+#[test]
+fn statement_sequence() -> LangResult<()> {
+    let input = include_str!("statement_sequence.pj");
+    let output = run(input)?;
+    assert_eq!("1\n2\n", output);
+    Ok(())
+}
+
 #[test]
 fn print_print() -> LangResult<()> {
     let input = include_str!("print_print.pj");
@@ -252,3 +316,278 @@ fn adler32() -> LangResult<()> {
     assert_eq!("300286872\n", output);
     Ok(())
 }
+
+#[test]
+fn show_instance() -> LangResult<()> {
+    let input = include_str!("show_instance.pj");
+    let output = run(input)?;
+    assert_eq!("2\n", output);
+    Ok(())
+}
+
+#[test]
+fn class_dispatch() -> LangResult<()> {
+    let input = include_str!("class_dispatch.pj");
+    let output = run(input)?;
+    assert_eq!("31\n", output);
+    Ok(())
+}
+
+#[test]
+fn instance_mutual_methods() -> LangResult<()> {
+    let input = include_str!("instance_mutual_methods.pj");
+    let output = run(input)?;
+    assert_eq!("true\ntrue\nfalse\n", output);
+    Ok(())
+}
+
+#[test]
+fn eq_instance() -> LangResult<()> {
+    let input = include_str!("eq_instance.pj");
+    let output = run(input)?;
+    assert_eq!("true\n", output);
+    Ok(())
+}
+
+#[test]
+fn ord_instance() -> LangResult<()> {
+    let input = include_str!("ord_instance.pj");
+    let output = run(input)?;
+    assert_eq!("true\n", output);
+    Ok(())
+}
+
+#[test]
+fn fixed_width_arithmetic() -> LangResult<()> {
+    let input = include_str!("fixed_width_arithmetic.pj");
+    let output = run(input)?;
+    assert_eq!("142\n", output);
+    Ok(())
+}
+
+#[test]
+fn print_u64_max() -> LangResult<()> {
+    let input = include_str!("print_u64_max.pj");
+    let output = run(input)?;
+    assert_eq!("18446744073709551615\n", output);
+    Ok(())
+}
+
+#[test]
+fn fnv1a_wraparound() -> LangResult<()> {
+    let input = include_str!("fnv1a_wraparound.pj");
+    let output = run_wrapping(input)?;
+    assert_eq!("4017758505\n", output);
+    Ok(())
+}
+
+#[test]
+fn wrapping_conversions() -> LangResult<()> {
+    let input = include_str!("wrapping_conversions.pj");
+    let output = run_wrapping(input)?;
+    assert_eq!("4294967239\n", output);
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn fixed_add_overflow_panics() {
+    let input = include_str!("fixed_add_overflow_panics.pj");
+    run(input).ok();
+}
+
+#[test]
+#[should_panic]
+fn conversion_overflow_panics() {
+    let input = include_str!("conversion_overflow_panics.pj");
+    run(input).ok();
+}
+
+#[test]
+fn bigint_factorial() -> LangResult<()> {
+    let input = include_str!("bigint_factorial.pj");
+    let output = run(input)?;
+    assert_eq!("15511210043330985984000000\n", output);
+    Ok(())
+}
+
+#[test]
+fn oversized_literal() -> LangResult<()> {
+    let input = include_str!("oversized_literal.pj");
+    let output = run(input)?;
+    assert_eq!("100000000000000000000\n", output);
+    Ok(())
+}
+
+#[test]
+fn big_shift() -> LangResult<()> {
+    let input = include_str!("big_shift.pj");
+    let output = run(input)?;
+    assert_eq!("1267650600228229401496703205375\n", output);
+    Ok(())
+}
+
+#[test]
+fn big_comparison() -> LangResult<()> {
+    let input = include_str!("big_comparison.pj");
+    let output = run(input)?;
+    assert_eq!("true\n", output);
+    Ok(())
+}
+
+#[test]
+fn wrapping_big_conversions() -> LangResult<()> {
+    let input = include_str!("wrapping_big_conversions.pj");
+    let output = run_wrapping(input)?;
+    assert_eq!("-210\n", output);
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn big_conversion_overflow_panics() {
+    let input = include_str!("big_conversion_overflow_panics.pj");
+    run(input).ok();
+}
+
+#[test]
+fn float_arithmetic() -> LangResult<()> {
+    let input = include_str!("float_arithmetic.pj");
+    let output = run(input)?;
+    assert_eq!("11.06636\n", output);
+    Ok(())
+}
+
+#[test]
+fn float_rounding() -> LangResult<()> {
+    let input = include_str!("float_rounding.pj");
+    let output = run(input)?;
+    assert_eq!("0.30000000000000004\n", output);
+    Ok(())
+}
+
+#[test]
+fn float_infinity() -> LangResult<()> {
+    let input = include_str!("float_infinity.pj");
+    let output = run(input)?;
+    assert_eq!("-inf\n", output);
+    Ok(())
+}
+
+#[test]
+fn float_nan() -> LangResult<()> {
+    let input = include_str!("float_nan.pj");
+    let output = run(input)?;
+    assert_eq!("false\n", output);
+    Ok(())
+}
+
+#[test]
+fn truncate() -> LangResult<()> {
+    let input = include_str!("truncate.pj");
+    let output = run(input)?;
+    assert_eq!("13\n", output);
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn truncate_overflow_panics() {
+    let input = include_str!("truncate_overflow_panics.pj");
+    run(input).ok();
+}
+
+#[test]
+fn print_char() -> LangResult<()> {
+    let input = include_str!("print_char.pj");
+    let output = run(input)?;
+    assert_eq!("é\n", output);
+    Ok(())
+}
+
+#[test]
+fn char_escapes() -> LangResult<()> {
+    let input = include_str!("char_escapes.pj");
+    let output = run(input)?;
+    assert_eq!("128687\n", output);
+    Ok(())
+}
+
+#[test]
+fn char_comparison() -> LangResult<()> {
+    let input = include_str!("char_comparison.pj");
+    let output = run(input)?;
+    assert_eq!("true\n", output);
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn invalid_chr_panics() {
+    let input = include_str!("invalid_chr_panics.pj");
+    run(input).ok();
+}
+
+#[test]
+fn safe_division() -> LangResult<()> {
+    let input = include_str!("safe_division.pj");
+    let output = run(input)?;
+    assert_eq!("5302\n", output);
+    Ok(())
+}
+
+#[test]
+fn lookup() -> LangResult<()> {
+    let input = include_str!("lookup.pj");
+    let output = run(input)?;
+    assert_eq!("true\n", output);
+    Ok(())
+}
+
+#[test]
+fn option_is_some() -> LangResult<()> {
+    let input = include_str!("option_is_some.pj");
+    let output = run(input)?;
+    assert_eq!("true\n", output);
+    Ok(())
+}
+
+#[test]
+fn pipe() -> LangResult<()> {
+    let input = include_str!("pipe.pj");
+    let output = run(input)?;
+    assert_eq!("15.0\n", output);
+    Ok(())
+}
+
+#[test]
+fn where_clause() -> LangResult<()> {
+    let input = include_str!("where_clause.pj");
+    let output = run(input)?;
+    assert_eq!("41\n", output);
+    Ok(())
+}
+
+#[test]
+fn let_in() -> LangResult<()> {
+    let input = include_str!("let_in.pj");
+    let output = run(input)?;
+    assert_eq!("43\n", output);
+    Ok(())
+}
+
+#[test]
+fn custom_operators() -> LangResult<()> {
+    let input = include_str!("custom_operators.pj");
+    let output = run(input)?;
+    assert_eq!("1035\n", output);
+    Ok(())
+}
+
+#[test]
+fn sections() -> LangResult<()> {
+    let input = include_str!("sections.pj");
+    let output = run(input)?;
+    assert_eq!("179\n", output);
+    Ok(())
+}
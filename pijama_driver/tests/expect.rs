@@ -0,0 +1,113 @@
+//! Runs every program under this directory whose header states its expected outcome.
+//!
+//! Pass `--bless` to rewrite the expectations of the failing programs with their actual outcome,
+//! and any other argument to only run the programs whose path contains it:
+//!
+//! ```text
+//! cargo test -p pijama_driver --test expect -- [--bless] [FILTER]
+//! ```
+extern crate pijama_driver;
+
+use std::{
+    env,
+    ffi::OsStr,
+    fs, panic,
+    path::{Path, PathBuf},
+    process,
+};
+
+use pijama_driver::expect::{bless, expectation, run_program, Outcome};
+
+fn main() {
+    let mut bless_mode = false;
+    let mut filters = Vec::new();
+    for arg in env::args().skip(1) {
+        if arg == "--bless" {
+            bless_mode = true;
+        } else if !arg.starts_with('-') {
+            filters.push(arg);
+        }
+    }
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+    let mut paths = Vec::new();
+    collect_programs(&root, &mut paths);
+    paths.sort();
+
+    let mut programs = Vec::new();
+    for path in paths {
+        let name = path.strip_prefix(&root).unwrap().display().to_string();
+        if !filters.is_empty() && !filters.iter().any(|filter| name.contains(filter.as_str())) {
+            continue;
+        }
+        let input = fs::read_to_string(&path).unwrap();
+        if let Some(expected) = expectation(&input) {
+            programs.push((name, path, input, expected));
+        }
+    }
+
+    // Panics are part of the outcome, so they must not be printed.
+    panic::set_hook(Box::new(|_| {}));
+
+    println!("\nrunning {} programs", programs.len());
+    let mut failures = Vec::new();
+    let mut blessed = 0;
+    for (name, path, input, expected) in &programs {
+        let actual = run_program(input);
+        if expected.matches(&actual) {
+            println!("program {} ... ok", name);
+        } else if bless_mode {
+            fs::write(path, bless(input, &actual)).unwrap();
+            println!("program {} ... blessed", name);
+            blessed += 1;
+        } else {
+            println!("program {} ... FAILED", name);
+            failures.push((name, expected, actual));
+        }
+    }
+
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for (name, expected, actual) in &failures {
+            println!("\n---- {} ----", name);
+            println!("expected: {}", describe(expected));
+            println!("  actual: {}", describe(actual));
+        }
+        println!("\nrun with `--bless` to update the expectations of the failing programs");
+    }
+
+    println!(
+        "\nexpect result: {}. {} passed; {} failed; {} blessed\n",
+        if failures.is_empty() { "ok" } else { "FAILED" },
+        programs.len() - failures.len() - blessed,
+        failures.len(),
+        blessed
+    );
+
+    if !failures.is_empty() {
+        process::exit(1);
+    }
+}
+
+/// Collects the path of every program inside `dir` and its subdirectories.
+fn collect_programs(dir: &Path, paths: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_programs(&path, paths);
+        } else if path.extension() == Some(OsStr::new("pj")) {
+            paths.push(path);
+        }
+    }
+}
+
+fn describe(outcome: &Outcome) -> String {
+    match outcome {
+        Outcome::Output(output) => format!("output {:?}", output),
+        Outcome::Error(error) => format!("error {}", error),
+        Outcome::Panic { output, message } => match message {
+            Some(message) => format!("output {:?} and panic {:?}", output, message),
+            None => format!("output {:?} and any panic", output),
+        },
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashSet;
+
+use pijama_driver::{explain::explain, LangErrorKind};
+use pijama_hir::LowerErrorKind;
+use pijama_parser::ParsingErrorKind;
+use pijama_ty::Ty;
+use pijama_tycheck::TyErrorKind;
+
+/// Returns one value of each error kind variant.
+fn all_kinds() -> Vec<LangErrorKind> {
+    vec![
+        ParsingErrorKind::UnexpectedToken {
+            found: String::default(),
+            expected: Vec::default(),
+        }
+        .into(),
+        ParsingErrorKind::InvalidToken.into(),
+        ParsingErrorKind::ExtraToken.into(),
+        ParsingErrorKind::Custom("").into(),
+        ParsingErrorKind::UndefinedOperator(String::default()).into(),
+        ParsingErrorKind::ConflictingOperators(String::default()).into(),
+        ParsingErrorKind::IntOutOfRange.into(),
+        LowerErrorKind::Unbounded {
+            name: String::default(),
+            suggestion: None,
+        }
+        .into(),
+        LowerErrorKind::UnknownTy(String::default()).into(),
+        LowerErrorKind::CyclicTyAlias(String::default()).into(),
+        LowerErrorKind::UnknownClass(String::default()).into(),
+        LowerErrorKind::UnexpectedMethod(String::default()).into(),
+        LowerErrorKind::MissingMethod(String::default()).into(),
+        TyErrorKind::Mismatch {
+            expected: Ty::Int,
+            found: Ty::Bool,
+        }
+        .into(),
+        TyErrorKind::Unbounded(String::default()).into(),
+        TyErrorKind::NotConcrete {
+            ty: Ty::Var(0),
+            name: None,
+        }
+        .into(),
+        TyErrorKind::NoInstance {
+            class: String::default(),
+            ty: Ty::Int,
+        }
+        .into(),
+        TyErrorKind::Hole(Ty::Int).into(),
+    ]
+}
+
+/// This function does not compile if a new variant is added to any of the error kinds, reminding
+/// us to add such variant to `all_kinds`.
+#[allow(dead_code)]
+fn all_kinds_is_exhaustive(kind: LangErrorKind) {
+    match kind {
+        LangErrorKind::Parse(kind) => match kind {
+            ParsingErrorKind::UnexpectedToken { .. }
+            | ParsingErrorKind::InvalidToken
+            | ParsingErrorKind::ExtraToken
+            | ParsingErrorKind::Custom(_)
+            | ParsingErrorKind::UndefinedOperator(_)
+            | ParsingErrorKind::ConflictingOperators(..)
+            | ParsingErrorKind::IntOutOfRange => (),
+        },
+        LangErrorKind::Lower(kind) => match kind {
+            LowerErrorKind::Unbounded { .. }
+            | LowerErrorKind::UnknownTy(_)
+            | LowerErrorKind::CyclicTyAlias(_)
+            | LowerErrorKind::UnknownClass(_)
+            | LowerErrorKind::UnexpectedMethod(_)
+            | LowerErrorKind::MissingMethod(_) => (),
+        },
+        LangErrorKind::Ty(kind) => match kind {
+            TyErrorKind::Mismatch { .. }
+            | TyErrorKind::Unbounded(_)
+            | TyErrorKind::NotConcrete { .. }
+            | TyErrorKind::NoInstance { .. }
+            | TyErrorKind::Hole(_) => (),
+        },
+    }
+}
+
+#[test]
+fn every_error_has_an_explanation() {
+    for kind in all_kinds() {
+        assert!(
+            explain(kind.code()).is_some(),
+            "{:?} has no explanation for {}",
+            kind,
+            kind.code()
+        );
+    }
+}
+
+#[test]
+fn error_codes_are_unique() {
+    let mut codes = HashSet::new();
+    for kind in all_kinds() {
+        assert!(codes.insert(kind.code()), "{} is used twice", kind.code());
+    }
+}
+
+#[test]
+fn every_explanation_has_an_example() {
+    for code in pijama_driver::explain::codes() {
+        let explanation = explain(code).unwrap();
+        assert!(
+            explanation.contains("```elixir"),
+            "explanation for {} has no example",
+            code
+        );
+    }
+}
+
+#[test]
+fn unknown_code_has_no_explanation() {
+    assert_eq!(explain("E9999"), None);
+}
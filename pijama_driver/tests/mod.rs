@@ -3,11 +3,20 @@ extern crate pijama_driver;
 use std::{panic, sync::mpsc, thread, time::Duration};
 
 use pijama_driver::{run_with_machine, LangResult};
-use pijama_machine::{arithmetic::CheckedArithmetic, env::Env, MachineBuilder};
+use pijama_machine::{
+    arithmetic::{CheckedArithmetic, OverflowArithmetic},
+    env::Env,
+    MachineBuilder,
+};
 
 mod ast;
+mod debug;
+mod doc;
 mod eval;
+mod explain;
 mod parse;
+mod testing;
+mod trace;
 mod type_check;
 mod util;
 
@@ -23,6 +32,17 @@ fn run(input: &str) -> LangResult<String> {
     Ok(String::from_utf8(output).unwrap())
 }
 
+/// Runs `input` using arithmetic that wraps around on overflow and returns its output.
+fn run_wrapping(input: &str) -> LangResult<String> {
+    let mut output = Vec::default();
+    let machine = MachineBuilder::default()
+        .with_arithmetic(OverflowArithmetic)
+        .with_env(Env::new(&mut output))
+        .build();
+    run_with_machine(input, machine)?;
+    Ok(String::from_utf8(output).unwrap())
+}
+
 fn panic_after<T, F>(d: Duration, f: F) -> T
 where
     T: Send + 'static,
@@ -2,10 +2,13 @@ use std::include_str;
 
 use pijama_ast::{
     self,
-    node::{Block, Branch, Expression as Expr, Node, Statement as Stat},
+    node::{
+        Assoc, Block, Branch, Doc, Expression as Expr, Fixity, InfixOp, Method, MethodSig, Node,
+        Statement as Stat,
+    },
     ty::{Ty, TyAnnotation},
 };
-use pijama_common::{BinOp::*, Literal, Local, UnOp};
+use pijama_common::{BinOp::*, IntTy, Literal, Local, Primitive, UnOp};
 use pijama_parser::{parse, ParsingResult};
 
 use crate::util::DummyLoc;
@@ -60,6 +63,95 @@ fn consecutive_comments() -> ParsingResult<()> {
     Ok(())
 }
 
+#[test]
+fn block_comment() -> ParsingResult<()> {
+    let input = include_str!("block_comment.pj");
+    let mut result = block_into_iter(parse(input)?);
+    assert_eq!(
+        Node::Expr(
+            Expr::BinaryOp(
+                Add,
+                Box::new(Expr::Local(Local::Name("foo")).loc()),
+                Box::new(Expr::Local(Local::Name("bar")).loc()),
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "nested and inline"
+    );
+    Ok(())
+}
+
+#[test]
+fn doc_comment() -> ParsingResult<()> {
+    let input = include_str!("doc_comment.pj");
+    let mut result = block_into_iter(parse(input)?);
+    let assign = |lhs, rhs, doc| {
+        Node::Stat(
+            Stat::Assign(
+                TyAnnotation {
+                    item: Local::Name(lhs).loc(),
+                    ty: Ty::Missing.loc(),
+                },
+                rhs,
+                doc,
+            )
+            .loc(),
+        )
+    };
+    assert_eq!(
+        Node::Stat(
+            Stat::FnDef(
+                Local::Name("id").loc(),
+                vec![TyAnnotation {
+                    item: Local::Name("x").loc(),
+                    ty: Ty::Missing.loc(),
+                }],
+                TyAnnotation {
+                    item: Block {
+                        nodes: Default::default(),
+                        expr: Box::new(Expr::Local(Local::Name("x")).loc()),
+                    },
+                    ty: Ty::Missing.loc(),
+                },
+                Some(Doc {
+                    lines: vec!["Returns its argument.", "", "  Indented line."],
+                }),
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "function definition"
+    );
+    assert_eq!(
+        assign(
+            "y",
+            Expr::Literal(Literal::Number(42)).loc(),
+            Some(Doc {
+                lines: vec!["The answer."],
+            })
+        ),
+        result.next().unwrap(),
+        "assignment"
+    );
+    assert_eq!(
+        Node::Expr(Expr::Local(Local::Name("y")).loc()),
+        result.next().unwrap(),
+        "expression"
+    );
+    assert_eq!(
+        assign("z", Expr::Local(Local::Name("y")).loc(), None),
+        result.next().unwrap(),
+        "regular comment"
+    );
+    assert_eq!(
+        assign("w", Expr::Local(Local::Name("z")).loc(), None),
+        result.next().unwrap(),
+        "banner comment"
+    );
+    Ok(())
+}
+
 #[test]
 fn literal() -> ParsingResult<()> {
     let input = include_str!("literal.pj");
@@ -349,6 +441,7 @@ fn assign() -> ParsingResult<()> {
                     ty: Ty::Missing.loc(),
                 },
                 Expr::Local(Local::Name("y")).loc(),
+                None,
             )
             .loc(),
         ),
@@ -368,6 +461,7 @@ fn assign() -> ParsingResult<()> {
                     Box::new(Expr::Local(Local::Name("z")).loc()),
                 )
                 .loc(),
+                None,
             )
             .loc(),
         ),
@@ -382,6 +476,7 @@ fn assign() -> ParsingResult<()> {
                     ty: Ty::Int.loc(),
                 },
                 Expr::Local(Local::Name("y")).loc(),
+                None,
             )
             .loc(),
         ),
@@ -409,6 +504,7 @@ fn assign() -> ParsingResult<()> {
                     },
                 )
                 .loc(),
+                None,
             )
             .loc(),
         ),
@@ -651,6 +747,7 @@ fn fn_def() -> ParsingResult<()> {
                     },
                     ty: Ty::Missing.loc(),
                 },
+                None,
             )
             .loc(),
         ),
@@ -672,6 +769,7 @@ fn fn_def() -> ParsingResult<()> {
                     },
                     ty: Ty::Unit.loc(),
                 },
+                None,
             )
             .loc(),
         ),
@@ -701,6 +799,7 @@ fn fn_def() -> ParsingResult<()> {
                     },
                     ty: Ty::Missing.loc(),
                 },
+                None,
             )
             .loc(),
         ),
@@ -917,3 +1016,616 @@ fn cmp_and_shift() -> ParsingResult<()> {
     );
     Ok(())
 }
+
+#[test]
+fn ty_alias() -> ParsingResult<()> {
+    let input = include_str!("ty_alias.pj");
+    let mut result = block_into_iter(parse(input)?);
+    assert_eq!(
+        Node::Stat(
+            Stat::TyAlias(
+                "Comparator".loc(),
+                Ty::Arrow(
+                    Box::new(Ty::Int),
+                    Box::new(Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Bool))),
+                )
+                .loc(),
+            )
+            .loc(),
+        ),
+        result.next().unwrap(),
+        "declaration"
+    );
+    assert_eq!(
+        Node::Stat(
+            Stat::Assign(
+                TyAnnotation {
+                    item: Local::Name("x").loc(),
+                    ty: Ty::Alias("Comparator".to_owned()).loc(),
+                },
+                Expr::Local(Local::Name("y")).loc(),
+                None,
+            )
+            .loc(),
+        ),
+        result.next().unwrap(),
+        "usage"
+    );
+    Ok(())
+}
+
+#[test]
+fn class() -> ParsingResult<()> {
+    let input = include_str!("class.pj");
+    let mut result = block_into_iter(parse(input)?);
+    assert_eq!(
+        Node::Stat(
+            Stat::Class(
+                "Describe".loc(),
+                vec![MethodSig {
+                    name: Local::Name("describe").loc(),
+                    args: vec![
+                        TyAnnotation {
+                            item: Local::Name("x").loc(),
+                            ty: Ty::Alias("Self".to_owned()).loc(),
+                        },
+                        TyAnnotation {
+                            item: Local::Name("y").loc(),
+                            ty: Ty::Int.loc(),
+                        },
+                    ],
+                    ty: Ty::Int.loc(),
+                }],
+            )
+            .loc(),
+        ),
+        result.next().unwrap(),
+        "class"
+    );
+    assert_eq!(
+        Node::Stat(
+            Stat::Instance(
+                "Show".loc(),
+                Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)).loc(),
+                vec![Method {
+                    name: Local::Name("print").loc(),
+                    args: vec![TyAnnotation {
+                        item: Local::Name("f").loc(),
+                        ty: Ty::Missing.loc(),
+                    }],
+                    body: TyAnnotation {
+                        item: Block {
+                            nodes: Default::default(),
+                            expr: Box::new(
+                                Expr::Call(
+                                    Box::new(Expr::PrimFn(Primitive::Print).loc()),
+                                    vec![Expr::Call(
+                                        Box::new(Expr::Local(Local::Name("f")).loc()),
+                                        vec![Expr::Literal(Literal::Number(0)).loc()],
+                                    )
+                                    .loc()],
+                                )
+                                .loc(),
+                            ),
+                        },
+                        ty: Ty::Missing.loc(),
+                    },
+                }
+                .loc()],
+            )
+            .loc(),
+        ),
+        result.next().unwrap(),
+        "instance"
+    );
+    Ok(())
+}
+
+#[test]
+fn ascription() -> ParsingResult<()> {
+    let input = include_str!("ascription.pj");
+    let mut result = block_into_iter(parse(input)?);
+    assert_eq!(
+        Node::Expr(
+            Expr::Ascription(Box::new(Expr::Local(Local::Name("x")).loc()), Ty::Int.loc()).loc()
+        ),
+        result.next().unwrap(),
+        "simple"
+    );
+    assert_eq!(
+        Node::Expr(
+            Expr::Call(
+                Box::new(
+                    Expr::Ascription(
+                        Box::new(Expr::Local(Local::Name("f")).loc()),
+                        Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)).loc()
+                    )
+                    .loc()
+                ),
+                vec![Expr::Literal(Literal::Number(1)).loc()]
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "call"
+    );
+    Ok(())
+}
+
+#[test]
+fn hole() -> ParsingResult<()> {
+    let input = include_str!("hole.pj");
+    let mut result = block_into_iter(parse(input)?);
+    assert_eq!(
+        Node::Expr(Expr::Hole.loc()),
+        result.next().unwrap(),
+        "alone"
+    );
+    assert_eq!(
+        Node::Expr(
+            Expr::Call(
+                Box::new(Expr::Hole.loc()),
+                vec![
+                    Expr::Local(Local::Name("x")).loc(),
+                    Expr::Literal(Literal::Number(1)).loc()
+                ]
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "call"
+    );
+    Ok(())
+}
+
+#[test]
+fn fixed_int() -> ParsingResult<()> {
+    let input = include_str!("fixed_int.pj");
+    let mut result = block_into_iter(parse(input)?);
+    assert_eq!(
+        Node::Expr(Expr::Literal(Literal::Fixed(200, IntTy::U8)).loc()),
+        result.next().unwrap(),
+        "unsigned"
+    );
+    assert_eq!(
+        Node::Expr(Expr::Literal(Literal::Fixed(-5, IntTy::I16)).loc()),
+        result.next().unwrap(),
+        "signed"
+    );
+    assert_eq!(
+        Node::Expr(Expr::Literal(Literal::Fixed(255, IntTy::U64)).loc()),
+        result.next().unwrap(),
+        "hexadecimal"
+    );
+    Ok(())
+}
+
+#[test]
+fn big_int() -> ParsingResult<()> {
+    let input = include_str!("big_int.pj");
+    let mut result = block_into_iter(parse(input)?);
+    assert_eq!(
+        Node::Expr(Expr::Literal(Literal::Big(25.into())).loc()),
+        result.next().unwrap(),
+        "suffix"
+    );
+    assert_eq!(
+        Node::Expr(Expr::Literal(Literal::Big(99999999999999999999u128.into())).loc()),
+        result.next().unwrap(),
+        "oversized"
+    );
+    assert_eq!(
+        Node::Expr(Expr::Literal(Literal::Big((-255).into())).loc()),
+        result.next().unwrap(),
+        "hexadecimal"
+    );
+    Ok(())
+}
+
+#[test]
+fn float() -> ParsingResult<()> {
+    let input = include_str!("float.pj");
+    let mut result = block_into_iter(parse(input)?);
+    assert_eq!(
+        Node::Expr(Expr::Literal(Literal::Float(1.5f64.to_bits())).loc()),
+        result.next().unwrap(),
+        "decimal"
+    );
+    assert_eq!(
+        Node::Expr(Expr::Literal(Literal::Float(2e10f64.to_bits())).loc()),
+        result.next().unwrap(),
+        "exponent"
+    );
+    assert_eq!(
+        Node::Expr(Expr::Literal(Literal::Float((-0.25e-2f64).to_bits())).loc()),
+        result.next().unwrap(),
+        "negative exponent"
+    );
+    Ok(())
+}
+
+#[test]
+fn char() -> ParsingResult<()> {
+    let input = include_str!("char.pj");
+    let mut result = block_into_iter(parse(input)?);
+    assert_eq!(
+        Node::Expr(Expr::Literal(Literal::Char('a')).loc()),
+        result.next().unwrap(),
+        "plain"
+    );
+    assert_eq!(
+        Node::Expr(Expr::Literal(Literal::Char('\n')).loc()),
+        result.next().unwrap(),
+        "escaped"
+    );
+    assert_eq!(
+        Node::Expr(Expr::Literal(Literal::Char('😀')).loc()),
+        result.next().unwrap(),
+        "unicode escape"
+    );
+    assert_eq!(
+        Node::Expr(Expr::Literal(Literal::Char('\'')).loc()),
+        result.next().unwrap(),
+        "quote"
+    );
+    Ok(())
+}
+
+#[test]
+fn sum_types() -> ParsingResult<()> {
+    let input = include_str!("sum_types.pj");
+    let mut result = block_into_iter(parse(input)?);
+    assert_eq!(
+        Node::Stat(
+            Stat::Assign(
+                TyAnnotation {
+                    item: Local::Name("x").loc(),
+                    ty: Ty::Option(Box::new(Ty::Int)).loc(),
+                },
+                Expr::Call(
+                    Box::new(Expr::Local(Local::Name("some")).loc()),
+                    vec![Expr::Literal(Literal::Number(1)).loc()]
+                )
+                .loc(),
+                None,
+            )
+            .loc(),
+        ),
+        result.next().unwrap(),
+        "option"
+    );
+    assert_eq!(
+        Node::Stat(
+            Stat::Assign(
+                TyAnnotation {
+                    item: Local::Name("y").loc(),
+                    ty: Ty::Result(Box::new(Ty::Int), Box::new(Ty::Bool)).loc(),
+                },
+                Expr::Call(
+                    Box::new(Expr::Local(Local::Name("err")).loc()),
+                    vec![Expr::Literal(Literal::Bool(true)).loc()]
+                )
+                .loc(),
+                None,
+            )
+            .loc(),
+        ),
+        result.next().unwrap(),
+        "result"
+    );
+    assert_eq!(
+        Node::Expr(Expr::Local(Local::Name("none")).loc()),
+        result.next().unwrap(),
+        "constant"
+    );
+    Ok(())
+}
+
+#[test]
+fn pipe() -> ParsingResult<()> {
+    let input = include_str!("pipe.pj");
+    let mut result = block_into_iter(parse(input)?);
+    assert_eq!(
+        Node::Expr(
+            Expr::Pipe(
+                Box::new(
+                    Expr::Pipe(
+                        Box::new(Expr::Local(Local::Name("a")).loc()),
+                        Box::new(
+                            Expr::Call(
+                                Box::new(Expr::Local(Local::Name("f")).loc()),
+                                vec![Expr::Local(Local::Name("b")).loc()]
+                            )
+                            .loc()
+                        ),
+                    )
+                    .loc()
+                ),
+                Box::new(Expr::Local(Local::Name("g")).loc()),
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "chain"
+    );
+    assert_eq!(
+        Node::Expr(
+            Expr::Pipe(
+                Box::new(
+                    Expr::BinaryOp(
+                        Add,
+                        Box::new(Expr::Local(Local::Name("a")).loc()),
+                        Box::new(Expr::Local(Local::Name("b")).loc()),
+                    )
+                    .loc()
+                ),
+                Box::new(Expr::Local(Local::Name("f")).loc()),
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "lowest precedence"
+    );
+    assert_eq!(
+        Node::Expr(
+            Expr::Pipe(
+                Box::new(Expr::Local(Local::Name("x")).loc()),
+                Box::new(Expr::Call(Box::new(Expr::Local(Local::Name("f")).loc()), vec![]).loc()),
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "new line"
+    );
+    Ok(())
+}
+
+#[test]
+fn let_in() -> ParsingResult<()> {
+    let input = include_str!("let_in.pj");
+    let mut result = block_into_iter(parse(input)?);
+    assert_eq!(
+        Node::Expr(
+            Expr::Let(
+                TyAnnotation {
+                    item: Local::Name("x").loc(),
+                    ty: Ty::Int.loc(),
+                },
+                Box::new(Expr::Literal(Literal::Number(1)).loc()),
+                Box::new(
+                    Expr::Let(
+                        TyAnnotation {
+                            item: Local::Name("y").loc(),
+                            ty: Ty::Missing.loc(),
+                        },
+                        Box::new(Expr::Local(Local::Name("x")).loc()),
+                        Box::new(Expr::Local(Local::Name("y")).loc()),
+                    )
+                    .loc()
+                ),
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "nested"
+    );
+    Ok(())
+}
+
+#[test]
+fn where_clause() -> ParsingResult<()> {
+    let input = include_str!("where_clause.pj");
+    let mut result = block_into_iter(parse(input)?);
+    assert_eq!(
+        Node::Stat(
+            Stat::FnDef(
+                Local::Name("foo").loc(),
+                vec![TyAnnotation {
+                    item: Local::Name("x").loc(),
+                    ty: Ty::Int.loc(),
+                }],
+                TyAnnotation {
+                    item: Block {
+                        nodes: vec![Node::Stat(
+                            Stat::Assign(
+                                TyAnnotation {
+                                    item: Local::Name("y").loc(),
+                                    ty: Ty::Missing.loc(),
+                                },
+                                Expr::Local(Local::Name("x")).loc(),
+                                None,
+                            )
+                            .loc()
+                        )]
+                        .into(),
+                        expr: Box::new(Expr::Local(Local::Name("y")).loc()),
+                    },
+                    ty: Ty::Missing.loc(),
+                },
+                None,
+            )
+            .loc(),
+        ),
+        result.next().unwrap(),
+        "definitions before body"
+    );
+    Ok(())
+}
+
+#[test]
+fn infix() -> ParsingResult<()> {
+    let input = include_str!("infix.pj");
+    let mut result = block_into_iter(parse(input)?);
+    let name = |name| Box::new(Expr::Local(Local::Name(name)).loc());
+    assert_eq!(
+        Node::Stat(
+            Stat::Infix(
+                Fixity {
+                    assoc: Assoc::Left,
+                    prec: 6
+                },
+                "<+>".loc(),
+                Expr::Local(Local::Name("add")).loc(),
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "declaration"
+    );
+    assert_eq!(
+        Node::Expr(
+            Expr::Infix(
+                "<+>".loc(),
+                name("a"),
+                Box::new(Expr::BinaryOp(Mul, name("b"), name("c")).loc()),
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "built-in operator with higher precedence"
+    );
+    assert_eq!(
+        Node::Expr(
+            Expr::BinaryOp(
+                Add,
+                Box::new(Expr::Infix("<+>".loc(), name("a"), name("b")).loc()),
+                name("c"),
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "left associative with the same precedence"
+    );
+    result.next();
+    assert_eq!(
+        Node::Expr(
+            Expr::Infix(
+                "++".loc(),
+                name("a"),
+                Box::new(Expr::Infix("++".loc(), name("b"), name("c")).loc()),
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "right associative"
+    );
+    assert_eq!(
+        Node::Expr(
+            Expr::BinaryOp(
+                Eq,
+                name("a"),
+                Box::new(Expr::Infix("++".loc(), name("b"), name("c")).loc()),
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "custom operator with higher precedence"
+    );
+    Ok(())
+}
+
+#[test]
+fn section() -> ParsingResult<()> {
+    let input = include_str!("section.pj");
+    let mut result = block_into_iter(parse(input)?);
+    assert_eq!(
+        Node::Expr(Expr::Section(InfixOp::BinOp(Add).loc(), None, None).loc()),
+        result.next().unwrap(),
+        "no operands"
+    );
+    assert_eq!(
+        Node::Expr(
+            Expr::Section(
+                InfixOp::BinOp(Mul).loc(),
+                Some(Box::new(Expr::Literal(Literal::Number(2)).loc())),
+                None
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "left operand"
+    );
+    assert_eq!(
+        Node::Expr(
+            Expr::Section(
+                InfixOp::BinOp(Lt).loc(),
+                None,
+                Some(Box::new(Expr::Literal(Literal::Number(0)).loc()))
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "right operand"
+    );
+    assert_eq!(
+        Node::Expr(Expr::UnaryOp(UnOp::Neg, Box::new(Expr::Local(Local::Name("x")).loc())).loc()),
+        result.next().unwrap(),
+        "negation is not a section"
+    );
+    assert_eq!(
+        Node::Expr(
+            Expr::Call(
+                Box::new(Expr::Section(InfixOp::BinOp(Sub).loc(), None, None).loc()),
+                vec![
+                    Expr::Local(Local::Name("a")).loc(),
+                    Expr::Local(Local::Name("b")).loc()
+                ]
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "called section"
+    );
+    Ok(())
+}
+
+#[test]
+fn test() -> ParsingResult<()> {
+    let input = include_str!("test.pj");
+    let mut result = block_into_iter(parse(input)?);
+    assert_eq!(
+        Node::Stat(
+            Stat::Test(
+                "with name".loc(),
+                Block {
+                    nodes: Default::default(),
+                    expr: Box::new(
+                        Expr::Call(
+                            Box::new(Expr::Local(Local::Name("assert")).loc()),
+                            vec![Expr::Literal(Literal::Bool(true)).loc()]
+                        )
+                        .loc()
+                    ),
+                }
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "assert"
+    );
+    assert_eq!(
+        Node::Stat(
+            Stat::Test(
+                "".loc(),
+                Block {
+                    nodes: vec![Node::Expr(
+                        Expr::Call(
+                            Box::new(Expr::Local(Local::Name("assert_eq")).loc()),
+                            vec![
+                                Expr::Literal(Literal::Number(1)).loc(),
+                                Expr::Literal(Literal::Number(2)).loc()
+                            ]
+                        )
+                        .loc()
+                    )]
+                    .into(),
+                    expr: Box::new(Expr::Literal(Literal::Unit).loc()),
+                }
+            )
+            .loc()
+        ),
+        result.next().unwrap(),
+        "assert_eq"
+    );
+    Ok(())
+}
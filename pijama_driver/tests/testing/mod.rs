@@ -0,0 +1,130 @@
+use std::include_str;
+
+use pijama_driver::{
+    testing::{find_tests, run_tests, TestReport},
+    LangErrorKind, LangResult,
+};
+use pijama_parser::ParsingErrorKind;
+use pijama_tycheck::TyErrorKind;
+
+use crate::run;
+
+/// Returns the name of each test with its failure message, if it failed.
+fn results(reports: &[TestReport]) -> Vec<(&str, Option<&str>)> {
+    reports
+        .iter()
+        .map(|report| {
+            let message = report.result.as_ref().err();
+            (
+                report.test.name.as_str(),
+                message.map(|failure| failure.message.as_str()),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn passing() -> LangResult<()> {
+    let input = include_str!("passing.pj");
+    let reports = run_tests(input, None, true)?;
+    assert_eq!(
+        vec![("square of two", None), ("square is positive", None)],
+        results(&reports)
+    );
+    Ok(())
+}
+
+#[test]
+fn failing() -> LangResult<()> {
+    let input = include_str!("failing.pj");
+    let reports = run_tests(input, None, true)?;
+    assert_eq!(
+        vec![
+            (
+                "first assertion",
+                Some("assertion failed: `6` is not equal to `9`")
+            ),
+            ("assert", Some("assertion failed")),
+            ("chars", Some("assertion failed: `a` is not equal to `b`")),
+        ],
+        results(&reports)
+    );
+
+    // The location of the first assertion, which stops the test.
+    let loc = reports[0].result.as_ref().unwrap_err().loc.unwrap();
+    assert_eq!("assert_eq", &input[loc.start..loc.end]);
+    // Each test has its own output, including the output of the nodes above it.
+    assert_eq!("0\n1\n", reports[0].output);
+    assert_eq!("0\n", reports[1].output);
+    Ok(())
+}
+
+#[test]
+fn filter() -> LangResult<()> {
+    let input = include_str!("passing.pj");
+    let reports = run_tests(input, Some("positive"), true)?;
+    assert_eq!(vec![("square is positive", None)], results(&reports));
+    Ok(())
+}
+
+#[test]
+fn isolated() -> LangResult<()> {
+    let input = include_str!("isolated.pj");
+    let reports = run_tests(input, None, true)?;
+    assert!(reports.iter().all(|report| report.result.is_ok()));
+    assert_eq!(3, reports.len());
+    Ok(())
+}
+
+#[test]
+fn instance() -> LangResult<()> {
+    let input = include_str!("instance.pj");
+    let reports = run_tests(input, None, true)?;
+    assert_eq!(
+        vec![("user instance", Some("assertion failed"))],
+        results(&reports)
+    );
+    Ok(())
+}
+
+#[test]
+fn overflow() -> LangResult<()> {
+    let input = include_str!("overflow.pj");
+    let reports = run_tests(input, None, true)?;
+    let failure = reports[0].result.as_ref().unwrap_err();
+    assert_eq!(None, failure.loc);
+    assert_eq!(
+        "Binary operation `+` overflowed with operands `9223372036854775807` and `1`",
+        failure.message
+    );
+    assert!(run_tests(input, None, false)?[0].result.is_err());
+    Ok(())
+}
+
+#[test]
+fn not_run() -> LangResult<()> {
+    let input = include_str!("not_run.pj");
+    assert_eq!("1\n", run(input)?);
+    assert_eq!(1, find_tests(input)?.len());
+    Ok(())
+}
+
+#[test]
+fn nested() {
+    let input = include_str!("nested.pj");
+    let err = find_tests(input).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        LangErrorKind::Parse(ParsingErrorKind::UnexpectedToken { .. })
+    ));
+}
+
+#[test]
+fn untyped() {
+    let input = include_str!("untyped.pj");
+    let err = run_tests(input, None, true).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        LangErrorKind::Ty(TyErrorKind::Mismatch { .. })
+    ));
+}
@@ -0,0 +1,86 @@
+use std::{
+    cell::RefCell,
+    include_str,
+    io::{self, Write},
+    rc::Rc,
+};
+
+use pijama_driver::{run_with_machine, LangResult};
+use pijama_machine::trace::Trace;
+
+use crate::machine_builder;
+
+/// A buffer written by the trace that can still be read after the machine is built.
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Runs `input` and returns its trace.
+///
+/// The output of the program is checked to be the same as without the trace.
+fn run_traced(input: &str, trace: Trace) -> LangResult<String> {
+    let buffer = SharedBuffer::default();
+    let mut output = Vec::default();
+    run_with_machine(
+        input,
+        machine_builder(&mut output)
+            .with_trace(trace.with_output(buffer.clone()))
+            .build(),
+    )?;
+    assert_eq!(crate::run(input)?, String::from_utf8(output).unwrap());
+
+    let trace = buffer.0.borrow().clone();
+    Ok(String::from_utf8(trace).unwrap())
+}
+
+#[test]
+fn reductions() -> LangResult<()> {
+    let input = include_str!("double.pj");
+    let output = run_traced(input, Trace::default())?;
+    assert_eq!(include_str!("double.trace"), output);
+    Ok(())
+}
+
+#[test]
+fn locations() -> LangResult<()> {
+    let input = include_str!("reductions.pj");
+    let output = run_traced(input, Trace::default().with_locations(input))?;
+    assert_eq!(include_str!("reductions.trace"), output);
+    Ok(())
+}
+
+#[test]
+fn max_depth() -> LangResult<()> {
+    let input = include_str!("double.pj");
+    let output = run_traced(input, Trace::default().with_max_depth(0))?;
+    let expected = include_str!("double.trace")
+        .lines()
+        .filter(|line| !line.contains("]  "))
+        .map(|line| format!("{}\n", line))
+        .collect::<String>();
+    assert_eq!(expected, output);
+    Ok(())
+}
+
+#[test]
+fn max_steps() -> LangResult<()> {
+    let input = include_str!("double.pj");
+    let output = run_traced(input, Trace::default().with_max_steps(2))?;
+    let mut lines = include_str!("double.trace").lines();
+    let expected = format!(
+        "{}\n{}\n[trace stopped after 2 steps]\n",
+        lines.next().unwrap(),
+        lines.next().unwrap()
+    );
+    assert_eq!(expected, output);
+    Ok(())
+}
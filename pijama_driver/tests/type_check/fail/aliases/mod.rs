@@ -0,0 +1,56 @@
+use crate::{test_type, type_check::type_check};
+
+use pijama_driver::LangErrorKind;
+use pijama_hir::LowerErrorKind;
+use pijama_ty::Ty;
+use pijama_tycheck::TyErrorKind;
+
+test_type!(
+    unknown_alias,
+    Err(&LangErrorKind::Lower(LowerErrorKind::UnknownTy(
+        "Foo".to_owned()
+    )))
+);
+test_type!(
+    self_referential_alias,
+    Err(&LangErrorKind::Lower(LowerErrorKind::CyclicTyAlias(
+        "A".to_owned()
+    )))
+);
+test_type!(
+    cyclic_aliases,
+    Err(&LangErrorKind::Lower(LowerErrorKind::CyclicTyAlias(
+        "A".to_owned()
+    )))
+);
+test_type!(
+    alias_out_of_scope,
+    Err(&LangErrorKind::Lower(LowerErrorKind::UnknownTy(
+        "T".to_owned()
+    )))
+);
+test_type!(
+    alias_mismatch,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Int,
+        found: Ty::Bool,
+    }))
+);
+
+#[test]
+fn alias_mismatch_shows_alias() {
+    let input = include_str!("alias_mismatch.pj");
+    let err = type_check(input).unwrap_err();
+    let labels = err
+        .labels()
+        .iter()
+        .map(|label| {
+            (
+                &input[label.loc.start..label.loc.end],
+                label.content.as_str(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(labels, [("IntFn", "expected `IntFn` due to this")]);
+    assert_eq!(err.notes()[0], "`IntFn` is an alias for `Int -> Int`");
+}
@@ -0,0 +1,52 @@
+use crate::{test_type, type_check::type_check};
+
+use pijama_driver::LangErrorKind;
+use pijama_hir::LowerErrorKind;
+use pijama_ty::Ty;
+use pijama_tycheck::TyErrorKind;
+
+test_type!(
+    ascription_mismatch,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Int,
+        found: Ty::Bool,
+    }))
+);
+test_type!(
+    nested_ascription_mismatch,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Bool,
+        found: Ty::Int,
+    }))
+);
+test_type!(
+    ascribed_fn_mismatch,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Int,
+        found: Ty::Bool,
+    }))
+);
+test_type!(
+    unknown_ty,
+    Err(&LangErrorKind::Lower(LowerErrorKind::UnknownTy(
+        "Foo".to_owned()
+    )))
+);
+
+#[test]
+fn ascription_mismatch_points_at_expression() {
+    let input = include_str!("ascription_mismatch.pj");
+    let err = type_check(input).unwrap_err();
+    let labels = err
+        .labels()
+        .iter()
+        .map(|label| {
+            (
+                &input[label.loc.start..label.loc.end],
+                label.content.as_str(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(labels, [("Int", "expected due to this")]);
+    assert_eq!(&input[err.loc().start..err.loc().end], "true");
+}
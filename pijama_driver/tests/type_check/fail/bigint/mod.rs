@@ -0,0 +1,32 @@
+use crate::test_type;
+
+use pijama_driver::LangErrorKind;
+use pijama_parser::ParsingErrorKind;
+use pijama_ty::Ty;
+use pijama_tycheck::TyErrorKind;
+
+test_type!(
+    big_with_int,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::BigInt,
+        found: Ty::Int,
+    }))
+);
+test_type!(
+    oversized_int,
+    Err(&LangErrorKind::Parse(ParsingErrorKind::IntOutOfRange))
+);
+
+#[test]
+fn oversized_int_suggests_suffix() {
+    let input = include_str!("oversized_int.pj");
+    let err = crate::type_check::type_check(input).unwrap_err();
+    assert_eq!(
+        &input[err.loc().start..err.loc().end],
+        "99999999999999999999"
+    );
+    assert_eq!(
+        err.kind().help().as_deref(),
+        Some("consider adding the `n` suffix to make it a `BigInt`")
+    );
+}
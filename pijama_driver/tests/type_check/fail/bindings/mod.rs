@@ -1,6 +1,7 @@
 use crate::test_type;
 
 use pijama_driver::LangErrorKind;
+use pijama_hir::LowerErrorKind;
 use pijama_ty::Ty;
 use pijama_tycheck::TyErrorKind;
 
@@ -11,3 +12,27 @@ test_type!(
         found: Ty::Bool
     }))
 );
+
+test_type!(
+    let_bool_to_int,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Int,
+        found: Ty::Bool
+    }))
+);
+
+test_type!(
+    let_out_of_scope,
+    Err(&LangErrorKind::Lower(LowerErrorKind::Unbounded {
+        name: "x".to_owned(),
+        suggestion: None,
+    }))
+);
+
+test_type!(
+    where_out_of_scope,
+    Err(&LangErrorKind::Lower(LowerErrorKind::Unbounded {
+        name: "twice".to_owned(),
+        suggestion: None,
+    }))
+);
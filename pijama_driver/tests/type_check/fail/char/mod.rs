@@ -0,0 +1,27 @@
+use crate::test_type;
+
+use pijama_driver::LangErrorKind;
+use pijama_ty::Ty;
+use pijama_tycheck::TyErrorKind;
+
+test_type!(
+    char_arithmetic,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Int,
+        found: Ty::Char,
+    }))
+);
+test_type!(
+    chr_char,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Int,
+        found: Ty::Char,
+    }))
+);
+test_type!(
+    ord_int,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Char,
+        found: Ty::Int,
+    }))
+);
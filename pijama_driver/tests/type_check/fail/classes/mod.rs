@@ -0,0 +1,59 @@
+use crate::test_type;
+
+use pijama_driver::LangErrorKind;
+use pijama_hir::LowerErrorKind;
+use pijama_ty::Ty;
+use pijama_tycheck::TyErrorKind;
+
+test_type!(
+    eq_fn,
+    Err(&LangErrorKind::Ty(TyErrorKind::NoInstance {
+        class: "Eq".to_owned(),
+        ty: Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)),
+    }))
+);
+
+test_type!(
+    no_instance,
+    Err(&LangErrorKind::Ty(TyErrorKind::NoInstance {
+        class: "Describe".to_owned(),
+        ty: Ty::Bool,
+    }))
+);
+
+test_type!(
+    instance_out_of_scope,
+    Err(&LangErrorKind::Ty(TyErrorKind::NoInstance {
+        class: "Ord".to_owned(),
+        ty: Ty::Bool,
+    }))
+);
+
+test_type!(
+    unknown_class,
+    Err(&LangErrorKind::Lower(LowerErrorKind::UnknownClass(
+        "Describe".to_owned()
+    )))
+);
+
+test_type!(
+    unexpected_method,
+    Err(&LangErrorKind::Lower(LowerErrorKind::UnexpectedMethod(
+        "eq".to_owned()
+    )))
+);
+
+test_type!(
+    missing_method,
+    Err(&LangErrorKind::Lower(LowerErrorKind::MissingMethod(
+        "neq".to_owned()
+    )))
+);
+
+test_type!(
+    method_signature_mismatch,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Int,
+        found: Ty::Bool,
+    }))
+);
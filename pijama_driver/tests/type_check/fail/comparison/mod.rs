@@ -11,9 +11,9 @@ use pijama_driver::LangErrorKind;
 // Test all int comparison operators with bool arguments
 test_type_for_all_comparision_binops!(
     wrong_type_placeholder,
-    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
-        expected: Ty::Int,
-        found: Ty::Bool
+    Err(&LangErrorKind::Ty(TyErrorKind::NoInstance {
+        class: "Ord".to_owned(),
+        ty: Ty::Bool
     })),
     OPERATOR
 );
@@ -0,0 +1,45 @@
+use crate::{test_type, type_check::type_check};
+
+use pijama_common::IntTy;
+use pijama_driver::LangErrorKind;
+use pijama_ty::Ty;
+use pijama_tycheck::TyErrorKind;
+
+test_type!(
+    unsuffixed_operand,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Fixed(IntTy::U8),
+        found: Ty::Int,
+    }))
+);
+test_type!(
+    mixed_widths,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Fixed(IntTy::U8),
+        found: Ty::Fixed(IntTy::U16),
+    }))
+);
+test_type!(
+    convert_bool,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Int,
+        found: Ty::Bool,
+    }))
+);
+test_type!(
+    unsuffixed_binding,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Fixed(IntTy::U32),
+        found: Ty::Int,
+    }))
+);
+
+#[test]
+fn convert_bool_note() {
+    let input = include_str!("convert_bool.pj");
+    let err = type_check(input).unwrap_err();
+    assert_eq!(
+        err.notes(),
+        ["the function `to_u8` requires an argument of an integer type"]
+    );
+}
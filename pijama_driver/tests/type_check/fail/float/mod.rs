@@ -0,0 +1,44 @@
+use crate::{test_type, type_check::type_check};
+
+use pijama_driver::LangErrorKind;
+use pijama_ty::Ty;
+use pijama_tycheck::TyErrorKind;
+
+test_type!(
+    float_with_int,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Float,
+        found: Ty::Int,
+    }))
+);
+test_type!(
+    float_bitwise,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Int,
+        found: Ty::Float,
+    }))
+);
+test_type!(
+    truncate_int,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Float,
+        found: Ty::Int,
+    }))
+);
+test_type!(
+    to_float_bool,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Int,
+        found: Ty::Bool,
+    }))
+);
+
+#[test]
+fn to_float_bool_note() {
+    let input = include_str!("to_float_bool.pj");
+    let err = type_check(input).unwrap_err();
+    assert_eq!(
+        err.notes(),
+        ["the function `to_float` requires an argument of a numeric type"]
+    );
+}
@@ -45,3 +45,33 @@ test_type!(
         found: Ty::Int,
     }))
 );
+
+test_type!(
+    mutually_rec_fns_mismatch,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Int,
+        found: Ty::Bool
+    }))
+);
+
+test_type!(
+    pipe_not_callable,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Int,
+        found: Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int))
+    }))
+);
+test_type!(
+    pipe_literal,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Int,
+        found: Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Var(3)))
+    }))
+);
+test_type!(
+    pipe_mismatch,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Int,
+        found: Ty::Bool,
+    }))
+);
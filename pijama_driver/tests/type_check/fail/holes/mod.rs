@@ -0,0 +1,52 @@
+use crate::{test_type, type_check::type_check};
+
+use pijama_driver::LangErrorKind;
+use pijama_ty::Ty;
+use pijama_tycheck::TyErrorKind;
+
+test_type!(
+    annotated_hole,
+    Err(&LangErrorKind::Ty(TyErrorKind::Hole(Ty::Int)))
+);
+test_type!(
+    hole_in_call,
+    Err(&LangErrorKind::Ty(TyErrorKind::Hole(Ty::Arrow(
+        Box::new(Ty::Int),
+        Box::new(Ty::Bool)
+    ))))
+);
+test_type!(
+    inferred_hole,
+    Err(&LangErrorKind::Ty(TyErrorKind::Hole(Ty::Int)))
+);
+
+/// Returns the note of the error produced by type-checking `input`.
+fn hole_note(input: &str) -> String {
+    let err = type_check(input).unwrap_err();
+    err.notes()[0].clone()
+}
+
+#[test]
+fn hole_lists_compatible_locals() {
+    let input = include_str!("hole_in_call.pj");
+    assert_eq!(
+        hole_note(input),
+        "these locals in scope have a compatible type: `g: Int -> Bool`"
+    );
+}
+
+#[test]
+fn hole_ignores_shadowed_locals() {
+    let input = include_str!("shadowed_local.pj");
+    assert_eq!(
+        hole_note(input),
+        "there are no locals in scope with a compatible type"
+    );
+}
+
+#[test]
+fn earliest_hole_is_reported() {
+    let input = include_str!("earliest_hole.pj");
+    let err = type_check(input).unwrap_err();
+    assert_eq!(err.loc().start, input.find('_').unwrap());
+}
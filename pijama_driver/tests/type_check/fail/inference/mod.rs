@@ -0,0 +1,63 @@
+use crate::test_type;
+
+use pijama_driver::LangErrorKind;
+use pijama_ty::Ty;
+use pijama_tycheck::TyErrorKind;
+
+test_type!(
+    unused_lambda_arg,
+    Err(&LangErrorKind::Ty(TyErrorKind::NotConcrete {
+        ty: Ty::Var(3),
+        name: Some("x".to_owned()),
+    }))
+);
+
+test_type!(
+    unused_second_arg,
+    Err(&LangErrorKind::Ty(TyErrorKind::NotConcrete {
+        ty: Ty::Var(3),
+        name: Some("y".to_owned()),
+    }))
+);
+
+test_type!(
+    unused_fn_arg,
+    Err(&LangErrorKind::Ty(TyErrorKind::NotConcrete {
+        ty: Ty::Arrow(Box::new(Ty::Var(3)), Box::new(Ty::Int)),
+        name: Some("foo".to_owned()),
+    }))
+);
+
+test_type!(
+    print_unconstrained,
+    Err(&LangErrorKind::Ty(TyErrorKind::NotConcrete {
+        ty: Ty::Var(4),
+        name: Some("x".to_owned()),
+    }))
+);
+
+test_type!(
+    earliest_binding,
+    Err(&LangErrorKind::Ty(TyErrorKind::NotConcrete {
+        ty: Ty::Arrow(Box::new(Ty::Var(3)), Box::new(Ty::Int)),
+        name: Some("f".to_owned()),
+    }))
+);
+
+#[test]
+fn reports_ambiguous_variable() {
+    let input = include_str!("unused_fn_arg.pj");
+    // Type checking several times makes sure the error does not depend on hashing order.
+    for _ in 0..16 {
+        let err = crate::type_check::type_check(input).unwrap_err();
+        assert_eq!(&input[err.loc().start..err.loc().end], "foo");
+        assert_eq!(
+            err.notes(),
+            ["the type variable `?X3` could not be inferred"]
+        );
+        assert_eq!(
+            err.kind().help().as_deref(),
+            Some("consider adding a type annotation to `foo`")
+        );
+    }
+}
@@ -1,7 +1,19 @@
+mod aliases;
 mod arithmetic;
+mod ascriptions;
+mod bigint;
 mod bindings;
+mod char;
+mod classes;
 mod comparison;
 mod conditionals;
+mod fixed_width;
+mod float;
 mod functions;
+mod holes;
+mod inference;
 mod logic;
+mod operators;
+mod origins;
+mod sum_types;
 mod variables;
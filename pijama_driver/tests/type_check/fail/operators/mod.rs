@@ -0,0 +1,53 @@
+use crate::test_type;
+
+use pijama_ty::Ty;
+
+use pijama_tycheck::TyErrorKind;
+
+use pijama_driver::LangErrorKind;
+use pijama_parser::ParsingErrorKind;
+
+test_type!(
+    undefined_operator,
+    Err(&LangErrorKind::Parse(ParsingErrorKind::UndefinedOperator(
+        "<+>".to_owned()
+    )))
+);
+test_type!(
+    operator_out_of_scope,
+    Err(&LangErrorKind::Parse(ParsingErrorKind::UndefinedOperator(
+        "<+>".to_owned()
+    )))
+);
+test_type!(
+    conflicting_assoc,
+    Err(&LangErrorKind::Parse(
+        ParsingErrorKind::ConflictingOperators("<+>".to_owned())
+    ))
+);
+test_type!(
+    non_assoc_chain,
+    Err(&LangErrorKind::Parse(
+        ParsingErrorKind::ConflictingOperators("~=".to_owned())
+    ))
+);
+test_type!(
+    infix_mismatch,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Int,
+        found: Ty::Bool,
+    }))
+);
+test_type!(
+    section_mismatch,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Bool,
+        found: Ty::Int,
+    }))
+);
+test_type!(
+    section_undefined,
+    Err(&LangErrorKind::Parse(ParsingErrorKind::UndefinedOperator(
+        "<+>".to_owned()
+    )))
+);
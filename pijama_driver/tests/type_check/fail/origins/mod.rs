@@ -0,0 +1,165 @@
+use pijama_driver::LangError;
+
+use crate::type_check::type_check;
+
+/// Type checks `input` and returns the source code and message of each secondary label of the
+/// resulting error.
+fn labels(input: &str) -> Vec<(&str, String)> {
+    let err = type_check(input).unwrap_err();
+    err.labels()
+        .iter()
+        .map(|label| {
+            (
+                &input[label.loc.start..label.loc.end],
+                label.content.clone(),
+            )
+        })
+        .collect()
+}
+
+/// Type checks `input` and returns the resulting error.
+fn error(input: &str) -> LangError {
+    type_check(input).unwrap_err()
+}
+
+#[test]
+fn return_annotation() {
+    let input = include_str!("return_annotation.pj");
+    assert_eq!(
+        labels(input),
+        vec![("Bool", "expected due to this".to_owned())]
+    );
+}
+
+#[test]
+fn binding_annotation() {
+    let input = include_str!("binding_annotation.pj");
+    assert_eq!(
+        labels(input),
+        vec![("Int", "expected due to this".to_owned())]
+    );
+}
+
+#[test]
+fn unannotated_binding() {
+    let input = include_str!("unannotated_binding.pj");
+    assert_eq!(
+        labels(input),
+        vec![("f", "expected due to the type of this binding".to_owned())]
+    );
+}
+
+#[test]
+fn sibling_branch() {
+    let input = include_str!("sibling_branch.pj");
+    assert_eq!(
+        labels(input),
+        vec![(
+            "true",
+            "expected because this branch has type `Bool`".to_owned()
+        )]
+    );
+}
+
+#[test]
+fn equality_operand() {
+    let input = include_str!("equality_operand.pj");
+    assert_eq!(
+        labels(input),
+        vec![(
+            "1",
+            "expected because the other operand of `==` has type `Int`".to_owned()
+        )]
+    );
+}
+
+#[test]
+fn arithmetic_operand() {
+    let input = include_str!("arithmetic_operand.pj");
+    let err = error(input);
+    assert!(err.labels().is_empty());
+    assert_eq!(
+        err.notes(),
+        ["the operator `+` requires operands of type `Int`"]
+    );
+}
+
+#[test]
+fn condition() {
+    let input = include_str!("condition.pj");
+    let err = error(input);
+    assert!(err.labels().is_empty());
+    assert_eq!(err.notes(), ["conditions must have type `Bool`"]);
+}
+
+#[test]
+fn sum_argument() {
+    let input = include_str!("sum_argument.pj");
+    let err = error(input);
+    assert!(err.labels().is_empty());
+    assert_eq!(
+        err.notes(),
+        ["the function `unwrap_or` requires an argument of type `Option` or `Result`"]
+    );
+}
+
+#[test]
+fn call_argument() {
+    let input = include_str!("call_argument.pj");
+    assert_eq!(
+        labels(input),
+        vec![(
+            "add",
+            "this function has type `Int -> Int -> Int`".to_owned()
+        )]
+    );
+}
+
+#[test]
+fn higher_order_argument() {
+    let input = include_str!("higher_order_argument.pj");
+    let err = error(input);
+    assert_eq!(
+        err.notes(),
+        ["expected type `(Int -> Int) -> Int -> Int`\n   found type `(Bool -> Bool) -> Int -> Int`"]
+    );
+}
+
+#[test]
+fn nested_arrow_annotation() {
+    let input = include_str!("nested_arrow_annotation.pj");
+    assert_eq!(
+        labels(input),
+        vec![("Int -> Bool", "expected due to this".to_owned())]
+    );
+    assert_eq!(
+        error(input).notes(),
+        ["expected type `Int -> Bool`\n   found type `(Int -> Int) -> Int -> Int`"]
+    );
+}
+
+#[test]
+fn pipe_argument() {
+    let input = include_str!("pipe_argument.pj");
+    assert_eq!(
+        labels(input),
+        vec![(
+            "add",
+            "the right side of this pipe has type `Int -> Int -> Int`".to_owned()
+        )]
+    );
+}
+
+#[test]
+fn pipe_not_callable() {
+    let input = include_str!("pipe_not_callable.pj");
+    let err = error(input);
+    assert_eq!(
+        labels(input),
+        vec![("2", "the right side of this pipe has type `Int`".to_owned())]
+    );
+    assert_eq!(
+        err.notes(),
+        ["the left side of a pipe is passed as the first argument of its right side"]
+    );
+}
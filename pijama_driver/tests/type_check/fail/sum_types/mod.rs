@@ -0,0 +1,27 @@
+use crate::test_type;
+
+use pijama_driver::LangErrorKind;
+use pijama_ty::Ty;
+use pijama_tycheck::TyErrorKind;
+
+test_type!(
+    unwrap_or_default,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Int,
+        found: Ty::Bool,
+    }))
+);
+test_type!(
+    result_as_option,
+    Err(&LangErrorKind::Ty(TyErrorKind::Mismatch {
+        expected: Ty::Option(Box::new(Ty::Int)),
+        found: Ty::Result(Box::new(Ty::Int), Box::new(Ty::Bool)),
+    }))
+);
+test_type!(
+    print_option,
+    Err(&LangErrorKind::Ty(TyErrorKind::NoInstance {
+        class: "Show".to_owned(),
+        ty: Ty::Option(Box::new(Ty::Int)),
+    }))
+);
@@ -7,9 +7,10 @@ use pijama_tycheck::TyErrorKind;
 
 test_type!(
     unbounded,
-    Err(&LangErrorKind::Lower(LowerErrorKind::Unbounded(
-        "x".to_owned()
-    )))
+    Err(&LangErrorKind::Lower(LowerErrorKind::Unbounded {
+        name: "x".to_owned(),
+        suggestion: None,
+    }))
 );
 
 test_type!(
@@ -0,0 +1,32 @@
+use crate::test_type;
+
+use pijama_ty::Ty;
+
+test_type!(
+    alias_in_fn_arg,
+    Ok(&Ty::Arrow(
+        Box::new(Ty::Arrow(
+            Box::new(Ty::Int),
+            Box::new(Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Bool)))
+        )),
+        Box::new(Ty::Arrow(
+            Box::new(Ty::Int),
+            Box::new(Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+        ))
+    ))
+);
+test_type!(
+    alias_in_binding,
+    Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+);
+test_type!(
+    adjacent_aliases,
+    Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Bool)))
+);
+test_type!(
+    alias_shadowing,
+    Ok(&Ty::Arrow(
+        Box::new(Ty::Int),
+        Box::new(Ty::Arrow(Box::new(Ty::Bool), Box::new(Ty::Bool)))
+    ))
+);
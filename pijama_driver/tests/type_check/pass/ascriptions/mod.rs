@@ -0,0 +1,24 @@
+use crate::test_type;
+
+use pijama_ty::Ty;
+
+test_type!(ascribed_literal, Ok(&Ty::Int));
+test_type!(
+    ascribed_polymorphic_fn,
+    Ok(&Ty::Arrow(Box::new(Ty::Bool), Box::new(Ty::Bool)))
+);
+test_type!(
+    ascribed_argument,
+    Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+);
+test_type!(
+    ascribed_with_alias,
+    Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+);
+test_type!(
+    fn_returning_anon_fn,
+    Ok(&Ty::Arrow(
+        Box::new(Ty::Int),
+        Box::new(Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+    ))
+);
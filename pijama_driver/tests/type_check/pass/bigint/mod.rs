@@ -0,0 +1,14 @@
+use crate::test_type;
+
+use pijama_ty::Ty;
+
+test_type!(big_literal, Ok(&Ty::BigInt));
+test_type!(oversized_literal, Ok(&Ty::BigInt));
+test_type!(
+    big_arithmetic,
+    Ok(&Ty::Arrow(
+        Box::new(Ty::BigInt),
+        Box::new(Ty::Arrow(Box::new(Ty::BigInt), Box::new(Ty::BigInt)))
+    ))
+);
+test_type!(big_conversion, Ok(&Ty::Bool));
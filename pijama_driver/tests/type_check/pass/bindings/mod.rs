@@ -3,3 +3,9 @@ use crate::test_type;
 use pijama_ty::Ty;
 
 test_type!(bind_int_to_int, Ok(&Ty::Int));
+test_type!(let_in, Ok(&Ty::Bool));
+test_type!(let_shadows_fn, Ok(&Ty::Int));
+test_type!(
+    where_rec,
+    Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+);
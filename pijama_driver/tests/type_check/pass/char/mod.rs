@@ -0,0 +1,13 @@
+use crate::test_type;
+
+use pijama_ty::Ty;
+
+test_type!(char_literal, Ok(&Ty::Char));
+test_type!(char_conversions, Ok(&Ty::Char));
+test_type!(
+    char_comparison,
+    Ok(&Ty::Arrow(
+        Box::new(Ty::Char),
+        Box::new(Ty::Arrow(Box::new(Ty::Char), Box::new(Ty::Bool)))
+    ))
+);
@@ -0,0 +1,11 @@
+use crate::test_type;
+
+use pijama_ty::Ty;
+
+test_type!(user_class, Ok(&Ty::Int));
+test_type!(ord_instance_for_bool, Ok(&Ty::Bool));
+test_type!(eq_instance_for_fn, Ok(&Ty::Bool));
+test_type!(builtin_method, Ok(&Ty::Bool));
+test_type!(method_as_value, Ok(&Ty::Bool));
+test_type!(instance_with_alias, Ok(&Ty::Unit));
+test_type!(only_instance_infers_self, Ok(&Ty::Int));
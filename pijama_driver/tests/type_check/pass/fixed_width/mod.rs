@@ -0,0 +1,26 @@
+use crate::test_type;
+
+use pijama_common::IntTy;
+use pijama_ty::Ty;
+
+test_type!(fixed_literal, Ok(&Ty::Fixed(IntTy::U8)));
+test_type!(inferred_fixed_operands, Ok(&Ty::Fixed(IntTy::U16)));
+test_type!(
+    operands_default_to_int,
+    Ok(&Ty::Arrow(
+        Box::new(Ty::Int),
+        Box::new(Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+    ))
+);
+test_type!(conversions, Ok(&Ty::Int));
+test_type!(
+    fixed_fn,
+    Ok(&Ty::Arrow(
+        Box::new(Ty::Fixed(IntTy::U64)),
+        Box::new(Ty::Arrow(
+            Box::new(Ty::Fixed(IntTy::U64)),
+            Box::new(Ty::Fixed(IntTy::U64))
+        ))
+    ))
+);
+test_type!(fixed_comparison, Ok(&Ty::Bool));
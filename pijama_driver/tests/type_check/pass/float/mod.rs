@@ -0,0 +1,14 @@
+use crate::test_type;
+
+use pijama_ty::Ty;
+
+test_type!(float_literal, Ok(&Ty::Float));
+test_type!(
+    float_arithmetic,
+    Ok(&Ty::Arrow(
+        Box::new(Ty::Float),
+        Box::new(Ty::Arrow(Box::new(Ty::Float), Box::new(Ty::Float)))
+    ))
+);
+test_type!(float_comparison, Ok(&Ty::Bool));
+test_type!(float_conversions, Ok(&Ty::Int));
@@ -16,3 +16,27 @@ test_type!(
     Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
 );
 test_type!(int_function_call_returns_int, Ok(&Ty::Int));
+test_type!(
+    anon_fn_from_int_to_int_with_type,
+    Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+);
+
+// Recursive functions
+test_type!(
+    rec_fn_without_return_ty,
+    Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+);
+test_type!(
+    rec_fn_without_types,
+    Ok(&Ty::Arrow(
+        Box::new(Ty::Int),
+        Box::new(Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+    ))
+);
+test_type!(
+    mutually_rec_fns,
+    Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Bool)))
+);
+
+// Pipes
+test_type!(pipe_call, Ok(&Ty::Bool));
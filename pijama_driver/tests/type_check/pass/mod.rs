@@ -1,8 +1,17 @@
+mod aliases;
 mod arithmetic;
+mod ascriptions;
+mod bigint;
 mod bindings;
+mod char;
+mod classes;
 mod comparison;
 mod conditionals;
+mod fixed_width;
+mod float;
 mod functions;
 mod literals;
 mod logic;
+mod operators;
 mod sequences;
+mod sum_types;
@@ -0,0 +1,42 @@
+use crate::test_type;
+
+use pijama_ty::Ty;
+
+test_type!(infix_call, Ok(&Ty::Int));
+test_type!(infix_polymorphic, Ok(&Ty::Bool));
+test_type!(
+    infix_in_scope,
+    Ok(&Ty::Arrow(
+        Box::new(Ty::Int),
+        Box::new(Ty::Arrow(
+            Box::new(Ty::Int),
+            Box::new(Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+        ))
+    ))
+);
+test_type!(
+    infix_shadowed,
+    Ok(&Ty::Arrow(
+        Box::new(Ty::Bool),
+        Box::new(Ty::Arrow(Box::new(Ty::Bool), Box::new(Ty::Bool)))
+    ))
+);
+test_type!(
+    section_both,
+    Ok(&Ty::Arrow(
+        Box::new(Ty::Int),
+        Box::new(Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+    ))
+);
+test_type!(
+    section_left,
+    Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+);
+test_type!(
+    section_right,
+    Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Bool)))
+);
+test_type!(
+    section_custom,
+    Ok(&Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Int)))
+);
@@ -0,0 +1,18 @@
+use crate::test_type;
+
+use pijama_ty::Ty;
+
+test_type!(option_literal, Ok(&Ty::Option(Box::new(Ty::Int))));
+test_type!(
+    result_annotation,
+    Ok(&Ty::Arrow(
+        Box::new(Ty::Int),
+        Box::new(Ty::Result(Box::new(Ty::Int), Box::new(Ty::Bool)))
+    ))
+);
+test_type!(map_option, Ok(&Ty::Option(Box::new(Ty::Bool))));
+test_type!(
+    map_result,
+    Ok(&Ty::Result(Box::new(Ty::Bool), Box::new(Ty::Bool)))
+);
+test_type!(and_then_alias, Ok(&Ty::Option(Box::new(Ty::Bool))));
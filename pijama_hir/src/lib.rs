@@ -40,6 +40,15 @@ pub enum TermKind {
     BinaryOp(BinOp, Box<Term>, Box<Term>),
     Cond(Box<Term>, Box<Term>, Box<Term>),
     Let(BindKind, LocalId, Box<Term>, Box<Term>),
+    LetGroup(Vec<(LocalId, Term)>, Box<Term>),
+    /// An instance of a type class, binding the dictionary of the instance to the local and its
+    /// methods to the locals of the group of mutually recursive functions.
+    Instance(LocalId, Vec<(LocalId, Term)>, Box<Term>),
+    /// A method of a type class, the dictionary it is selected from is chosen during
+    /// type-checking.
+    Method(String),
+    /// A missing term, its type is reported during type-checking.
+    Hole,
 }
 
 struct TermCtx<'ast, 'ctx> {
@@ -66,6 +75,8 @@ impl<'ast, 'ctx> fmt::Display for TermCtx<'ast, 'ctx> {
         match &self.term.kind {
             TermKind::Lit(lit) => write!(f, "{}", lit),
             TermKind::PrimFn(prim) => write!(f, "{}", prim),
+            TermKind::Method(name) => write!(f, "{}", name),
+            TermKind::Hole => write!(f, "_"),
             TermKind::Var(local_id) => write!(f, "{}", self.get_local(*local_id)),
             TermKind::Abs(arg_id, body) => write!(
                 f,
@@ -108,6 +119,34 @@ impl<'ast, 'ctx> fmt::Display for TermCtx<'ast, 'ctx> {
                     self.spawn(tail.as_ref())
                 )
             }
+            TermKind::LetGroup(binds, tail) => {
+                write!(f, "(letrec")?;
+                for (i, (lhs_id, rhs)) in binds.iter().enumerate() {
+                    let sep = if i == 0 { "" } else { " and" };
+                    write!(
+                        f,
+                        "{} {} = {}",
+                        sep,
+                        self.get_local(*lhs_id),
+                        self.spawn(rhs)
+                    )?;
+                }
+                write!(f, " in {})", self.spawn(tail.as_ref()))
+            }
+            TermKind::Instance(dict_id, binds, tail) => {
+                write!(f, "(instance {} =", self.get_local(*dict_id))?;
+                for (i, (lhs_id, rhs)) in binds.iter().enumerate() {
+                    let sep = if i == 0 { "" } else { " and" };
+                    write!(
+                        f,
+                        "{} {} = {}",
+                        sep,
+                        self.get_local(*lhs_id),
+                        self.spawn(rhs)
+                    )?;
+                }
+                write!(f, " in {})", self.spawn(tail.as_ref()))
+            }
         }
     }
 }
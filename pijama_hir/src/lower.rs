@@ -1,16 +1,16 @@
 use thiserror::Error;
 
 use pijama_ast::{
-    analysis::is_fn_def_recursive,
-    node::{Block, Branch, Expression, Node, Statement},
+    analysis::{fn_def_groups, is_fn_def_recursive},
+    node::{Block, Branch, Expression, InfixOp, Method, MethodSig, Node, Statement},
     ty::{Ty as AstTy, TyAnnotation},
 };
 use pijama_common::{
     location::{Located, LocatedError, Location},
-    BinOp, Local, UnOp,
+    BinOp, IntTy, Local, Primitive, UnOp,
 };
-use pijama_ctx::{Context, ContextExt, LocalId, TermId, TypeInfo};
-use pijama_ty::Ty;
+use pijama_ctx::{BuiltinMethod, Context, ContextExt, Dict, LocalId, MethodUse, TermId, TypeInfo};
+use pijama_ty::{Scheme, Ty};
 
 use crate::{BindKind, Term, TermKind};
 
@@ -27,51 +27,393 @@ pub type LowerResult<T> = Result<T, LowerError>;
 
 #[derive(Error, Debug, Eq, PartialEq)]
 pub enum LowerErrorKind {
-    #[error("Required type annotation is missing")]
-    RequiredTy,
-    #[error("Anonymous functions cannot have a return type annotation")]
-    AnonWithTy,
-    #[error("Local {0} is not bounded in the current scope")]
-    Unbounded(String),
+    #[error("Local {name} is not bounded in the current scope")]
+    Unbounded {
+        name: String,
+        suggestion: Option<String>,
+    },
+    #[error("Type {0} is not defined in the current scope")]
+    UnknownTy(String),
+    #[error("Type alias {0} is defined in terms of itself")]
+    CyclicTyAlias(String),
+    #[error("Class {0} is not defined in the current scope")]
+    UnknownClass(String),
+    #[error("Method {0} is not expected in this instance")]
+    UnexpectedMethod(String),
+    #[error("Instance is missing method {0}")]
+    MissingMethod(String),
+}
+
+impl LowerErrorKind {
+    /// Returns the stable code identifying this kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LowerErrorKind::Unbounded { .. } => "E0103",
+            LowerErrorKind::UnknownTy(_) => "E0104",
+            LowerErrorKind::CyclicTyAlias(_) => "E0105",
+            LowerErrorKind::UnknownClass(_) => "E0106",
+            LowerErrorKind::UnexpectedMethod(_) => "E0107",
+            LowerErrorKind::MissingMethod(_) => "E0108",
+        }
+    }
+
+    /// Returns a hint on how to fix this error, if there is one.
+    pub fn help(&self) -> Option<String> {
+        match self {
+            LowerErrorKind::Unbounded {
+                suggestion: Some(suggestion),
+                ..
+            } => Some(format!("did you mean `{}`?", suggestion)),
+            _ => None,
+        }
+    }
 }
 
 pub type LowerError = LocatedError<LowerErrorKind>;
 
-fn require_ty(ty: &AstTy, loc: Location) -> LowerResult<()> {
-    if let AstTy::Missing = ty {
-        Err(LowerError::new(LowerErrorKind::RequiredTy, loc))
-    } else {
-        Ok(())
+/// Returns the edit distance between two strings.
+///
+/// This is the minimum number of single-character insertions, deletions, substitutions and
+/// transpositions of adjacent characters required to transform one string into the other. Also
+/// known as the optimal string alignment distance.
+fn edit_distance(s1: &str, s2: &str) -> usize {
+    let s1: Vec<char> = s1.chars().collect();
+    let s2: Vec<char> = s2.chars().collect();
+    // `dist[i][j]` stores the distance between the first `i` characters of `s1` and the first `j`
+    // characters of `s2`.
+    let mut dist = vec![vec![0; s2.len() + 1]; s1.len() + 1];
+
+    for (i, row) in dist.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, dist) in dist[0].iter_mut().enumerate() {
+        *dist = j;
     }
+
+    for i in 1..=s1.len() {
+        for j in 1..=s2.len() {
+            let cost = if s1[i - 1] == s2[j - 1] { 0 } else { 1 };
+            dist[i][j] = (dist[i - 1][j] + 1)
+                .min(dist[i][j - 1] + 1)
+                .min(dist[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && s1[i - 1] == s2[j - 2] && s1[i - 2] == s2[j - 1] {
+                dist[i][j] = dist[i][j].min(dist[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    dist[s1.len()][s2.len()]
+}
+
+/// Index of the built-in `Show` class in the stack of classes.
+const SHOW: usize = 0;
+/// Index of the built-in `Eq` class in the stack of classes.
+const EQ: usize = 1;
+/// Index of the built-in `Ord` class in the stack of classes.
+const ORD: usize = 2;
+
+/// Names of the methods of the built-in `Eq` class and the operators implementing them.
+const EQ_METHODS: [(&str, BinOp); 2] = [("eq", BinOp::Eq), ("neq", BinOp::Neq)];
+/// Names of the methods of the built-in `Ord` class and the operators implementing them.
+const ORD_METHODS: [(&str, BinOp); 4] = [
+    ("lt", BinOp::Lt),
+    ("lte", BinOp::Lte),
+    ("gt", BinOp::Gt),
+    ("gte", BinOp::Gte),
+];
+
+/// Returns the indices of the class and method of a built-in class implemented by `op`, if any.
+fn builtin_method(op: BinOp) -> Option<(usize, usize)> {
+    EQ_METHODS
+        .iter()
+        .position(|(_, op2)| *op2 == op)
+        .map(|index| (EQ, index))
+        .or_else(|| {
+            ORD_METHODS
+                .iter()
+                .position(|(_, op2)| *op2 == op)
+                .map(|index| (ORD, index))
+        })
+}
+
+/// Returns the primitive function named `name`, if any.
+///
+/// Primitive functions are predefined in every scope, so they are shadowed by any local or method
+/// with the same name.
+fn find_primitive(name: Local) -> Option<Primitive> {
+    match name {
+        Local::Name(name) => Primitive::ALL
+            .iter()
+            .copied()
+            .find(|prim| prim.to_string() == name),
+        Local::Wildcard | Local::Temp(_) => None,
+    }
+}
+
+/// Returns all the numeric types.
+fn num_tys() -> impl Iterator<Item = Ty> {
+    std::iter::once(Ty::Int)
+        .chain(IntTy::ALL.iter().copied().map(Ty::Fixed))
+        .chain([Ty::BigInt, Ty::Float])
 }
 
 struct Scope<'ast, 'ctx> {
     ctx: &'ctx mut Context<'ast>,
     locals: Vec<(Local<'ast>, LocalId)>,
+    ty_aliases: Vec<(&'ast str, Ty)>,
+    classes: Vec<Class<'ast>>,
+    instances: Vec<Instance>,
+    /// Index of the type variable standing for `Self` in the signatures of the methods of a class.
+    ///
+    /// This variable comes from the context, so substituting it never replaces any other type
+    /// variable.
+    self_var: usize,
 }
 
 impl<'ast, 'ctx> Scope<'ast, 'ctx> {
     fn new(ctx: &'ctx mut Context<'ast>) -> Self {
-        Self {
+        let self_var = match ctx.new_ty() {
+            Ty::Var(index) => index,
+            _ => unreachable!("the context only generates type variables"),
+        };
+        let mut scope = Self {
             ctx,
             locals: vec![],
+            ty_aliases: vec![],
+            classes: vec![],
+            instances: vec![],
+            self_var,
+        };
+        scope.declare_builtin_classes();
+        scope
+    }
+
+    /// Declares the `Show`, `Eq` and `Ord` classes with instances for the built-in types.
+    ///
+    /// Every numeric type and `Char` have an instance of each one of these classes.
+    ///
+    /// The methods of these instances are implemented by the `print` primitive and the comparison
+    /// operators. Using `print` or a comparison operator is a use of the corresponding method.
+    fn declare_builtin_classes(&mut self) {
+        let self_ty = Ty::Var(self.self_var);
+        let show_ty = Ty::Arrow(Box::new(self_ty.clone()), Box::new(Ty::Unit));
+        let cmp_ty = Ty::Arrow(
+            Box::new(self_ty.clone()),
+            Box::new(Ty::Arrow(Box::new(self_ty), Box::new(Ty::Bool))),
+        );
+
+        self.classes = vec![
+            Class {
+                name: "Show",
+                methods: vec![(Local::Name("print"), show_ty)],
+            },
+            Class {
+                name: "Eq",
+                methods: EQ_METHODS
+                    .iter()
+                    .map(|(name, _)| (Local::Name(name), cmp_ty.clone()))
+                    .collect(),
+            },
+            Class {
+                name: "Ord",
+                methods: ORD_METHODS
+                    .iter()
+                    .map(|(name, _)| (Local::Name(name), cmp_ty.clone()))
+                    .collect(),
+            },
+        ];
+
+        // Every function can be shown, whatever the types of its argument and return value.
+        let arrow = Ty::Arrow(Box::new(self.ctx.new_ty()), Box::new(self.ctx.new_ty()));
+        for ty in num_tys().chain([Ty::Char, Ty::Bool, Ty::Unit, arrow]) {
+            self.instances.push(Instance {
+                class: SHOW,
+                ty: Scheme::new(ty),
+                dict: Dict::Builtin(vec![BuiltinMethod::Prim(Primitive::Print)]),
+            });
+        }
+        for ty in num_tys().chain([Ty::Char, Ty::Bool, Ty::Unit]) {
+            self.instances.push(Instance {
+                class: EQ,
+                ty: Scheme::new(ty),
+                dict: Dict::Builtin(
+                    EQ_METHODS
+                        .iter()
+                        .map(|(_, op)| BuiltinMethod::BinOp(*op))
+                        .collect(),
+                ),
+            });
+        }
+        for ty in num_tys().chain([Ty::Char]) {
+            self.instances.push(Instance {
+                class: ORD,
+                ty: Scheme::new(ty),
+                dict: Dict::Builtin(
+                    ORD_METHODS
+                        .iter()
+                        .map(|(_, op)| BuiltinMethod::BinOp(*op))
+                        .collect(),
+                ),
+            });
         }
     }
 
-    fn lower_ty(&mut self, ty: AstTy) -> Ty {
-        match ty {
+    /// Returns the indices of the innermost class with a method named `name` and of such method.
+    fn find_method(&self, name: Local<'ast>) -> Option<(usize, usize)> {
+        self.classes
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(class, Class { methods, .. })| {
+                let method = methods.iter().position(|(name2, _)| *name2 == name)?;
+                Some((class, method))
+            })
+    }
+
+    /// Stores that the term `term_id` uses the `method`-th method of the `class`-th class and
+    /// returns the type standing for `Self` in such use.
+    ///
+    /// The dictionary of every instance of the class in the current scope is a candidate to select
+    /// the method from. The type-checker picks one of them once the type standing for `Self` is
+    /// known.
+    fn use_method(&mut self, term_id: TermId, class: usize, method: usize) -> Ty {
+        let self_ty = self.ctx.new_ty();
+        let instances = self
+            .instances
+            .iter()
+            .filter(|instance| instance.class == class)
+            .map(|instance| (instance.ty.clone(), instance.dict.clone()))
+            .collect();
+        self.ctx.save_method_use(
+            term_id,
+            MethodUse {
+                class: self.classes[class].name.to_owned(),
+                method,
+                methods: self.classes[class].methods.len(),
+                self_ty: self_ty.clone(),
+                instances,
+            },
+        );
+        self_ty
+    }
+
+    /// Lowers a type annotation written at `loc`.
+    ///
+    /// The location of every written annotation is saved in the context. If the whole annotation
+    /// is a type alias, its name is saved too so it can be shown in type errors.
+    fn lower_ty(&mut self, ty: AstTy, loc: Location) -> LowerResult<Ty> {
+        match &ty {
+            AstTy::Missing => (),
+            AstTy::Alias(name) => {
+                self.ctx.save_annotation(loc);
+                self.ctx.save_ty_alias(loc, name.clone());
+            }
+            _ => self.ctx.save_annotation(loc),
+        }
+        self.lower_ty_inner(&ty, loc)
+    }
+
+    /// Lowers the type `ty` written at `loc` using the type aliases in scope.
+    fn lower_ty_inner(&mut self, ty: &AstTy, loc: Location) -> LowerResult<Ty> {
+        self.lower_ty_with(ty, loc, &mut |this, name, loc| this.get_ty_alias(name, loc))
+    }
+
+    /// Lowers the type `ty` written at `loc`, using `expand_alias` to find the type of each type
+    /// alias in it.
+    fn lower_ty_with(
+        &mut self,
+        ty: &AstTy,
+        loc: Location,
+        expand_alias: &mut dyn FnMut(&mut Self, &str, Location) -> LowerResult<Ty>,
+    ) -> LowerResult<Ty> {
+        let ty = match ty {
             AstTy::Bool => Ty::Bool,
             AstTy::Int => Ty::Int,
+            AstTy::Fixed(ty) => Ty::Fixed(*ty),
+            AstTy::BigInt => Ty::BigInt,
+            AstTy::Float => Ty::Float,
+            AstTy::Char => Ty::Char,
             AstTy::Unit => Ty::Unit,
             AstTy::Missing => self.ctx.new_ty(),
-            AstTy::Arrow(ty1, ty2) => {
-                Ty::Arrow(Box::new(self.lower_ty(*ty1)), Box::new(self.lower_ty(*ty2)))
-            }
+            AstTy::Arrow(ty1, ty2) => Ty::Arrow(
+                Box::new(self.lower_ty_with(ty1, loc, expand_alias)?),
+                Box::new(self.lower_ty_with(ty2, loc, expand_alias)?),
+            ),
+            AstTy::Option(ty) => Ty::Option(Box::new(self.lower_ty_with(ty, loc, expand_alias)?)),
+            AstTy::Result(ty1, ty2) => Ty::Result(
+                Box::new(self.lower_ty_with(ty1, loc, expand_alias)?),
+                Box::new(self.lower_ty_with(ty2, loc, expand_alias)?),
+            ),
+            AstTy::Alias(name) => expand_alias(self, name, loc)?,
+        };
+        Ok(ty)
+    }
+
+    /// Returns the type of the innermost type alias named `name`.
+    fn get_ty_alias(&self, name: &str, loc: Location) -> LowerResult<Ty> {
+        self.ty_aliases
+            .iter()
+            .rev()
+            .find(|(name2, _)| *name2 == name)
+            .map(|(_, ty)| ty.clone())
+            .ok_or_else(|| LowerError::new(LowerErrorKind::UnknownTy(name.to_owned()), loc))
+    }
+
+    /// Resolves the types of a sequence of adjacent type alias declarations.
+    ///
+    /// The aliases in the sequence can use each other regardless of the order in which they were
+    /// declared, which means that they can form cycles. An error is returned if any of them is
+    /// defined in terms of itself.
+    fn resolve_ty_aliases(&mut self, aliases: &[TyAlias<'ast>]) -> LowerResult<Vec<Ty>> {
+        let mut resolved = vec![None; aliases.len()];
+        let mut visiting = Vec::new();
+        for index in 0..aliases.len() {
+            self.resolve_ty_alias(aliases, index, &mut resolved, &mut visiting)?;
         }
+        Ok(resolved.into_iter().map(Option::unwrap).collect())
     }
 
-    fn push_local(&mut self, local: TyAnnotation<Located<Local<'ast>>>) -> LocalId {
-        let ty = self.lower_ty(local.ty.content);
+    /// Resolves the type of the `index`-th type alias in `aliases`.
+    ///
+    /// The `visiting` stack holds the indices of the aliases whose resolution depends on the
+    /// current one. Finding `index` there means that the alias is defined in terms of itself.
+    fn resolve_ty_alias(
+        &mut self,
+        aliases: &[TyAlias<'ast>],
+        index: usize,
+        resolved: &mut Vec<Option<Ty>>,
+        visiting: &mut Vec<usize>,
+    ) -> LowerResult<Ty> {
+        if let Some(ty) = &resolved[index] {
+            return Ok(ty.clone());
+        }
+
+        let alias = &aliases[index];
+        if visiting.contains(&index) {
+            return Err(LowerError::new(
+                LowerErrorKind::CyclicTyAlias(alias.name.content.to_owned()),
+                alias.name.loc,
+            ));
+        }
+
+        visiting.push(index);
+        // Aliases declared in the same sequence shadow the ones declared before it.
+        let mut expand_alias = |this: &mut Self, name: &str, loc: Location| {
+            let index = aliases.iter().position(|alias| alias.name.content == name);
+            match index {
+                Some(index) => this.resolve_ty_alias(aliases, index, resolved, visiting),
+                None => this.get_ty_alias(name, loc),
+            }
+        };
+        let ty = self.lower_ty_with(&alias.ty.content, alias.ty.loc, &mut expand_alias)?;
+        visiting.pop();
+
+        resolved[index] = Some(ty.clone());
+        Ok(ty)
+    }
+
+    fn push_local(&mut self, local: TyAnnotation<Located<Local<'ast>>>) -> LowerResult<LocalId> {
+        let ty = self.lower_ty(local.ty.content, local.ty.loc)?;
         let ty_loc = local.ty.loc;
         let loc = local.item.loc;
         let local = local.item.content;
@@ -84,13 +426,54 @@ impl<'ast, 'ctx> Scope<'ast, 'ctx> {
 
         self.locals.push((local, id));
 
-        id
+        Ok(id)
     }
 
     fn pop_local(&mut self) -> (Local<'ast>, LocalId) {
         self.locals.pop().expect("Stack of locals is empty")
     }
 
+    /// Returns the name in scope that is most similar to `name`, if any is similar enough.
+    ///
+    /// The candidates are the locals in the current scope, the methods of the classes in the
+    /// current scope and the primitive functions. If two candidates are equally similar, the most
+    /// recently bound local is preferred.
+    fn suggest_name(&self, name: &str) -> Option<String> {
+        // Allow roughly one typo every three characters.
+        let max_distance = (name.chars().count() / 3).max(1);
+
+        let locals = self
+            .locals
+            .iter()
+            .rev()
+            .filter_map(|(local, _)| match local {
+                Local::Name(name) => Some(name.to_string()),
+                Local::Wildcard | Local::Temp(_) => None,
+            });
+        let methods = self
+            .classes
+            .iter()
+            .rev()
+            .flat_map(|class| class.methods.iter().map(|(name, _)| name.to_string()));
+        let primitives = Primitive::ALL.iter().map(Primitive::to_string);
+
+        let mut best: Option<(usize, String)> = None;
+        for candidate in locals.chain(methods).chain(primitives) {
+            let distance = edit_distance(name, &candidate);
+            if distance > max_distance {
+                continue;
+            }
+            if let Some((best_distance, _)) = &best {
+                if *best_distance <= distance {
+                    continue;
+                }
+            }
+            best = Some((distance, candidate));
+        }
+
+        best.map(|(_, candidate)| candidate)
+    }
+
     pub fn lower_block(&mut self, mut block: Block<'ast>) -> LowerResult<Term> {
         if let Some(node) = block.nodes.pop_front() {
             match node {
@@ -121,9 +504,99 @@ impl<'ast, 'ctx> Scope<'ast, 'ctx> {
                     ))
                 }
                 Node::Stat(stat) => match stat.content {
-                    Statement::Assign(lhs, rhs) => self.lower_assign(stat.loc, lhs, rhs, block),
-                    Statement::FnDef(name, args, body) => {
-                        self.lower_fn_def(stat.loc, name, args, body, block)
+                    Statement::Assign(lhs, rhs, _) => {
+                        self.lower_let(stat.loc, lhs, rhs, |scope| scope.lower_block(block))
+                    }
+                    Statement::FnDef(name, args, body, _) => {
+                        let mut defs = vec![FnDef {
+                            loc: stat.loc,
+                            name,
+                            args,
+                            body,
+                        }];
+                        // Adjacent function definitions are lowered together so they can use each
+                        // other. A function whose name was already defined in this sequence
+                        // starts a new sequence because it shadows the previous one.
+                        while let Some(Node::Stat(Located {
+                            content: Statement::FnDef(name, ..),
+                            ..
+                        })) = block.nodes.front()
+                        {
+                            if defs.iter().any(|def| def.name.content == name.content) {
+                                break;
+                            }
+                            if let Some(Node::Stat(Located {
+                                loc,
+                                content: Statement::FnDef(name, args, body, _),
+                            })) = block.nodes.pop_front()
+                            {
+                                defs.push(FnDef {
+                                    loc,
+                                    name,
+                                    args,
+                                    body,
+                                });
+                            }
+                        }
+                        self.lower_fn_defs(defs, block)
+                    }
+                    Statement::TyAlias(name, ty) => {
+                        let mut aliases = vec![TyAlias { name, ty }];
+                        // Adjacent type aliases are declared together so they can use each other.
+                        // An alias whose name was already declared in this sequence starts a new
+                        // sequence because it shadows the previous one.
+                        while let Some(Node::Stat(Located {
+                            content: Statement::TyAlias(name, _),
+                            ..
+                        })) = block.nodes.front()
+                        {
+                            if aliases
+                                .iter()
+                                .any(|alias| alias.name.content == name.content)
+                            {
+                                break;
+                            }
+                            if let Some(Node::Stat(Located {
+                                content: Statement::TyAlias(name, ty),
+                                ..
+                            })) = block.nodes.pop_front()
+                            {
+                                aliases.push(TyAlias { name, ty });
+                            }
+                        }
+                        self.lower_ty_aliases(aliases, block)
+                    }
+                    Statement::Class(name, methods) => self.lower_class(name, methods, block),
+                    Statement::Instance(class, ty, methods) => {
+                        self.lower_instance(stat.loc, class, ty, methods, block)
+                    }
+                    // The operator is bound like any other local, its name cannot clash with an
+                    // identifier.
+                    Statement::Infix(_, op, rhs) => {
+                        let lhs = TyAnnotation {
+                            item: op.map(Local::Name),
+                            ty: op.loc.with_content(AstTy::Missing),
+                        };
+                        self.lower_let(stat.loc, lhs, rhs, |scope| scope.lower_block(block))
+                    }
+                    // Tests are only run by the test runner. Otherwise their bodies are wrapped in
+                    // a function taking `unit` that is never called, so they are still
+                    // type-checked.
+                    Statement::Test(name, body) => {
+                        let wildcard = |ty| TyAnnotation {
+                            item: name.loc.with_content(Local::Wildcard),
+                            ty: name.loc.with_content(ty),
+                        };
+                        let body = TyAnnotation {
+                            item: body,
+                            ty: name.loc.with_content(AstTy::Missing),
+                        };
+                        let rhs = stat
+                            .loc
+                            .with_content(Expression::AnonFn(vec![wildcard(AstTy::Unit)], body));
+                        self.lower_let(stat.loc, wildcard(AstTy::Missing), rhs, |scope| {
+                            scope.lower_block(block)
+                        })
                     }
                 },
             }
@@ -143,8 +616,16 @@ impl<'ast, 'ctx> Scope<'ast, 'ctx> {
                         return Ok(Term::new(term_id, TermKind::Var(local_id)));
                     }
                 }
+                if let Some((class, method)) = self.find_method(local) {
+                    return Ok(self.lower_method(loc, class, method));
+                }
+                if let Some(prim) = find_primitive(local) {
+                    return Ok(self.lower_prim_fn(loc, prim));
+                }
+                let name = local.to_string();
+                let suggestion = self.suggest_name(&name);
                 Err(LowerError::new(
-                    LowerErrorKind::Unbounded(local.to_string()),
+                    LowerErrorKind::Unbounded { name, suggestion },
                     loc,
                 ))
             }
@@ -153,21 +634,73 @@ impl<'ast, 'ctx> Scope<'ast, 'ctx> {
                 self.ctx.insert_location(term_id, loc);
                 Ok(Term::new(term_id, TermKind::Lit(lit)))
             }
-            Expression::PrimFn(prim) => {
-                let term_id: TermId = self.ctx.new_id();
-                self.ctx.insert_location(term_id, loc);
-                Ok(Term::new(term_id, TermKind::PrimFn(prim)))
-            }
+            Expression::PrimFn(prim) => Ok(self.lower_prim_fn(loc, prim)),
             Expression::Cond(if_branch, branches, else_block) => {
                 self.lower_cond(loc, if_branch, branches, else_block)
             }
             Expression::Call(func, args) => self.lower_call(loc, *func, args),
+            Expression::Let(lhs, rhs, body) => {
+                self.lower_let(loc, lhs, *rhs, |scope| scope.lower_expression(*body))
+            }
+            Expression::Pipe(arg, func) => self.lower_pipe(loc, *arg, *func),
             Expression::BinaryOp(bin_op, expr1, expr2) => {
                 self.lower_binary_op(loc, bin_op, *expr1, *expr2)
             }
+            Expression::Infix(op, expr1, expr2) => {
+                let func = op.map(|name| Expression::Local(Local::Name(name)));
+                self.lower_call(loc, func, vec![*expr1, *expr2])
+            }
+            Expression::Section(op, lhs, rhs) => self.lower_section(loc, op, lhs, rhs),
             Expression::UnaryOp(un_op, expr) => self.lower_unary_op(loc, un_op, *expr),
             Expression::AnonFn(args, body) => self.lower_anon_fn(loc, args, body),
+            Expression::Ascription(expr, ty) => {
+                let term = self.lower_expression(*expr)?;
+                let ty_loc = ty.loc;
+                let ty = self.lower_ty(ty.content, ty_loc)?;
+                Ok(self.annotate(term, TypeInfo { ty, loc: ty_loc }))
+            }
+            Expression::Hole => {
+                let term_id: TermId = self.ctx.new_id();
+                self.ctx.insert_location(term_id, loc);
+                let locals = self.locals_in_scope();
+                self.ctx.save_hole(term_id, locals);
+                Ok(Term::new(term_id, TermKind::Hole))
+            }
+        }
+    }
+
+    fn lower_prim_fn(&mut self, loc: Location, prim: Primitive) -> Term {
+        let term_id: TermId = self.ctx.new_id();
+        self.ctx.insert_location(term_id, loc);
+        match prim {
+            Primitive::Print => {
+                self.use_method(term_id, SHOW, 0);
+            }
+            // The arguments of `assert_eq` are compared using the `eq` method.
+            Primitive::AssertEq => {
+                self.use_method(term_id, EQ, 0);
+            }
+            _ => (),
+        }
+        Term::new(term_id, TermKind::PrimFn(prim))
+    }
+
+    /// Returns the named locals that can be used in the current scope, from innermost to
+    /// outermost.
+    ///
+    /// Shadowed locals are not included.
+    fn locals_in_scope(&self) -> Vec<LocalId> {
+        let mut names = Vec::new();
+        let mut ids = Vec::new();
+        for &(local, id) in self.locals.iter().rev() {
+            if let Local::Name(name) = local {
+                if !names.contains(&name) {
+                    names.push(name);
+                    ids.push(id);
+                }
+            }
         }
+        ids
     }
 
     fn lower_cond(
@@ -218,7 +751,17 @@ impl<'ast, 'ctx> Scope<'ast, 'ctx> {
         let term_id: TermId = self.ctx.new_id();
         self.ctx.insert_location(term_id, loc);
 
-        let mut term = self.lower_expression(func)?;
+        let term = self.lower_expression(func)?;
+        self.lower_args(loc, term, args)
+    }
+
+    /// Lowers the application of `term` to each one of `args`.
+    fn lower_args(
+        &mut self,
+        loc: Location,
+        mut term: Term,
+        args: Vec<Located<Expression<'ast>>>,
+    ) -> LowerResult<Term> {
         for arg in args {
             let term_id = self.ctx.new_id();
             self.ctx.insert_location(term_id, loc);
@@ -231,6 +774,33 @@ impl<'ast, 'ctx> Scope<'ast, 'ctx> {
         Ok(term)
     }
 
+    /// Lowers a pipe `arg |> func` as a call to `func` with `arg` as its first argument.
+    ///
+    /// If `func` is already a call, `arg` is prepended to its arguments. The application of `arg`
+    /// is stored as a pipe so the type checker can tell where it came from.
+    fn lower_pipe(
+        &mut self,
+        loc: Location,
+        arg: Located<Expression<'ast>>,
+        func: Located<Expression<'ast>>,
+    ) -> LowerResult<Term> {
+        let (func, args) = match func.content {
+            Expression::Call(func, args) => (*func, args),
+            _ => (func, Vec::new()),
+        };
+
+        let term = self.lower_expression(func)?;
+        let term_id = self.ctx.new_id();
+        self.ctx.insert_location(term_id, loc);
+        self.ctx.save_pipe(term_id);
+        let term = Term::new(
+            term_id,
+            TermKind::App(Box::new(term), Box::new(self.lower_expression(arg)?)),
+        );
+
+        self.lower_args(loc, term, args)
+    }
+
     fn lower_binary_op(
         &mut self,
         loc: Location,
@@ -241,6 +811,10 @@ impl<'ast, 'ctx> Scope<'ast, 'ctx> {
         let term_id: TermId = self.ctx.new_id();
         self.ctx.insert_location(term_id, loc);
 
+        if let Some((class, method)) = builtin_method(bin_op) {
+            self.use_method(term_id, class, method);
+        }
+
         Ok(Term::new(
             term_id,
             TermKind::BinaryOp(
@@ -251,6 +825,76 @@ impl<'ast, 'ctx> Scope<'ast, 'ctx> {
         ))
     }
 
+    /// Lowers an operator section as a function taking the missing operands.
+    ///
+    /// `(+)` stands for `fn(x, y) do x + y end`, `(2 *)` for `fn(y) do 2 * y end` and `(< 0)` for
+    /// `fn(x) do x < 0 end`. Operators declared by the user are called instead.
+    fn lower_section(
+        &mut self,
+        loc: Location,
+        op: Located<InfixOp<'ast>>,
+        lhs: Option<Box<Located<Expression<'ast>>>>,
+        rhs: Option<Box<Located<Expression<'ast>>>>,
+    ) -> LowerResult<Term> {
+        let mut params = Vec::new();
+        let mut operands = Vec::new();
+        for operand in [lhs, rhs] {
+            let term = match operand {
+                Some(expr) => self.lower_expression(*expr)?,
+                None => {
+                    let param = self.new_param(loc);
+                    params.push(param);
+
+                    let term_id: TermId = self.ctx.new_id();
+                    self.ctx.insert_location(term_id, loc);
+                    Term::new(term_id, TermKind::Var(param))
+                }
+            };
+            operands.push(term);
+        }
+        let term2 = Box::new(operands.pop().unwrap());
+        let term1 = Box::new(operands.pop().unwrap());
+
+        let term_id: TermId = self.ctx.new_id();
+        self.ctx.insert_location(term_id, loc);
+
+        let mut term = match op.content {
+            InfixOp::BinOp(bin_op) => {
+                if let Some((class, method)) = builtin_method(bin_op) {
+                    self.use_method(term_id, class, method);
+                }
+                Term::new(term_id, TermKind::BinaryOp(bin_op, term1, term2))
+            }
+            InfixOp::Custom(name) => {
+                let func =
+                    self.lower_expression(op.map(|_| Expression::Local(Local::Name(name))))?;
+                let app_id: TermId = self.ctx.new_id();
+                self.ctx.insert_location(app_id, loc);
+                let app = Term::new(app_id, TermKind::App(Box::new(func), term1));
+                Term::new(term_id, TermKind::App(Box::new(app), term2))
+            }
+        };
+
+        for param in params.into_iter().rev() {
+            let term_id: TermId = self.ctx.new_id();
+            self.ctx.insert_location(term_id, loc);
+            term = Term::new(term_id, TermKind::Abs(param, Box::new(term)));
+        }
+
+        Ok(term)
+    }
+
+    /// Creates an unnamed parameter with an unknown type for a function built by the compiler.
+    fn new_param(&mut self, loc: Location) -> LocalId {
+        let local_id: LocalId = self.ctx.new_id();
+        let local = self.ctx.new_local();
+        let ty = self.ctx.new_ty();
+        self.ctx.save_local(local_id, local);
+        self.ctx.insert_location(local_id, loc);
+        self.ctx.insert_type_info(local_id, TypeInfo { ty, loc });
+        local_id
+    }
+
     fn lower_unary_op(
         &mut self,
         loc: Location,
@@ -266,21 +910,25 @@ impl<'ast, 'ctx> Scope<'ast, 'ctx> {
         ))
     }
 
-    fn lower_assign(
+    /// Lowers a non-recursive binding of `lhs` to `rhs` that is visible inside `tail`.
+    ///
+    /// This is used for assignments, where `tail` is the rest of the block, and for `let`
+    /// expressions, where `tail` is the body of the expression.
+    fn lower_let(
         &mut self,
         loc: Location,
         lhs: TyAnnotation<Located<Local<'ast>>>,
         rhs: Located<Expression<'ast>>,
-        tail: Block<'ast>,
+        tail: impl FnOnce(&mut Self) -> LowerResult<Term>,
     ) -> LowerResult<Term> {
         let term_id: TermId = self.ctx.new_id();
         self.ctx.insert_location(term_id, loc);
 
         let rhs = self.lower_expression(rhs)?;
 
-        let lhs_id = self.push_local(lhs);
+        let lhs_id = self.push_local(lhs)?;
 
-        let tail = self.lower_block(tail)?;
+        let tail = tail(self)?;
 
         self.pop_local();
 
@@ -290,82 +938,299 @@ impl<'ast, 'ctx> Scope<'ast, 'ctx> {
         ))
     }
 
-    fn lower_fn_def(
+    /// Lowers a sequence of adjacent type alias declarations followed by `tail`.
+    ///
+    /// Type aliases only exist during lowering, so the resulting term is just the lowered `tail`.
+    fn lower_ty_aliases(
         &mut self,
-        loc: Location,
-        name: Located<Local<'ast>>,
-        args: Vec<TyAnnotation<Located<Local<'ast>>>>,
-        body: TyAnnotation<Block<'ast>>,
+        aliases: Vec<TyAlias<'ast>>,
         tail: Block<'ast>,
     ) -> LowerResult<Term> {
+        let tys = self.resolve_ty_aliases(&aliases)?;
+
+        let len = aliases.len();
+        for (alias, ty) in aliases.into_iter().zip(tys) {
+            self.ty_aliases.push((alias.name.content, ty));
+        }
+
+        let tail = self.lower_block(tail);
+
+        self.ty_aliases.truncate(self.ty_aliases.len() - len);
+
+        tail
+    }
+
+    /// Annotates `term` with the type information `info`.
+    ///
+    /// If `term` already has type information, it is bound to a new local annotated with `info`
+    /// and the result is the new local. This way both annotations are enforced.
+    fn annotate(&mut self, term: Term, info: TypeInfo) -> Term {
+        if self.ctx.get_type_info(term.id).is_none() {
+            self.ctx.insert_type_info(term.id, info);
+            return term;
+        }
+
+        let loc = self.ctx.get_location(term.id).unwrap();
+
+        let local_id: LocalId = self.ctx.new_id();
+        let local = self.ctx.new_local();
+        self.ctx.save_local(local_id, local);
+        self.ctx.insert_location(local_id, loc);
+        self.ctx.insert_type_info(local_id, info);
+
+        let var_id: TermId = self.ctx.new_id();
+        self.ctx.insert_location(var_id, loc);
+
         let term_id: TermId = self.ctx.new_id();
         self.ctx.insert_location(term_id, loc);
 
-        let is_recursive = is_fn_def_recursive(name.content, &body.item);
+        Term::new(
+            term_id,
+            TermKind::Let(
+                BindKind::NonRec,
+                local_id,
+                Box::new(term),
+                Box::new(Term::new(var_id, TermKind::Var(local_id))),
+            ),
+        )
+    }
 
-        let arity = args.len();
+    /// Lowers a use of the `method`-th method of the `class`-th class.
+    ///
+    /// The type of the method is its signature with a new type variable standing for `Self`.
+    fn lower_method(&mut self, loc: Location, class: usize, method: usize) -> Term {
+        let term_id: TermId = self.ctx.new_id();
+        self.ctx.insert_location(term_id, loc);
 
-        let body_ty = body.ty.content;
+        let self_ty = self.use_method(term_id, class, method);
+        let class = &self.classes[class];
+        let (name, ty) = &class.methods[method];
+        let name = name.to_string();
+        let ty = ty.substitute(self.self_var, &self_ty);
+        self.ctx.insert_type_info(term_id, TypeInfo { ty, loc });
 
-        // we need to decide if the function is recursive or not
-        let kind = if is_recursive {
-            // if the function is recursive, we need the return type.
-            // FIXME this restriction is artificial now.
-            require_ty(&body_ty, loc)?;
+        Term::new(term_id, TermKind::Method(name))
+    }
 
-            let loc = name.loc;
-            let local = name.content;
-            let id: LocalId = self.ctx.new_id();
-            self.ctx.save_local(id, local);
-            self.ctx.insert_location(id, loc);
+    /// Lowers a type class declaration followed by `tail`.
+    ///
+    /// Classes only exist during lowering, so the resulting term is just the lowered `tail`.
+    fn lower_class(
+        &mut self,
+        name: Located<&'ast str>,
+        sigs: Vec<MethodSig<'ast>>,
+        tail: Block<'ast>,
+    ) -> LowerResult<Term> {
+        self.ty_aliases.push(("Self", Ty::Var(self.self_var)));
+        let mut methods = Vec::with_capacity(sigs.len());
+        for sig in sigs {
+            let mut ty = self.lower_ty_inner(&sig.ty.content, sig.ty.loc)?;
+            for arg in sig.args.into_iter().rev() {
+                let arg_ty = self.lower_ty_inner(&arg.ty.content, arg.ty.loc)?;
+                ty = Ty::Arrow(Box::new(arg_ty), Box::new(ty));
+            }
+            methods.push((sig.name.content, ty));
+        }
+        self.ty_aliases.pop();
 
-            self.locals.push((local, id));
+        self.classes.push(Class {
+            name: name.content,
+            methods,
+        });
 
-            BindKind::Rec
-        } else {
-            BindKind::NonRec
-        };
+        let tail = self.lower_block(tail);
 
-        for arg in args {
-            self.push_local(arg);
+        self.classes.pop();
+
+        tail
+    }
+
+    /// Lowers an instance of a type class followed by `tail`.
+    ///
+    /// The instance is lowered to a dictionary holding its methods, bound to a new local. The
+    /// names of the methods are not pushed into the scope, each method is only selected from the
+    /// dictionary by the uses of the method where the instance is chosen. The instance is in scope
+    /// for its own methods so they can use each other.
+    fn lower_instance(
+        &mut self,
+        loc: Location,
+        class: Located<&'ast str>,
+        ty: Located<AstTy>,
+        methods: Vec<Located<Method<'ast>>>,
+        tail: Block<'ast>,
+    ) -> LowerResult<Term> {
+        let class_index = self
+            .classes
+            .iter()
+            .rposition(|class2| class2.name == class.content)
+            .ok_or_else(|| {
+                LowerError::new(
+                    LowerErrorKind::UnknownClass(class.content.to_owned()),
+                    class.loc,
+                )
+            })?;
+        let sigs = self.classes[class_index].methods.clone();
+
+        let ty = self.lower_ty(ty.content, ty.loc)?;
+
+        // Sort the methods in the same order as the signatures of the class.
+        let mut defs = sigs.iter().map(|_| None).collect::<Vec<_>>();
+        for method in methods {
+            match sigs
+                .iter()
+                .position(|(name, _)| *name == method.content.name.content)
+            {
+                Some(index) if defs[index].is_none() => defs[index] = Some(method),
+                _ => {
+                    return Err(LowerError::new(
+                        LowerErrorKind::UnexpectedMethod(method.content.name.content.to_string()),
+                        method.content.name.loc,
+                    )
+                    .with_label(
+                        class.loc,
+                        format!("instance of the class `{}`", class.content),
+                    ))
+                }
+            }
         }
 
-        let mut term = self.lower_block(body.item)?;
+        let mut methods = Vec::with_capacity(defs.len());
+        for (def, (name, sig_ty)) in defs.into_iter().zip(sigs) {
+            let method = def.ok_or_else(|| {
+                LowerError::new(LowerErrorKind::MissingMethod(name.to_string()), class.loc)
+                    .with_note(format!(
+                        "the class `{}` declares the method `{}`",
+                        class.content, name
+                    ))
+            })?;
+
+            let name = method.content.name;
+            let local_id: LocalId = self.ctx.new_id();
+            self.ctx.save_local(local_id, name.content);
+            self.ctx.insert_location(local_id, name.loc);
+            self.ctx.insert_type_info(
+                local_id,
+                TypeInfo {
+                    ty: sig_ty.substitute(self.self_var, &ty),
+                    loc: name.loc,
+                },
+            );
+            methods.push((local_id, method));
+        }
 
-        let mut term_ty = self.lower_ty(body_ty);
-        self.ctx.insert_type_info(
-            term.id,
-            TypeInfo {
-                ty: term_ty.clone(),
-                loc: body.ty.loc,
-            },
-        );
+        let dict_id: LocalId = self.ctx.new_id();
+        let dict = self.ctx.new_local();
+        self.ctx.save_local(dict_id, dict);
+        self.ctx.insert_location(dict_id, loc);
+
+        self.instances.push(Instance {
+            class: class_index,
+            ty: Scheme::new(ty),
+            dict: Dict::Local(dict_id),
+        });
+
+        let mut binds = Vec::with_capacity(methods.len());
+        for (local_id, method) in methods {
+            let Located {
+                loc: method_loc,
+                content: method,
+            } = method;
+            let (term, _) = self.lower_fn(method_loc, method.args, method.body)?;
+            binds.push((local_id, term));
+        }
 
-        for _ in 0..arity {
-            let (_, arg_id) = self.pop_local();
+        let tail = self.lower_block(tail)?;
 
-            let mut term_info = self.ctx.get_type_info(arg_id).unwrap().clone();
-            term_ty = Ty::Arrow(Box::new(term_info.ty), Box::new(term_ty));
-            term_info.ty = term_ty.clone();
+        self.instances.pop();
 
-            let term_id: TermId = self.ctx.new_id();
-            self.ctx.insert_location(term_id, loc);
-            self.ctx.insert_type_info(term_id, term_info);
+        let term_id: TermId = self.ctx.new_id();
+        self.ctx.insert_location(term_id, loc);
 
-            term = Term::new(term_id, TermKind::Abs(arg_id, Box::new(term)));
+        Ok(Term::new(
+            term_id,
+            TermKind::Instance(dict_id, binds, Box::new(tail)),
+        ))
+    }
+
+    /// Lowers a sequence of adjacent function definitions followed by `tail`.
+    ///
+    /// The functions are lowered in the order given by `fn_def_groups`. Each group of mutually
+    /// recursive functions is lowered as a single `LetGroup` and every other function is lowered
+    /// as a regular `Let`. A function can only use the functions defined after it whose names are
+    /// not bound in the current scope.
+    fn lower_fn_defs(
+        &mut self,
+        mut defs: Vec<FnDef<'ast>>,
+        tail: Block<'ast>,
+    ) -> LowerResult<Term> {
+        if defs.is_empty() {
+            return self.lower_block(tail);
         }
 
-        if !is_recursive {
-            let loc = name.loc;
-            let local = name.content;
-            let id: LocalId = self.ctx.new_id();
-            self.ctx.save_local(id, local);
-            self.ctx.insert_location(id, loc);
+        let group = {
+            let bodies = defs
+                .iter()
+                .map(|def| (def.name.content, &def.body.item))
+                .collect::<Vec<_>>();
+            let locals = &self.locals;
+            fn_def_groups(&bodies, |name| {
+                locals.iter().any(|(name2, _)| *name2 == name)
+            })
+            .swap_remove(0)
+        };
 
-            self.locals.push((local, id));
+        // Remove the functions of the first group from `defs`. `group` is sorted so the indices
+        // are removed from last to first to keep them valid.
+        let mut group_defs = group
+            .into_iter()
+            .rev()
+            .map(|index| defs.remove(index))
+            .collect::<Vec<_>>();
+        group_defs.reverse();
+
+        if group_defs.len() == 1 {
+            let def = group_defs.pop().unwrap();
+            self.lower_fn_def(def, |scope| scope.lower_fn_defs(defs, tail))
+        } else {
+            self.lower_fn_group(group_defs, |scope| scope.lower_fn_defs(defs, tail))
         }
+    }
 
-        let tail = self.lower_block(tail)?;
+    fn lower_fn_def(
+        &mut self,
+        def: FnDef<'ast>,
+        tail: impl FnOnce(&mut Self) -> LowerResult<Term>,
+    ) -> LowerResult<Term> {
+        let FnDef {
+            loc,
+            name,
+            args,
+            body,
+        } = def;
+
+        let term_id: TermId = self.ctx.new_id();
+        self.ctx.insert_location(term_id, loc);
+
+        let is_recursive = is_fn_def_recursive(name.content, &body.item);
+
+        let ty_loc = body.ty.loc;
+
+        // we need to decide if the function is recursive or not. If the return type is missing, it
+        // is inferred using a new type variable, even if the function is recursive.
+        let kind = if is_recursive {
+            self.push_fn_name(name);
+            BindKind::Rec
+        } else {
+            BindKind::NonRec
+        };
+
+        let (term, term_ty) = self.lower_fn(loc, args, body)?;
+
+        if !is_recursive {
+            self.push_fn_name(name);
+        }
+
+        let tail = tail(self)?;
 
         let (_, local_id) = self.pop_local();
 
@@ -373,42 +1238,87 @@ impl<'ast, 'ctx> Scope<'ast, 'ctx> {
             local_id,
             TypeInfo {
                 ty: term_ty,
-                loc: body.ty.loc,
+                loc: ty_loc,
             },
         );
 
-        term = Term::new(
+        Ok(Term::new(
             term_id,
             TermKind::Let(kind, local_id, Box::new(term), Box::new(tail)),
-        );
+        ))
+    }
 
-        Ok(term)
+    /// Lowers a group of mutually recursive functions.
+    ///
+    /// The names of all the functions are pushed before lowering any of them so each function can
+    /// use the others.
+    fn lower_fn_group(
+        &mut self,
+        defs: Vec<FnDef<'ast>>,
+        tail: impl FnOnce(&mut Self) -> LowerResult<Term>,
+    ) -> LowerResult<Term> {
+        let loc = defs[0].loc + defs[defs.len() - 1].loc;
+        let term_id: TermId = self.ctx.new_id();
+        self.ctx.insert_location(term_id, loc);
+
+        let mut ids = Vec::with_capacity(defs.len());
+        for def in &defs {
+            ids.push(self.push_fn_name(def.name));
+        }
+
+        let mut binds = Vec::with_capacity(defs.len());
+        for (def, local_id) in defs.into_iter().zip(ids) {
+            let ty_loc = def.body.ty.loc;
+            let (term, ty) = self.lower_fn(def.loc, def.args, def.body)?;
+            self.ctx
+                .insert_type_info(local_id, TypeInfo { ty, loc: ty_loc });
+            binds.push((local_id, term));
+        }
+
+        let tail = tail(self)?;
+
+        for _ in 0..binds.len() {
+            self.pop_local();
+        }
+
+        Ok(Term::new(
+            term_id,
+            TermKind::LetGroup(binds, Box::new(tail)),
+        ))
     }
 
-    fn lower_anon_fn(
+    /// Pushes the name of a function into the scope.
+    ///
+    /// The type information of the returned local must be inserted after lowering the function.
+    fn push_fn_name(&mut self, name: Located<Local<'ast>>) -> LocalId {
+        let id: LocalId = self.ctx.new_id();
+        self.ctx.save_local(id, name.content);
+        self.ctx.insert_location(id, name.loc);
+
+        self.locals.push((name.content, id));
+
+        id
+    }
+
+    /// Lowers the arguments and body of a function and returns the lowered function with its
+    /// type.
+    fn lower_fn(
         &mut self,
         loc: Location,
         args: Vec<TyAnnotation<Located<Local<'ast>>>>,
         body: TyAnnotation<Block<'ast>>,
-    ) -> LowerResult<Term> {
-        // FIXME this restriction is artificial now.
-        if let AstTy::Missing = body.ty.content {
-            ()
-        } else {
-            return Err(LowerError::new(LowerErrorKind::AnonWithTy, body.ty.loc));
-        };
-
+    ) -> LowerResult<(Term, Ty)> {
         let arity = args.len();
 
         for arg in args {
-            self.push_local(arg);
+            self.push_local(arg)?;
         }
 
-        let mut term = self.lower_block(body.item)?;
+        let term = self.lower_block(body.item)?;
 
-        let mut term_ty = self.ctx.new_ty();
-        self.ctx.insert_type_info(
-            term.id,
+        let mut term_ty = self.lower_ty(body.ty.content, body.ty.loc)?;
+        let mut term = self.annotate(
+            term,
             TypeInfo {
                 ty: term_ty.clone(),
                 loc: body.ty.loc,
@@ -429,6 +1339,47 @@ impl<'ast, 'ctx> Scope<'ast, 'ctx> {
             term = Term::new(term_id, TermKind::Abs(arg_id, Box::new(term)));
         }
 
+        Ok((term, term_ty))
+    }
+
+    fn lower_anon_fn(
+        &mut self,
+        loc: Location,
+        args: Vec<TyAnnotation<Located<Local<'ast>>>>,
+        body: TyAnnotation<Block<'ast>>,
+    ) -> LowerResult<Term> {
+        let (term, _) = self.lower_fn(loc, args, body)?;
         Ok(term)
     }
 }
+
+/// A function definition.
+struct FnDef<'ast> {
+    loc: Location,
+    name: Located<Local<'ast>>,
+    args: Vec<TyAnnotation<Located<Local<'ast>>>>,
+    body: TyAnnotation<Block<'ast>>,
+}
+
+/// A type class.
+struct Class<'ast> {
+    name: &'ast str,
+    /// Name and signature of each method.
+    methods: Vec<(Local<'ast>, Ty)>,
+}
+
+/// An instance of a type class for a type.
+struct Instance {
+    /// Index of the class in the stack of classes.
+    class: usize,
+    /// Type of the instance, which may quantify type variables.
+    ty: Scheme,
+    /// Dictionary holding the implementation of each method.
+    dict: Dict,
+}
+
+/// A type alias declaration.
+struct TyAlias<'ast> {
+    name: Located<&'ast str>,
+    ty: Located<AstTy>,
+}
@@ -1,6 +1,8 @@
 use std::fmt;
 
-use pijama_common::{BinOp, Literal, UnOp};
+use num_bigint::BigInt;
+
+use pijama_common::{location::Location, BinOp, IntTy, Literal, UnOp};
 use pijama_ctx::Context;
 
 use Term::*;
@@ -11,13 +13,37 @@ mod lower;
 pub enum Term {
     Var(usize),
     Lit(i64),
+    /// A literal of an integer type with a fixed width, stored as explained in `IntTy::value`.
+    FixedLit(IntTy, i64),
+    /// A literal of an integer type with arbitrary precision.
+    BigLit(BigInt),
+    /// A floating-point literal, stored using `f64::to_bits`.
+    FloatLit(u64),
+    /// A character literal, kept apart from integers so it can be printed as a character.
+    CharLit(char),
     Abs(Box<Term>),
     UnaryOp(UnOp, Box<Term>),
     BinaryOp(BinOp, Box<Term>, Box<Term>),
     App(Box<Term>, Box<Term>),
     Cond(Box<Term>, Box<Term>, Box<Term>),
     Fix(Box<Term>),
+    FixGroup(usize, Vec<Term>),
     PrimFn(PrimFn),
+    /// A value of a sum type, tagged `0` for `none` and `err` values and `1` for `some` and `ok`
+    /// values. The payload of `none` values is `unit`.
+    Variant(usize, Box<Term>),
+    /// An elimination of a sum type (case t1 of t2 | t3) applying the branch `t2` or `t3`, as
+    /// chosen by the tag of `t1`, to the payload of `t1`.
+    Case(Box<Term>, Box<Term>, Box<Term>),
+    /// A sequence (t1; t2) evaluating `t1` before `t2` and discarding the value of `t1`.
+    Seq(Box<Term>, Box<Term>),
+    /// A term annotated with the location of the source code it was lowered from. It evaluates
+    /// to the inner term and it is only added when lowering with `Term::from_hir_with_locations`.
+    Loc(Location, Box<Term>),
+    /// The body of the function defined at the given location, wrapped by the abstraction
+    /// binding the last argument of the function. It evaluates to the inner term and it is only
+    /// added when lowering with `Term::from_hir_with_locations`.
+    Body(Location, Box<Term>),
 }
 
 impl Term {
@@ -32,17 +58,46 @@ impl Term {
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum PrimFn {
     PrintInt,
+    PrintFloat,
+    PrintChar,
     PrintBool,
     PrintUnit,
     PrintFunc,
+    ToInt,
+    ToFixed(IntTy),
+    ToBigInt,
+    ToFloat,
+    Truncate,
+    Ord,
+    Chr,
+    /// Fails at the given location if its argument is `false`.
+    Assert(Location),
+    /// Fails at the given location if its two arguments are not equal. The first argument is
+    /// stored once it is applied. Both arguments are shown in the failure message in the same way
+    /// as the print primitive stored here would show them.
+    AssertEq(Location, Box<PrimFn>, Option<Box<Term>>),
 }
 
 impl fmt::Display for PrimFn {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            PrimFn::PrintInt | PrimFn::PrintBool | PrimFn::PrintUnit | PrimFn::PrintFunc => {
+            PrimFn::PrintInt
+            | PrimFn::PrintFloat
+            | PrimFn::PrintChar
+            | PrimFn::PrintBool
+            | PrimFn::PrintUnit
+            | PrimFn::PrintFunc => {
                 write!(f, "print")
             }
+            PrimFn::ToInt => write!(f, "to_int"),
+            PrimFn::ToFixed(ty) => write!(f, "to_{}", ty.suffix()),
+            PrimFn::ToBigInt => write!(f, "to_bigint"),
+            PrimFn::ToFloat => write!(f, "to_float"),
+            PrimFn::Truncate => write!(f, "truncate"),
+            PrimFn::Ord => write!(f, "ord"),
+            PrimFn::Chr => write!(f, "chr"),
+            PrimFn::Assert(_) => write!(f, "assert"),
+            PrimFn::AssertEq(..) => write!(f, "assert_eq"),
         }
     }
 }
@@ -53,6 +108,10 @@ impl From<Literal> for Term {
             Literal::Bool(b) => b.into(),
             Literal::Unit => ().into(),
             Literal::Number(n) => n.into(),
+            Literal::Fixed(bits, ty) => FixedLit(ty, bits),
+            Literal::Big(n) => BigLit(n),
+            Literal::Float(bits) => FloatLit(bits),
+            Literal::Char(c) => CharLit(c),
         }
     }
 }
@@ -84,21 +143,42 @@ impl fmt::Display for Term {
             BinaryOp(op, t1, t2) => write!(f, "({} {} {})", t1, op, t2),
             App(t1, t2) => write!(f, "({} {})", t1, t2),
             Lit(literal) => write!(f, "{}", literal),
+            FixedLit(ty, bits) => write!(f, "{}", ty.value(*bits)),
+            BigLit(n) => write!(f, "{}", n),
+            FloatLit(bits) => write!(f, "{:?}", f64::from_bits(*bits)),
+            CharLit(c) => write!(f, "{:?}", c),
             Cond(t1, t2, t3) => write!(f, "(if {} then {} else {})", t1, t2, t3),
             Fix(t1) => write!(f, "(fix {})", t1),
+            FixGroup(index, terms) => {
+                write!(f, "(fix{}", index)?;
+                for term in terms {
+                    write!(f, " {}", term)?;
+                }
+                write!(f, ")")
+            }
             PrimFn(prim) => write!(f, "{}", prim),
+            Variant(tag, term) => write!(f, "(variant{} {})", tag, term),
+            Case(t1, t2, t3) => write!(f, "(case {} of {} | {})", t1, t2, t3),
+            Seq(t1, t2) => write!(f, "({}; {})", t1, t2),
+            Loc(_, t1) | Body(_, t1) => write!(f, "{}", t1),
         }
     }
 }
 
 impl Term {
     pub fn from_hir<'ast>(ctx: &Context, hir: pijama_hir::Term) -> Self {
-        lower::remove_names(ctx, hir)
+        lower::remove_names(ctx, hir, false)
+    }
+
+    /// Lowers `hir` like `Term::from_hir` but annotating every term that can be reduced and the
+    /// body of every function with their locations.
+    pub fn from_hir_with_locations(ctx: &Context, hir: pijama_hir::Term) -> Self {
+        lower::remove_names(ctx, hir, true)
     }
 
     pub fn shift(&mut self, up: bool, cutoff: usize) {
         match self {
-            Lit(_) | PrimFn(_) => (),
+            Lit(_) | FixedLit(_, _) | BigLit(_) | FloatLit(_) | CharLit(_) | PrimFn(_) => (),
             Var(index) => {
                 if *index >= cutoff {
                     if up {
@@ -118,24 +198,29 @@ impl Term {
                 t1.shift(up, cutoff);
                 t2.shift(up, cutoff);
             }
-            App(t1, t2) => {
+            App(t1, t2) | Seq(t1, t2) => {
                 t1.shift(up, cutoff);
                 t2.shift(up, cutoff);
             }
-            Cond(t1, t2, t3) => {
+            Cond(t1, t2, t3) | Case(t1, t2, t3) => {
                 t1.shift(up, cutoff);
                 t2.shift(up, cutoff);
                 t3.shift(up, cutoff);
             }
-            Fix(t1) => {
+            Fix(t1) | Variant(_, t1) | Loc(_, t1) | Body(_, t1) => {
                 t1.shift(up, cutoff);
             }
+            FixGroup(_, terms) => {
+                for term in terms {
+                    term.shift(up, cutoff);
+                }
+            }
         }
     }
 
     pub fn replace(&mut self, index: usize, subs: &mut Term) {
         match self {
-            Lit(_) | PrimFn(_) => (),
+            Lit(_) | FixedLit(_, _) | BigLit(_) | FloatLit(_) | CharLit(_) | PrimFn(_) => (),
             Var(index2) => {
                 if index == *index2 {
                     *self = subs.clone();
@@ -153,18 +238,23 @@ impl Term {
                 t1.replace(index, subs);
                 t2.replace(index, subs);
             }
-            App(t1, t2) => {
+            App(t1, t2) | Seq(t1, t2) => {
                 t1.replace(index, subs);
                 t2.replace(index, subs);
             }
-            Cond(t1, t2, t3) => {
+            Cond(t1, t2, t3) | Case(t1, t2, t3) => {
                 t1.replace(index, subs);
                 t2.replace(index, subs);
                 t3.replace(index, subs);
             }
-            Fix(t1) => {
+            Fix(t1) | Variant(_, t1) | Loc(_, t1) | Body(_, t1) => {
                 t1.replace(index, subs);
             }
+            FixGroup(_, terms) => {
+                for term in terms {
+                    term.replace(index, subs);
+                }
+            }
         }
     }
 }
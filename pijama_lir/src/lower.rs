@@ -1,41 +1,213 @@
-use pijama_common::Primitive;
-use pijama_ctx::{Context, ContextExt, LocalId};
+use pijama_common::{Local, Primitive, SumFn};
+use pijama_ctx::{BuiltinMethod, Context, ContextExt, LocalId, Selection, TermId};
 use pijama_hir::{BindKind, Term as HirTerm, TermKind};
 use pijama_ty::Ty;
 
 use crate::{PrimFn, Term};
 
-pub fn remove_names(ctx: &Context, term: HirTerm) -> Term {
-    Scope::new(ctx).remove_names(term)
+pub fn remove_names(ctx: &Context, term: HirTerm, locations: bool) -> Term {
+    Scope::new(ctx, locations).remove_names(term)
 }
 
 struct Scope<'ast, 'ctx> {
     inner: Vec<LocalId>,
     ctx: &'ctx Context<'ast>,
+    /// Whether the terms that can be reduced are annotated with their locations.
+    locations: bool,
 }
 
 impl<'ast, 'ctx> Scope<'ast, 'ctx> {
-    fn new(ctx: &'ctx Context<'ast>) -> Self {
-        Self { inner: vec![], ctx }
+    fn new(ctx: &'ctx Context<'ast>, locations: bool) -> Self {
+        Self {
+            inner: vec![],
+            ctx,
+            locations,
+        }
+    }
+
+    fn var(&self, name: LocalId) -> Term {
+        let (index, _) = self
+            .inner
+            .iter()
+            .rev()
+            .enumerate()
+            .find(|(_, name2)| name == **name2)
+            .unwrap();
+        Term::Var(index)
+    }
+
+    /// Returns the primitive function printing the argument of the `print` function `id`.
+    fn print_fn(&self, id: TermId) -> PrimFn {
+        match self
+            .ctx
+            .get_type_info(id)
+            .unwrap()
+            .ty
+            .iter()
+            .next()
+            .unwrap()
+        {
+            Ty::Int | Ty::Fixed(_) | Ty::BigInt => PrimFn::PrintInt,
+            Ty::Float => PrimFn::PrintFloat,
+            Ty::Char => PrimFn::PrintChar,
+            Ty::Bool => PrimFn::PrintBool,
+            Ty::Unit => PrimFn::PrintUnit,
+            Ty::Arrow(_, _) => PrimFn::PrintFunc,
+            Ty::Option(_) | Ty::Result(_, _) | Ty::Var(_) => unreachable!(),
+        }
+    }
+
+    /// Returns the primitive function implementing the primitive `prim` used by the term `id`.
+    fn prim_fn(&self, id: TermId, prim: Primitive) -> PrimFn {
+        match prim {
+            Primitive::Print => self.print_fn(id),
+            Primitive::ToInt => PrimFn::ToInt,
+            Primitive::ToFixed(ty) => PrimFn::ToFixed(ty),
+            Primitive::ToBigInt => PrimFn::ToBigInt,
+            Primitive::ToFloat => PrimFn::ToFloat,
+            Primitive::Truncate => PrimFn::Truncate,
+            Primitive::Ord => PrimFn::Ord,
+            Primitive::Chr => PrimFn::Chr,
+            Primitive::Assert => PrimFn::Assert(self.ctx.get_location(id).unwrap()),
+            Primitive::Sum(_) => unreachable!("sum primitives are lowered by `sum_fn`"),
+            Primitive::AssertEq => unreachable!("`assert_eq` is lowered by `assert_eq`"),
+        }
+    }
+
+    /// Returns the term selecting the `index`-th of the `len` methods of the dictionary `dict`.
+    ///
+    /// A dictionary is a function applying its argument to each one of its methods, so the
+    /// selection is `dict (λ ... λ (len - 1 - index))` with `len` abstractions.
+    fn select(&self, dict: LocalId, index: usize, len: usize) -> Term {
+        let mut selector = Term::Var(len - 1 - index);
+        for _ in 0..len {
+            selector = Term::Abs(Box::new(selector));
+        }
+        Term::App(Box::new(self.var(dict)), Box::new(selector))
+    }
+
+    /// Returns the term implementing the method used by the term `id`.
+    ///
+    /// Methods implemented by operators are lowered as `λ λ (1 op 0)`.
+    fn method(&self, id: TermId) -> Term {
+        match self.ctx.get_selection(id).unwrap() {
+            Selection::Builtin(BuiltinMethod::Prim(prim)) => Term::PrimFn(self.prim_fn(id, prim)),
+            Selection::Builtin(BuiltinMethod::BinOp(op)) => {
+                Term::Abs(Box::new(Term::Abs(Box::new(Term::BinaryOp(
+                    op,
+                    Box::new(Term::Var(1)),
+                    Box::new(Term::Var(0)),
+                )))))
+            }
+            Selection::Dict { dict, index, len } => self.select(dict, index, len),
+        }
+    }
+
+    /// Returns the term implementing the `assert_eq` function used by the term `id`.
+    ///
+    /// If the `eq` method comparing the arguments is implemented by the user, this is
+    /// `λ λ assert ((eq 1) 0)`. Otherwise the arguments are compared by the `AssertEq` primitive,
+    /// which shows them if they are not equal.
+    fn assert_eq(&self, id: TermId) -> Term {
+        let loc = self.ctx.get_location(id).unwrap();
+        match self.ctx.get_selection(id) {
+            Some(Selection::Dict { dict, index, len }) => {
+                let abs = |term| Term::Abs(Box::new(term));
+                let app = |t1, t2| Term::App(Box::new(t1), Box::new(t2));
+                // The method is used inside two abstractions.
+                let mut eq = self.select(dict, index, len);
+                eq.shift(true, 0);
+                eq.shift(true, 0);
+                let cond = app(app(eq, Term::Var(1)), Term::Var(0));
+                abs(abs(app(Term::PrimFn(PrimFn::Assert(loc)), cond)))
+            }
+            _ => Term::PrimFn(PrimFn::AssertEq(loc, Box::new(self.print_fn(id)), None)),
+        }
+    }
+
+    /// Returns the term implementing a primitive over `Option` and `Result` values.
+    ///
+    /// These primitives are lowered to abstractions building variants or eliminating them with
+    /// `Case`.
+    fn sum_fn(sum_fn: SumFn) -> Term {
+        let abs = |term| Term::Abs(Box::new(term));
+        let variant = |tag, term| Term::Variant(tag, Box::new(term));
+        let case = |t1, t2, t3| Term::Case(Box::new(t1), Box::new(t2), Box::new(t3));
+        let app = |t1, t2| Term::App(Box::new(t1), Box::new(t2));
+
+        match sum_fn {
+            // `λ variant1 0`
+            SumFn::Some | SumFn::Ok => abs(variant(1, Term::Var(0))),
+            // `variant0 unit`
+            SumFn::None => variant(0, ().into()),
+            // `λ variant0 0`
+            SumFn::Err => abs(variant(0, Term::Var(0))),
+            // `λ case 0 of (λ false) | (λ true)`
+            SumFn::IsSome | SumFn::IsOk => {
+                abs(case(Term::Var(0), abs(false.into()), abs(true.into())))
+            }
+            // `λ λ case 1 of (λ 1) | (λ 0)`
+            SumFn::UnwrapOr => abs(abs(case(
+                Term::Var(1),
+                abs(Term::Var(1)),
+                abs(Term::Var(0)),
+            ))),
+            // `λ λ case 1 of (λ variant0 0) | (λ variant1 (1 0))`
+            SumFn::Map => abs(abs(case(
+                Term::Var(1),
+                abs(variant(0, Term::Var(0))),
+                abs(variant(1, app(Term::Var(1), Term::Var(0)))),
+            ))),
+            // `λ λ case 1 of (λ variant0 0) | (λ 1 0)`
+            SumFn::AndThen => abs(abs(case(
+                Term::Var(1),
+                abs(variant(0, Term::Var(0))),
+                abs(app(Term::Var(1), Term::Var(0))),
+            ))),
+        }
     }
 
     fn remove_names(&mut self, term: HirTerm) -> Term {
+        let loc = match term.kind {
+            TermKind::UnaryOp(..)
+            | TermKind::BinaryOp(..)
+            | TermKind::App(..)
+            | TermKind::Let(..)
+            | TermKind::LetGroup(..)
+            | TermKind::Instance(..)
+            | TermKind::Cond(..)
+                if self.locations =>
+            {
+                self.ctx.get_location(term.id)
+            }
+            _ => None,
+        };
+
+        let lowered = self.remove_names_inner(term);
+        match loc {
+            Some(loc) => Term::Loc(loc, Box::new(lowered)),
+            None => lowered,
+        }
+    }
+
+    fn remove_names_inner(&mut self, term: HirTerm) -> Term {
         match term.kind {
             TermKind::Lit(lit) => lit.into(),
-            TermKind::Var(name) => {
-                let (index, _) = self
-                    .inner
-                    .iter()
-                    .rev()
-                    .enumerate()
-                    .find(|(_, name2)| name == **name2)
-                    .unwrap();
-                Term::Var(index)
-            }
+            TermKind::Var(name) => self.var(name),
             TermKind::Abs(name, body) => {
+                // The abstractions binding the arguments of a function share its location, so the
+                // body is the first term with a different location.
+                let loc = self.ctx.get_location(term.id);
+                let is_body = self.locations
+                    && !matches!(body.kind, TermKind::Abs(..) if self.ctx.get_location(body.id) == loc);
+
                 self.inner.push(name);
-                let body = self.remove_names(*body);
+                let mut body = self.remove_names(*body);
                 self.inner.pop().unwrap();
+
+                if let (true, Some(loc)) = (is_body, loc) {
+                    body = Term::Body(loc, Box::new(body));
+                }
                 Term::Abs(Box::new(body))
             }
             TermKind::UnaryOp(op, t1) => {
@@ -45,13 +217,30 @@ impl<'ast, 'ctx> Scope<'ast, 'ctx> {
             TermKind::BinaryOp(op, t1, t2) => {
                 let t1 = self.remove_names(*t1);
                 let t2 = self.remove_names(*t2);
-                Term::BinaryOp(op, Box::new(t1), Box::new(t2))
+                // Comparisons implemented by a user-defined instance are calls to its method.
+                let selection = self.ctx.get_selection(term.id);
+                if let Some(Selection::Dict { dict, index, len }) = selection {
+                    let func = Term::App(Box::new(self.select(dict, index, len)), Box::new(t1));
+                    Term::App(Box::new(func), Box::new(t2))
+                } else {
+                    Term::BinaryOp(op, Box::new(t1), Box::new(t2))
+                }
             }
             TermKind::App(t1, t2) => {
                 let t1 = self.remove_names(*t1);
                 let t2 = self.remove_names(*t2);
                 Term::App(Box::new(t1), Box::new(t2))
             }
+            // Expression statements are bound to a wildcard. They are only evaluated because of
+            // their side effects, so they are evaluated before the rest of the block even if their
+            // value is not used.
+            TermKind::Let(BindKind::NonRec, name, t1, t2)
+                if matches!(self.ctx.get_local(name), Some(Local::Wildcard)) =>
+            {
+                let t1 = self.remove_names(*t1);
+                let t2 = self.remove_names(*t2);
+                Term::Seq(Box::new(t1), Box::new(t2))
+            }
             TermKind::Let(kind, name, t1, t2) => {
                 let t1 = if let BindKind::Rec = kind {
                     // if the let binding is recursive we are dealing with a recursive function and
@@ -76,32 +265,74 @@ impl<'ast, 'ctx> Scope<'ast, 'ctx> {
                 self.inner.pop().unwrap();
                 Term::App(Box::new(Term::Abs(Box::new(t2))), Box::new(t1))
             }
+            TermKind::LetGroup(binds, t2) => {
+                // A group of mutually recursive functions is lowered using the fixed-point of the
+                // whole group. Each function is wrapped in one abstraction for each function of
+                // the group, the `i`-th of them binding the `i`-th function. Then `(fixi t1 ...
+                // tn)` stands for the `i`-th function of the group.
+                let (names, terms): (Vec<_>, Vec<_>) = binds.into_iter().unzip();
+                let len = names.len();
+
+                self.inner.extend(names.iter().copied());
+                let terms = terms
+                    .into_iter()
+                    .map(|term| {
+                        let mut term = self.remove_names(term);
+                        for _ in 0..len {
+                            term = Term::Abs(Box::new(term));
+                        }
+                        term
+                    })
+                    .collect::<Vec<_>>();
+                self.inner.truncate(self.inner.len() - len);
+
+                // The tail is lowered as `(λ ... λ t2) (fix0 t1 ... tn) ... (fixn t1 ... tn)`.
+                self.inner.extend(names);
+                let mut t2 = self.remove_names(*t2);
+                self.inner.truncate(self.inner.len() - len);
+
+                for _ in 0..len {
+                    t2 = Term::Abs(Box::new(t2));
+                }
+
+                for index in 0..len {
+                    t2 = Term::App(Box::new(t2), Box::new(Term::FixGroup(index, terms.clone())));
+                }
+                t2
+            }
+            TermKind::Instance(dict, binds, t2) => {
+                // The dictionary of an instance is lowered as `fix (λ λ 0 t1 ... tn)`, applying
+                // its argument to each method. The dictionary is in scope for the methods so they
+                // can select each other from it. The locals of the methods are not bound, the
+                // methods are only selected from the dictionary.
+                self.inner.push(dict);
+                let mut methods = Term::Var(0);
+                for (_, term) in binds {
+                    let mut term = self.remove_names(term);
+                    // The method is used inside the abstraction binding the argument.
+                    term.shift(true, 0);
+                    methods = Term::App(Box::new(methods), Box::new(term));
+                }
+                let t1 = Term::Fix(Box::new(Term::Abs(Box::new(Term::Abs(Box::new(methods))))));
+
+                let t2 = self.remove_names(*t2);
+                self.inner.pop().unwrap();
+                Term::App(Box::new(Term::Abs(Box::new(t2))), Box::new(t1))
+            }
             TermKind::Cond(t1, t2, t3) => {
                 let t1 = self.remove_names(*t1);
                 let t2 = self.remove_names(*t2);
                 let t3 = self.remove_names(*t3);
                 Term::Cond(Box::new(t1), Box::new(t2), Box::new(t3))
             }
-            TermKind::PrimFn(prim) => {
-                let prim = match prim {
-                    Primitive::Print => match self
-                        .ctx
-                        .get_type_info(term.id)
-                        .unwrap()
-                        .ty
-                        .iter()
-                        .next()
-                        .unwrap()
-                    {
-                        Ty::Int => PrimFn::PrintInt,
-                        Ty::Bool => PrimFn::PrintBool,
-                        Ty::Unit => PrimFn::PrintUnit,
-                        Ty::Arrow(_, _) => PrimFn::PrintFunc,
-                        Ty::Var(_) => unreachable!(),
-                    },
-                };
-                Term::PrimFn(prim)
-            }
+            TermKind::PrimFn(Primitive::Sum(sum_fn)) => Self::sum_fn(sum_fn),
+            TermKind::PrimFn(Primitive::AssertEq) => self.assert_eq(term.id),
+            TermKind::PrimFn(prim) => match self.ctx.get_selection(term.id) {
+                Some(Selection::Dict { dict, index, len }) => self.select(dict, index, len),
+                _ => Term::PrimFn(self.prim_fn(term.id, prim)),
+            },
+            TermKind::Method(_) => self.method(term.id),
+            TermKind::Hole => unreachable!("holes are rejected during type-checking"),
         }
     }
 }
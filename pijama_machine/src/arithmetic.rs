@@ -1,11 +1,23 @@
 use std::convert::TryFrom;
 
-use pijama_common::{BinOp, BinOp::*, UnOp, UnOp::*};
+use num_bigint::BigInt;
+
+use pijama_common::{BinOp, BinOp::*, IntTy, UnOp, UnOp::*};
 
 /// Trait determining how arithmetic operations should be handled.
+///
+/// Integers of a type with a fixed width are stored as explained in `IntTy::value`.
 pub trait Arithmetic {
     fn binary_operation(op: BinOp, n1: i64, n2: i64) -> i64;
     fn unary_operation(op: UnOp, n: i64) -> i64;
+    fn fixed_binary_operation(op: BinOp, ty: IntTy, n1: i64, n2: i64) -> i64;
+    fn fixed_unary_operation(op: UnOp, ty: IntTy, n: i64) -> i64;
+    /// Converts the integer `n` of type `from` to the type `to`.
+    fn conversion(n: i64, from: IntTy, to: IntTy) -> i64;
+    /// Converts the integer `n` of arbitrary precision to the type `to`.
+    fn big_conversion(n: &BigInt, to: IntTy) -> i64;
+    /// Converts the floating-point number `x` to an `Int` by discarding its fractional part.
+    fn truncation(x: f64) -> i64;
 }
 
 /// Regular arithmetic that is allowed to overflow or panic when dividing by zero.
@@ -39,6 +51,29 @@ impl Arithmetic for OverflowArithmetic {
             Not => !n,
         }
     }
+
+    fn fixed_binary_operation(op: BinOp, ty: IntTy, n1: i64, n2: i64) -> i64 {
+        wrapping_binary_operation(op, ty, n1, n2).0
+    }
+
+    fn fixed_unary_operation(op: UnOp, ty: IntTy, n: i64) -> i64 {
+        wrapping_unary_operation(op, ty, n).0
+    }
+
+    fn conversion(n: i64, from: IntTy, to: IntTy) -> i64 {
+        to.wrap(from.value(n))
+    }
+
+    fn big_conversion(n: &BigInt, to: IntTy) -> i64 {
+        // Only the lowest 64 bits are kept by `IntTy::wrap`.
+        let bits = u64::try_from(n & BigInt::from(u64::MAX)).unwrap();
+        to.wrap(bits.into())
+    }
+
+    fn truncation(x: f64) -> i64 {
+        // Values out of range saturate and `NaN` becomes zero.
+        x as i64
+    }
 }
 
 /// Checked arithmetic that panics when overflowing or dividing by zero.
@@ -87,6 +122,206 @@ impl Arithmetic for CheckedArithmetic {
 
         result
     }
+
+    fn fixed_binary_operation(op: BinOp, ty: IntTy, n1: i64, n2: i64) -> i64 {
+        let (result, overflowed) = wrapping_binary_operation(op, ty, n1, n2);
+
+        if overflowed {
+            panic!(
+                "Binary operation `{}` overflowed with operands `{}` and `{}` of type `{}`",
+                op,
+                ty.value(n1),
+                ty.value(n2),
+                ty
+            )
+        }
+
+        result
+    }
+
+    fn fixed_unary_operation(op: UnOp, ty: IntTy, n: i64) -> i64 {
+        let (result, overflowed) = wrapping_unary_operation(op, ty, n);
+
+        if overflowed {
+            panic!(
+                "Unary operation `{}` overflowed with operand `{}` of type `{}`",
+                op,
+                ty.value(n),
+                ty
+            )
+        }
+
+        result
+    }
+
+    fn conversion(n: i64, from: IntTy, to: IntTy) -> i64 {
+        let value = from.value(n);
+
+        if !to.fits(value) {
+            panic!("Conversion of `{}` to type `{}` overflowed", value, to)
+        }
+
+        to.wrap(value)
+    }
+
+    fn big_conversion(n: &BigInt, to: IntTy) -> i64 {
+        match i128::try_from(n) {
+            Ok(value) if to.fits(value) => to.wrap(value),
+            _ => panic!("Conversion of `{}` to type `{}` overflowed", n, to),
+        }
+    }
+
+    fn truncation(x: f64) -> i64 {
+        // `-2^63` and `2^63` are represented exactly by an `f64`.
+        if x.is_nan() || x < i64::MIN as f64 || x >= -(i64::MIN as f64) {
+            panic!("Truncation of `{:?}` to type `Int` overflowed", x)
+        }
+
+        x as i64
+    }
+}
+
+/// Does a binary operation over integers of type `ty`, wrapping the result around the range of
+/// `ty`.
+///
+/// Returns the result and whether the operation overflowed. The result of a comparison is `0` or
+/// `1`. Shifting by an amount outside of `0..ty.bits()` overflows and shifts by the amount modulo
+/// `ty.bits()`.
+fn wrapping_binary_operation(op: BinOp, ty: IntTy, n1: i64, n2: i64) -> (i64, bool) {
+    let (v1, v2) = (ty.value(n1), ty.value(n2));
+    let value = match op {
+        Add => v1 + v2,
+        Sub => v1 - v2,
+        Mul => match v1.checked_mul(v2) {
+            Some(value) => value,
+            // The product of two unsigned 64 bit integers might not fit in an `i128`, the lowest
+            // bits of the wrapped product are still correct.
+            None => return (ty.wrap(v1.wrapping_mul(v2)), true),
+        },
+        Div => v1 / v2,
+        Rem => v1 % v2,
+        Lt => return ((v1 < v2).into(), false),
+        Lte => return ((v1 <= v2).into(), false),
+        Gt => return ((v1 > v2).into(), false),
+        Gte => return ((v1 >= v2).into(), false),
+        Eq => return ((v1 == v2).into(), false),
+        Neq => return ((v1 != v2).into(), false),
+        BitAnd | And => v1 & v2,
+        BitOr | Or => v1 | v2,
+        BitXor => v1 ^ v2,
+        Shr | Shl => {
+            let bits = i128::from(ty.bits());
+            let shift = v2.rem_euclid(bits) as u32;
+            let value = if let Shr = op {
+                v1 >> shift
+            } else {
+                v1 << shift
+            };
+            return (ty.wrap(value), !(0..bits).contains(&v2));
+        }
+    };
+
+    (ty.wrap(value), !ty.fits(value))
+}
+
+/// Does an unary operation over an integer of type `ty`, wrapping the result around the range of
+/// `ty`.
+///
+/// Returns the result and whether the operation overflowed.
+fn wrapping_unary_operation(op: UnOp, ty: IntTy, n: i64) -> (i64, bool) {
+    let value = match op {
+        Neg => -ty.value(n),
+        Not => !ty.value(n),
+    };
+
+    (ty.wrap(value), !ty.fits(value))
+}
+
+/// Compares two integers of arbitrary precision.
+///
+/// Returns `None` if `op` is not a comparison operator.
+pub(crate) fn big_comparison(op: BinOp, n1: &BigInt, n2: &BigInt) -> Option<bool> {
+    match op {
+        Lt => Some(n1 < n2),
+        Lte => Some(n1 <= n2),
+        Gt => Some(n1 > n2),
+        Gte => Some(n1 >= n2),
+        Eq => Some(n1 == n2),
+        Neq => Some(n1 != n2),
+        _ => None,
+    }
+}
+
+/// Does a binary operation that is not a comparison over integers of arbitrary precision.
+///
+/// These operations cannot overflow so they are the same for every `Arithmetic`.
+pub(crate) fn big_binary_operation(op: BinOp, n1: &BigInt, n2: &BigInt) -> BigInt {
+    match op {
+        Add => n1 + n2,
+        Sub => n1 - n2,
+        Mul => n1 * n2,
+        Div => n1 / n2,
+        Rem => n1 % n2,
+        BitAnd => n1 & n2,
+        BitOr => n1 | n2,
+        BitXor => n1 ^ n2,
+        Shr => n1 >> try_into_usize_or_panic(n2),
+        Shl => n1 << try_into_usize_or_panic(n2),
+        Lt | Lte | Gt | Gte | Eq | Neq => unreachable!("comparisons are done by `big_comparison`"),
+        And | Or => unreachable!("logic operators are not defined over integers"),
+    }
+}
+
+/// Does an unary operation over an integer of arbitrary precision.
+pub(crate) fn big_unary_operation(op: UnOp, n: &BigInt) -> BigInt {
+    match op {
+        Neg => -n,
+        Not => !n,
+    }
+}
+
+/// Compares two floating-point numbers following IEEE 754.
+///
+/// Returns `None` if `op` is not a comparison operator.
+pub(crate) fn float_comparison(op: BinOp, x: f64, y: f64) -> Option<bool> {
+    match op {
+        Lt => Some(x < y),
+        Lte => Some(x <= y),
+        Gt => Some(x > y),
+        Gte => Some(x >= y),
+        Eq => Some(x == y),
+        Neq => Some(x != y),
+        _ => None,
+    }
+}
+
+/// Does an arithmetic operation over floating-point numbers following IEEE 754.
+///
+/// These operations do not overflow or panic, they produce infinities and `NaN` instead.
+pub(crate) fn float_binary_operation(op: BinOp, x: f64, y: f64) -> f64 {
+    match op {
+        Add => x + y,
+        Sub => x - y,
+        Mul => x * y,
+        Div => x / y,
+        Rem => x % y,
+        _ => unreachable!("`{}` is not an arithmetic operator", op),
+    }
+}
+
+/// Does an unary operation over a floating-point number following IEEE 754.
+pub(crate) fn float_unary_operation(op: UnOp, x: f64) -> f64 {
+    match op {
+        Neg => -x,
+        Not => unreachable!("`{}` is not an arithmetic operator", op),
+    }
+}
+
+fn try_into_usize_or_panic(n: &BigInt) -> usize {
+    match usize::try_from(n) {
+        Ok(n) => n,
+        _ => panic!("Operand `{}` is negative or too large", n),
+    }
 }
 
 fn try_into_u32_or_panic(n: i64) -> u32 {
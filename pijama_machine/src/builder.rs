@@ -3,12 +3,15 @@ use std::io::{Stdout, Write};
 use crate::{
     arithmetic::{Arithmetic, OverflowArithmetic},
     env::Env,
+    observer::{Observation, Observer},
+    trace::Trace,
     Machine,
 };
 
 pub struct MachineBuilder<W: Write, A: Arithmetic> {
     env: Env<W>,
     arithmetic: A,
+    observer: Option<Box<dyn Observer>>,
 }
 
 impl Default for MachineBuilder<Stdout, OverflowArithmetic> {
@@ -16,6 +19,7 @@ impl Default for MachineBuilder<Stdout, OverflowArithmetic> {
         MachineBuilder {
             env: Env::default(),
             arithmetic: OverflowArithmetic,
+            observer: None,
         }
     }
 }
@@ -25,6 +29,7 @@ impl<W: Write, A: Arithmetic> MachineBuilder<W, A> {
         Machine {
             env: self.env,
             _arithmetic: self.arithmetic,
+            observation: self.observer.map(Observation::new),
         }
     }
 
@@ -32,6 +37,7 @@ impl<W: Write, A: Arithmetic> MachineBuilder<W, A> {
         MachineBuilder {
             env,
             arithmetic: self.arithmetic,
+            observer: self.observer,
         }
     }
 
@@ -39,6 +45,20 @@ impl<W: Write, A: Arithmetic> MachineBuilder<W, A> {
         MachineBuilder {
             env: self.env,
             arithmetic,
+            observer: self.observer,
         }
     }
+
+    /// Calls `observer` before each reduction performed by the machine.
+    pub fn with_observer(self, observer: impl Observer + 'static) -> Self {
+        MachineBuilder {
+            observer: Some(Box::new(observer)),
+            ..self
+        }
+    }
+
+    /// Logs every reduction performed by the machine as explained in `Trace`.
+    pub fn with_trace(self, trace: Trace) -> Self {
+        self.with_observer(trace)
+    }
 }
@@ -1,39 +1,72 @@
-use std::{borrow::Borrow, io::Write};
+use std::{borrow::Borrow, convert::TryFrom, io::Write};
 
-use pijama_common::{BinOp, Literal, UnOp};
+use num_bigint::BigInt;
+
+use pijama_common::{BinOp, IntTy, Literal, UnOp};
 
 use pijama_lir::{
     PrimFn as Primitive,
     Term::{self, *},
 };
 
-use crate::{arithmetic::Arithmetic, Machine};
+use crate::{
+    arithmetic::{
+        big_binary_operation, big_comparison, big_unary_operation, float_binary_operation,
+        float_comparison, float_unary_operation, Arithmetic,
+    },
+    observer::{Control, Reduction, Step},
+    EvalResult, Failure, Machine,
+};
 
-/// Evaluate `$term` in place using the `$self` machine. Then return `(changed, $ret)` where
+/// Evaluate `$term` in place using the `$self` machine. Then return `Ok((changed, $ret))` where
 /// `changed` states if the evaluation produced any changes and `$ret` is a `Term` (possibly
-/// including `$term`).
+/// including `$term`). If the evaluation fails, return the failure instead.
 macro_rules! eval_in_place {
     ($self:ident, $term:ident, $ret:expr) => {{
-        let (changed, new_t) = $self.eval(*$term);
+        let (changed, new_t) = $self.eval(*$term)?;
         *$term = new_t;
-        (changed, $ret)
+        Ok((changed, $ret))
     }};
 }
 
 impl<W: Write, A: Arithmetic> Machine<W, A> {
-    pub(super) fn eval(&mut self, mut term: Term) -> (bool, Term) {
+    pub(super) fn eval(&mut self, mut term: Term) -> EvalResult<(bool, Term)> {
+        if let Some(observation) = &mut self.observation {
+            observation.enter();
+        }
         let mut changed = false;
         while {
-            let (eval, new_term) = self.step(term);
+            let (eval, new_term) = self.step(term)?;
             term = new_term;
             eval
         } {
             changed = true;
         }
-        (changed, term)
+        if let Some(observation) = &mut self.observation {
+            observation.exit();
+        }
+        Ok((changed, term))
     }
 
-    pub(super) fn step(&mut self, term: Term) -> (bool, Term) {
+    /// Calls the observer, if there is one, before a reduction of the redex returned by `redex`.
+    ///
+    /// Returns `Failure::Stopped` if the observer stops the evaluation.
+    fn observe(&mut self, reduction: Reduction, redex: impl Fn() -> Term) -> EvalResult<()> {
+        if let Some(observation) = &mut self.observation {
+            let step = Step::new(reduction, observation.depth(), observation.loc, &redex);
+            if let Control::Stop = observation.observer.reduce(&step, self.env.stdout()) {
+                return Err(Failure::Stopped);
+            }
+        }
+        Ok(())
+    }
+
+    /// Performs one step of the evaluation of `term`. Returns whether `term` changed and the term
+    /// after the step.
+    ///
+    /// The subterms that must be evaluated before reducing `term` are evaluated completely during
+    /// the step.
+    pub fn step(&mut self, term: Term) -> EvalResult<(bool, Term)> {
         match term {
             // Dispatch step for binary operations
             BinaryOp(op, t1, t2) => self.step_bin_op(op, t1, t2),
@@ -52,20 +85,82 @@ impl<W: Write, A: Arithmetic> Machine<W, A> {
             Cond(t1, t2, t3) => self.step_cond(t1, t2, t3),
             // Dispatch step for fixed point operation
             Fix(t1) => self.step_fix(t1),
+            // Dispatch step for fixed point operation over a group of terms
+            FixGroup(index, terms) => self.step_fix_group(index, terms),
+            // Dispatch step for eliminations of sum types
+            Case(t1, t2, t3) => self.step_case(t1, t2, t3),
+            // Dispatch step for sequences
+            Seq(t1, t2) => self.step_seq(*t1, *t2),
+            // Located terms evaluate to the inner term.
+            Loc(loc, t1) => {
+                if let Some(observation) = &mut self.observation {
+                    observation.loc = Some(loc);
+                }
+                Ok((true, *t1))
+            }
+            // Function bodies evaluate to the inner term.
+            Body(loc, t1) => {
+                if let Some(observation) = &mut self.observation {
+                    observation.observer.call(loc);
+                }
+                Ok((true, *t1))
+            }
             // Any other term stops the evaluation.
-            Var(_) | Lit(_) | Abs(_) | PrimFn(_) => (false, term),
+            Var(_)
+            | Lit(_)
+            | FixedLit(_, _)
+            | BigLit(_)
+            | FloatLit(_)
+            | CharLit(_)
+            | Abs(_)
+            | PrimFn(_)
+            | Variant(_, _) => Ok((false, term)),
         }
     }
+
+    /// Evaluation step for eliminations of sum types (case t1 of t2 | t3)
+    fn step_case(
+        &mut self,
+        mut t1: Box<Term>,
+        t2: Box<Term>,
+        t3: Box<Term>,
+    ) -> EvalResult<(bool, Term)> {
+        if let Term::Variant(..) = *t1 {
+            self.observe(Reduction::Case, || Case(t1.clone(), t2.clone(), t3.clone()))?;
+        }
+        match *t1 {
+            // If t1 is a variant, apply the branch for its tag to its payload.
+            Term::Variant(0, payload) => Ok((true, App(t2, payload))),
+            Term::Variant(_, payload) => Ok((true, App(t3, payload))),
+            // If t1 is not a variant, evaluate it in place and return (case t1 of t2 | t3)
+            _ => eval_in_place!(self, t1, Term::Case(t1, t2, t3)),
+        }
+    }
+    /// Evaluation step for sequences (t1; t2)
+    fn step_seq(&mut self, t1: Term, t2: Term) -> EvalResult<(bool, Term)> {
+        self.observe(Reduction::Seq, || {
+            Seq(Box::new(t1.clone()), Box::new(t2.clone()))
+        })?;
+        // Evaluate t1 completely and evaluate to t2.
+        self.eval(t1)?;
+        Ok((true, t2))
+    }
     /// Evaluation step for conditionals (if t1 then t2 else t3)
-    fn step_cond(&mut self, mut t1: Box<Term>, t2: Box<Term>, t3: Box<Term>) -> (bool, Term) {
+    fn step_cond(
+        &mut self,
+        mut t1: Box<Term>,
+        t2: Box<Term>,
+        t3: Box<Term>,
+    ) -> EvalResult<(bool, Term)> {
         // If t1 is a literal, we should be able to evaluate the conditional
         if let lit @ Term::Lit(_) = t1.borrow() {
+            self.observe(Reduction::Cond, || Cond(t1.clone(), t2.clone(), t3.clone()))?;
             if lit.as_bool() {
                 // If t1 is true, evaluate to t2.
-                (true, *t2)
+                Ok((true, *t2))
             } else {
                 // If t1 is false, evaluate to t3.
-                (true, *t3)
+                Ok((true, *t3))
             }
         } else {
             // If t1 is not a literal, evaluate it in place and return (if t1 then t2 else t3)
@@ -74,21 +169,72 @@ impl<W: Write, A: Arithmetic> Machine<W, A> {
     }
 
     /// Evaluation step for binary operations (t1 op t2)
-    fn step_bin_op(&mut self, op: BinOp, mut t1: Box<Term>, mut t2: Box<Term>) -> (bool, Term) {
+    fn step_bin_op(
+        &mut self,
+        op: BinOp,
+        mut t1: Box<Term>,
+        mut t2: Box<Term>,
+    ) -> EvalResult<(bool, Term)> {
         use BinOp::*;
 
+        if matches!(
+            (op, t1.borrow(), t2.borrow()),
+            (And, Lit(0), _)
+                | (Or, Lit(1), _)
+                | (_, Lit(_), Lit(_))
+                | (_, FixedLit(_, _), FixedLit(_, _))
+                | (_, BigLit(_), BigLit(_))
+                | (_, FloatLit(_), FloatLit(_))
+                | (_, CharLit(_), CharLit(_))
+        ) {
+            self.observe(Reduction::Prim, || BinaryOp(op, t1.clone(), t2.clone()))?;
+        }
+
         match (op, t1.borrow(), t2.borrow()) {
             // If op is && and t1 is false evaluate to false
-            (And, Lit(0), _) => (true, false.into()),
+            (And, Lit(0), _) => Ok((true, false.into())),
             // If op is || and t1 is true evaluate to true
-            (Or, Lit(1), _) => (true, true.into()),
+            (Or, Lit(1), _) => Ok((true, true.into())),
             // If both are literals evaluate with native operation
-            (_, Lit(l1), Lit(l2)) => (true, Lit(A::binary_operation(op, *l1, *l2))),
+            (_, Lit(l1), Lit(l2)) => Ok((true, Lit(A::binary_operation(op, *l1, *l2)))),
+            // If both are literals with a fixed width evaluate with the operation for their type.
+            // Comparisons evaluate to booleans instead.
+            (_, FixedLit(ty, l1), FixedLit(_, l2)) => {
+                let result = A::fixed_binary_operation(op, *ty, *l1, *l2);
+                match op {
+                    Lt | Lte | Gt | Gte | Eq | Neq => Ok((true, Lit(result))),
+                    _ => Ok((true, FixedLit(*ty, result))),
+                }
+            }
+            // If both are literals with arbitrary precision evaluate with the operation for them.
+            // Comparisons evaluate to booleans instead.
+            (_, BigLit(l1), BigLit(l2)) => match big_comparison(op, l1, l2) {
+                Some(result) => Ok((true, result.into())),
+                None => Ok((true, BigLit(big_binary_operation(op, l1, l2)))),
+            },
+            // If both are floating-point literals evaluate with the operation for them. Comparisons
+            // evaluate to booleans instead.
+            (_, FloatLit(l1), FloatLit(l2)) => {
+                let (x, y) = (f64::from_bits(*l1), f64::from_bits(*l2));
+                match float_comparison(op, x, y) {
+                    Some(result) => Ok((true, result.into())),
+                    None => Ok((true, FloatLit(float_binary_operation(op, x, y).to_bits()))),
+                }
+            }
+            // If both are character literals compare their code points.
+            (_, CharLit(c1), CharLit(c2)) => {
+                let (n1, n2) = (u32::from(*c1).into(), u32::from(*c2).into());
+                Ok((true, Lit(A::binary_operation(op, n1, n2))))
+            }
             // If t2 is not a literal, evaluate it.
-            (_, Lit(_), _) => {
-                let (changed, new_t2) = self.eval(*t2);
+            (_, Lit(_), _)
+            | (_, FixedLit(_, _), _)
+            | (_, BigLit(_), _)
+            | (_, FloatLit(_), _)
+            | (_, CharLit(_), _) => {
+                let (changed, new_t2) = self.eval(*t2)?;
                 *t2 = new_t2;
-                (changed, Term::BinaryOp(op, t1, t2))
+                Ok((changed, Term::BinaryOp(op, t1, t2)))
             }
             // If t1 is not a literal, evaluate it.
             _ => eval_in_place!(self, t1, Term::BinaryOp(op, t1, t2)),
@@ -96,32 +242,64 @@ impl<W: Write, A: Arithmetic> Machine<W, A> {
     }
 
     /// Evaluation step for unary operations (op t1)
-    fn step_un_op(&mut self, op: UnOp, mut t1: Box<Term>) -> (bool, Term) {
+    fn step_un_op(&mut self, op: UnOp, mut t1: Box<Term>) -> EvalResult<(bool, Term)> {
+        if let Lit(_) | FixedLit(_, _) | BigLit(_) | FloatLit(_) = t1.borrow() {
+            self.observe(Reduction::Prim, || UnaryOp(op, t1.clone()))?;
+        }
         // If t1 is a literal, do the operation.
-        if let Term::Lit(lit) = t1.borrow() {
-            (true, Term::Lit(A::unary_operation(op, *lit)))
-        // If t1 is not a literal, evaluate it.
-        } else {
-            eval_in_place!(self, t1, Term::UnaryOp(op, t1))
+        match t1.borrow() {
+            Term::Lit(lit) => Ok((true, Term::Lit(A::unary_operation(op, *lit)))),
+            Term::FixedLit(ty, lit) => Ok((
+                true,
+                Term::FixedLit(*ty, A::fixed_unary_operation(op, *ty, *lit)),
+            )),
+            Term::BigLit(lit) => Ok((true, Term::BigLit(big_unary_operation(op, lit)))),
+            Term::FloatLit(lit) => {
+                let result = float_unary_operation(op, f64::from_bits(*lit));
+                Ok((true, Term::FloatLit(result.to_bits())))
+            }
+            // If t1 is not a literal, evaluate it.
+            _ => eval_in_place!(self, t1, Term::UnaryOp(op, t1)),
         }
     }
 
     /// Evaluation step for the fixed-point operation (fix t1)
-    fn step_fix(&mut self, mut t1: Box<Term>) -> (bool, Term) {
+    fn step_fix(&mut self, mut t1: Box<Term>) -> EvalResult<(bool, Term)> {
         // If t1 is an abstraction (\. t2), replace the argument of t1 by (fix t1) inside t2
         // and evaluate to t2.
         if let Term::Abs(t2) = t1.borrow() {
+            self.observe(Reduction::Fix, || Fix(t1.clone()))?;
             let mut t2 = t2.clone();
             t2.replace(0, &mut Term::Fix(t1));
-            (true, *t2)
+            Ok((true, *t2))
         // If t1 is not an abstraction, evaluate it.
         } else {
             eval_in_place!(self, t1, Term::Fix(t1))
         }
     }
 
+    /// Evaluation step for the fixed-point operation over a group of terms (fixi t1 ... tn)
+    fn step_fix_group(&mut self, index: usize, terms: Vec<Term>) -> EvalResult<(bool, Term)> {
+        self.observe(Reduction::Fix, || FixGroup(index, terms.clone()))?;
+        // Apply the i-th term to (fix0 t1 ... tn), ..., (fixn t1 ... tn). Each term is a chain of n
+        // abstractions, so beta reduction will replace each argument by the corresponding fixed
+        // point.
+        let mut term = terms[index].clone();
+        for index in 0..terms.len() {
+            term = App(Box::new(term), Box::new(FixGroup(index, terms.clone())));
+        }
+        Ok((true, term))
+    }
+
     /// Evaluation step for beta reduction ((λ. body) arg)
-    fn step_beta_reduction(&mut self, mut body: Term, mut arg: Box<Term>) -> (bool, Term) {
+    fn step_beta_reduction(
+        &mut self,
+        mut body: Term,
+        mut arg: Box<Term>,
+    ) -> EvalResult<(bool, Term)> {
+        self.observe(Reduction::Beta, || {
+            App(Box::new(Abs(Box::new(body.clone()))), arg.clone())
+        })?;
         // increase the indices of the argument so they can coincide with the indices of the body.
         arg.shift(true, 0);
         // replace the index 0 by the argument inside the body.
@@ -130,20 +308,116 @@ impl<W: Write, A: Arithmetic> Machine<W, A> {
         // longer exists.
         body.shift(false, 0);
         // return the body
-        (true, body)
+        Ok((true, body))
     }
     /// Evaluation step for application of primitive functions (prim arg)
-    fn step_primitive_app(&mut self, prim: Primitive, arg: Term) -> (bool, Term) {
+    fn step_primitive_app(&mut self, prim: Primitive, arg: Term) -> EvalResult<(bool, Term)> {
         // Evaluate argument
-        let (_, arg) = self.eval(arg);
+        let (_, arg) = self.eval(arg)?;
+        self.observe(Reduction::Prim, || {
+            App(Box::new(PrimFn(prim.clone())), Box::new(arg.clone()))
+        })?;
         let stdout = self.env.stdout();
         match prim {
-            Primitive::PrintInt => writeln!(stdout, "{}", arg),
-            Primitive::PrintBool => writeln!(stdout, "{}", arg != Term::Lit(0)),
-            Primitive::PrintUnit => writeln!(stdout, "unit"),
-            Primitive::PrintFunc => writeln!(stdout, "<function>"),
+            Primitive::PrintInt
+            | Primitive::PrintFloat
+            | Primitive::PrintChar
+            | Primitive::PrintBool
+            | Primitive::PrintUnit
+            | Primitive::PrintFunc => writeln!(stdout, "{}", Self::show(&prim, &arg)),
+            Primitive::ToInt => return Ok((true, Lit(Self::convert(arg, IntTy::I64)))),
+            Primitive::ToFixed(ty) => return Ok((true, FixedLit(ty, Self::convert(arg, ty)))),
+            Primitive::ToBigInt => return Ok((true, BigLit(Self::convert_big(arg)))),
+            Primitive::ToFloat => return Ok((true, FloatLit(Self::convert_float(arg).to_bits()))),
+            Primitive::Truncate => return Ok((true, Lit(A::truncation(Self::convert_float(arg))))),
+            Primitive::Ord => match arg {
+                CharLit(c) => return Ok((true, Lit(u32::from(c).into()))),
+                _ => panic!("Non-character literal {}", arg),
+            },
+            Primitive::Chr => match arg {
+                Lit(n) => match u32::try_from(n).ok().and_then(std::char::from_u32) {
+                    Some(c) => return Ok((true, CharLit(c))),
+                    None => panic!("Integer `{}` is not a valid character", n),
+                },
+                _ => panic!("Non-integer literal {}", arg),
+            },
+            Primitive::Assert(loc) => {
+                if !arg.as_bool() {
+                    return Err(Failure::Assertion {
+                        loc,
+                        message: "assertion failed".to_owned(),
+                    });
+                }
+                return Ok((true, Literal::Unit.into()));
+            }
+            // The first argument is stored until the second one is applied.
+            Primitive::AssertEq(loc, print, None) => {
+                return Ok((
+                    true,
+                    PrimFn(Primitive::AssertEq(loc, print, Some(Box::new(arg)))),
+                ));
+            }
+            Primitive::AssertEq(loc, print, Some(lhs)) => {
+                let eq = BinaryOp(BinOp::Eq, lhs.clone(), Box::new(arg.clone()));
+                if !self.eval(eq)?.1.as_bool() {
+                    let message = format!(
+                        "assertion failed: `{}` is not equal to `{}`",
+                        Self::show(&print, &lhs),
+                        Self::show(&print, &arg)
+                    );
+                    return Err(Failure::Assertion { loc, message });
+                }
+                return Ok((true, Literal::Unit.into()));
+            }
         }
         .expect("Primitive print failed");
-        (true, Literal::Unit.into())
+        Ok((true, Literal::Unit.into()))
+    }
+
+    /// Shows the literal `term` in the same way as the print primitive `print`.
+    fn show(print: &Primitive, term: &Term) -> String {
+        match print {
+            Primitive::PrintChar => match term {
+                CharLit(c) => c.to_string(),
+                _ => panic!("Non-character literal {}", term),
+            },
+            Primitive::PrintBool => (*term != Term::Lit(0)).to_string(),
+            Primitive::PrintUnit => "unit".to_owned(),
+            Primitive::PrintFunc => "<function>".to_owned(),
+            _ => term.to_string(),
+        }
+    }
+
+    /// Converts the integer literal `term` to the type `to`. Integers of type `Int` are converted
+    /// as if their type were `I64`.
+    fn convert(term: Term, to: IntTy) -> i64 {
+        match term {
+            Lit(n) => A::conversion(n, IntTy::I64, to),
+            FixedLit(from, n) => A::conversion(n, from, to),
+            BigLit(n) => A::big_conversion(&n, to),
+            _ => panic!("Non-integer literal {}", term),
+        }
+    }
+
+    /// Converts the integer literal `term` to an integer of arbitrary precision.
+    fn convert_big(term: Term) -> BigInt {
+        match term {
+            Lit(n) => n.into(),
+            FixedLit(ty, n) => ty.value(n).into(),
+            BigLit(n) => n,
+            _ => panic!("Non-integer literal {}", term),
+        }
+    }
+
+    /// Converts the numeric literal `term` to the nearest floating-point number.
+    fn convert_float(term: Term) -> f64 {
+        match term {
+            Lit(n) => n as f64,
+            FixedLit(ty, n) => ty.value(n) as f64,
+            // Parsing the decimal representation rounds to the nearest floating-point number.
+            BigLit(n) => n.to_string().parse().unwrap(),
+            FloatLit(bits) => f64::from_bits(bits),
+            _ => panic!("Non-numeric literal {}", term),
+        }
     }
 }
@@ -1,23 +1,60 @@
-use std::io::Write;
+use std::{fmt, io::Write};
 
+use pijama_common::location::Location;
 use pijama_lir::Term;
 
-use crate::{arithmetic::Arithmetic, env::Env};
+use crate::{arithmetic::Arithmetic, env::Env, observer::Observation};
 
 pub mod arithmetic;
 mod builder;
 pub mod env;
 mod eval;
+pub mod observer;
+pub mod trace;
 
 pub use builder::MachineBuilder;
 
 pub struct Machine<W: Write, A: Arithmetic> {
     env: Env<W>,
     _arithmetic: A,
+    observation: Option<Observation>,
+}
+
+/// The type returned by the evaluation of a term.
+pub type EvalResult<T> = Result<T, Failure>;
+
+/// The reason why the evaluation of a term stopped before reaching a value.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Failure {
+    /// An assertion failed.
+    Assertion {
+        /// Location of the assertion function that failed.
+        loc: Location,
+        message: String,
+    },
+    /// An observer stopped the evaluation by returning `Control::Stop`.
+    Stopped,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::Assertion { message, .. } => write!(f, "{}", message),
+            Failure::Stopped => write!(f, "the evaluation was stopped"),
+        }
+    }
 }
 
 impl<W: Write, A: Arithmetic> Machine<W, A> {
-    pub fn evaluate(&mut self, term: Term) -> Term {
-        self.eval(term).1
+    /// Checks if the evaluation performed by this machine is observed.
+    pub fn is_observed(&self) -> bool {
+        self.observation.is_some()
+    }
+
+    /// Evaluates `term`, stopping at the first assertion that fails or when the observer stops the
+    /// evaluation.
+    pub fn evaluate(&mut self, term: Term) -> EvalResult<Term> {
+        let (_, term) = self.eval(term)?;
+        Ok(term)
     }
 }
@@ -0,0 +1,128 @@
+//! Hooks into the evaluation performed by the machine.
+//!
+//! An `Observer` is called before each reduction performed by the machine and it can inspect the
+//! redex, write to the output of the machine or stop the evaluation. This is used to trace the
+//! evaluation and to debug programs step by step.
+use std::{fmt, io::Write};
+
+use pijama_common::location::Location;
+use pijama_lir::Term;
+
+/// A kind of reduction performed by the machine.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Reduction {
+    /// Replacement of the argument of an abstraction applied to a term.
+    Beta,
+    /// Unfolding of a fixed point.
+    Fix,
+    /// Choice of a branch of a conditional.
+    Cond,
+    /// Choice of a branch of an elimination of a sum type.
+    Case,
+    /// Evaluation of the first term of a sequence.
+    Seq,
+    /// Application of a primitive function or operation.
+    Prim,
+}
+
+impl fmt::Display for Reduction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Reduction::Beta => write!(f, "beta"),
+            Reduction::Fix => write!(f, "fix"),
+            Reduction::Cond => write!(f, "cond"),
+            Reduction::Case => write!(f, "case"),
+            Reduction::Seq => write!(f, "seq"),
+            Reduction::Prim => write!(f, "prim"),
+        }
+    }
+}
+
+/// A reduction about to be performed by the machine.
+pub struct Step<'a> {
+    pub reduction: Reduction,
+    /// How deeply the redex is nested inside the evaluated term. Top-level redexes have depth
+    /// zero.
+    pub depth: usize,
+    /// Location of the innermost located term containing the redex. This is only available for
+    /// terms lowered with `Term::from_hir_with_locations`.
+    pub loc: Option<Location>,
+    redex: &'a dyn Fn() -> Term,
+}
+
+impl<'a> Step<'a> {
+    pub(crate) fn new(
+        reduction: Reduction,
+        depth: usize,
+        loc: Option<Location>,
+        redex: &'a dyn Fn() -> Term,
+    ) -> Self {
+        Step {
+            reduction,
+            depth,
+            loc,
+            redex,
+        }
+    }
+
+    /// Returns the term being reduced.
+    ///
+    /// The redex is built on each call, so it should only be called if it is going to be used.
+    pub fn redex(&self) -> Term {
+        (self.redex)()
+    }
+}
+
+/// Whether the evaluation must go on after an observer is called.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Control {
+    Continue,
+    /// Stop the evaluation. The machine returns `Failure::Stopped`.
+    Stop,
+}
+
+/// An observer of the evaluation performed by a machine.
+pub trait Observer {
+    /// Called before each reduction, `output` is the output of the machine.
+    fn reduce(&mut self, step: &Step, output: &mut dyn Write) -> Control;
+
+    /// Called when the machine starts evaluating the body of the function defined at `loc`. This
+    /// only happens for terms lowered with `Term::from_hir_with_locations`.
+    fn call(&mut self, _loc: Location) {}
+}
+
+/// An observer together with the location of the term being evaluated.
+pub(crate) struct Observation {
+    pub(crate) observer: Box<dyn Observer>,
+    /// Location of the innermost located term being evaluated.
+    pub(crate) loc: Option<Location>,
+    /// Locations to restore after each nested evaluation.
+    saved_locs: Vec<Option<Location>>,
+}
+
+impl Observation {
+    pub(crate) fn new(observer: Box<dyn Observer>) -> Self {
+        Observation {
+            observer,
+            loc: None,
+            saved_locs: Vec::new(),
+        }
+    }
+
+    /// Starts a nested evaluation.
+    pub(crate) fn enter(&mut self) {
+        self.saved_locs.push(self.loc);
+    }
+
+    /// Finishes a nested evaluation.
+    pub(crate) fn exit(&mut self) {
+        if let Some(loc) = self.saved_locs.pop() {
+            self.loc = loc;
+        }
+    }
+
+    /// Returns the depth of the current nested evaluation.
+    pub(crate) fn depth(&self) -> usize {
+        self.saved_locs.len().saturating_sub(1)
+    }
+}
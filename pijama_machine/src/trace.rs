@@ -0,0 +1,104 @@
+//! Tracing of the reductions performed by the machine.
+use std::io::{stderr, Write};
+
+use crate::observer::{Control, Observer, Step};
+
+/// Settings and state of the tracing of the reductions performed by a machine.
+///
+/// Each reduction is written as a line with its number, its kind and the redex, indented by how
+/// deeply the redex is nested inside the evaluated term. The lines are written to their own output,
+/// which is the standard error by default, so they are not mixed with the output of the program.
+pub struct Trace {
+    output: Box<dyn Write>,
+    max_depth: Option<usize>,
+    max_steps: Option<usize>,
+    /// Source code used to show where each reduction comes from.
+    source: Option<String>,
+    /// Number of reductions performed so far.
+    steps: usize,
+}
+
+impl Default for Trace {
+    fn default() -> Self {
+        Trace {
+            output: Box::new(stderr()),
+            max_depth: None,
+            max_steps: None,
+            source: None,
+            steps: 0,
+        }
+    }
+}
+
+impl Trace {
+    /// Write the trace to `output` instead of the standard error.
+    pub fn with_output(mut self, output: impl Write + 'static) -> Self {
+        self.output = Box::new(output);
+        self
+    }
+
+    /// Only log the reductions nested at most `depth` evaluations deep, top-level reductions
+    /// have depth zero.
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Stop logging after `steps` reductions. The evaluation continues normally.
+    pub fn with_max_steps(mut self, steps: usize) -> Self {
+        self.max_steps = Some(steps);
+        self
+    }
+
+    /// Show the line and column of `source` where each reduction comes from.
+    ///
+    /// This requires a term lowered with `Term::from_hir_with_locations`.
+    pub fn with_locations(mut self, source: &str) -> Self {
+        self.source = Some(source.to_owned());
+        self
+    }
+
+    /// Counts a reduction and returns the line logging it, if it must be logged.
+    // `Option::is_some_and` is not available in the toolchain targeted by the workspace.
+    #[allow(clippy::unnecessary_map_or)]
+    fn log(&mut self, step: &Step) -> Option<String> {
+        self.steps += 1;
+
+        if let Some(max_steps) = self.max_steps {
+            if self.steps == max_steps + 1 {
+                return Some(format!("[trace stopped after {} steps]", max_steps));
+            } else if self.steps > max_steps {
+                return None;
+            }
+        }
+
+        if self
+            .max_depth
+            .map_or(false, |max_depth| step.depth > max_depth)
+        {
+            return None;
+        }
+
+        let mut line = format!(
+            "[{}] {}{}",
+            self.steps,
+            "  ".repeat(step.depth),
+            step.reduction
+        );
+        if let (Some(source), Some(loc)) = (&self.source, step.loc) {
+            let (line_num, column) = loc.line_column(source);
+            line.push_str(&format!(" at {}:{}", line_num, column));
+        }
+        line.push_str(&format!(" {}", step.redex()));
+        Some(line)
+    }
+}
+
+impl Observer for Trace {
+    fn reduce(&mut self, step: &Step, _output: &mut dyn Write) -> Control {
+        if let Some(line) = self.log(step) {
+            writeln!(self.output, "{}", line).expect("Trace failed");
+        }
+        Control::Continue
+    }
+}
@@ -2,7 +2,7 @@ mod lower;
 
 use std::fmt;
 
-use pijama_common::{BinOp, Literal, Local, UnOp};
+use pijama_common::{BinOp, IntTy, Literal, Local, SumFn, UnOp};
 use pijama_ctx::{Context, LocalId, TermId};
 
 #[derive(Debug)]
@@ -14,6 +14,16 @@ pub enum BindKind {
 #[derive(Debug)]
 pub enum PrimFn {
     Print,
+    ToInt,
+    ToFixed(IntTy),
+    ToBigInt,
+    ToFloat,
+    Truncate,
+    Ord,
+    Chr,
+    Sum(SumFn),
+    Assert,
+    AssertEq,
     BinOp(BinOp),
     UnOp(UnOp),
 }
@@ -21,6 +31,16 @@ impl fmt::Display for PrimFn {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             PrimFn::Print => write!(f, "print"),
+            PrimFn::ToInt => write!(f, "to_int"),
+            PrimFn::ToFixed(ty) => write!(f, "to_{}", ty.suffix()),
+            PrimFn::ToBigInt => write!(f, "to_bigint"),
+            PrimFn::ToFloat => write!(f, "to_float"),
+            PrimFn::Truncate => write!(f, "truncate"),
+            PrimFn::Ord => write!(f, "ord"),
+            PrimFn::Chr => write!(f, "chr"),
+            PrimFn::Sum(sum_fn) => write!(f, "{}", sum_fn),
+            PrimFn::Assert => write!(f, "assert"),
+            PrimFn::AssertEq => write!(f, "assert_eq"),
             PrimFn::BinOp(op) => write!(f, "{}", op),
             PrimFn::UnOp(op) => write!(f, "{}", op),
         }
@@ -42,6 +62,12 @@ pub enum TermKind {
     PrimApp(PrimFn, Vec<Term>),
     Cond(Box<Term>, Box<Term>, Box<Term>),
     Let(BindKind, LocalId, Box<Term>, Box<Term>),
+    LetGroup(Vec<(LocalId, Term)>, Box<Term>),
+    /// The dictionary of an instance of a type class, holding its methods in the same order as
+    /// the class declares them.
+    Dict(Vec<Term>),
+    /// The selection of the method with the given index from a dictionary.
+    Select(Box<Term>, usize),
 }
 
 impl<'ast> Term {
@@ -120,6 +146,30 @@ impl<'ast, 'ctx> fmt::Display for TermCtx<'ast, 'ctx> {
                     self.spawn(tail.as_ref())
                 )
             }
+            TermKind::LetGroup(binds, tail) => {
+                write!(f, "(letrec")?;
+                for (i, (lhs_id, rhs)) in binds.iter().enumerate() {
+                    let sep = if i == 0 { "" } else { " and" };
+                    write!(
+                        f,
+                        "{} {} = {}",
+                        sep,
+                        self.get_local(*lhs_id),
+                        self.spawn(rhs)
+                    )?;
+                }
+                write!(f, " in {})", self.spawn(tail.as_ref()))
+            }
+            TermKind::Dict(methods) => {
+                write!(f, "(dict")?;
+                for method in methods {
+                    write!(f, " {}", self.spawn(method))?;
+                }
+                write!(f, ")")
+            }
+            TermKind::Select(dict, index) => {
+                write!(f, "(select{} {})", index, self.spawn(dict.as_ref()))
+            }
         }
     }
 }
@@ -1,27 +1,91 @@
 use pijama_common::Primitive;
-use pijama_ctx::{Context, ContextExt, LocalId, TermId, TypeInfo};
+use pijama_ctx::{BuiltinMethod, Context, ContextExt, LocalId, Selection, TermId, TypeInfo};
 use pijama_hir::{BindKind as HirBindKind, Term as HirTerm, TermKind as HirTermKind};
 use pijama_ty::Ty;
 
 use crate::{BindKind, PrimFn, Term, TermKind};
 
+/// Returns the primitive function implementing `prim`.
+fn prim_fn(prim: Primitive) -> PrimFn {
+    match prim {
+        Primitive::Print => PrimFn::Print,
+        Primitive::ToInt => PrimFn::ToInt,
+        Primitive::ToFixed(ty) => PrimFn::ToFixed(ty),
+        Primitive::ToBigInt => PrimFn::ToBigInt,
+        Primitive::ToFloat => PrimFn::ToFloat,
+        Primitive::Truncate => PrimFn::Truncate,
+        Primitive::Ord => PrimFn::Ord,
+        Primitive::Chr => PrimFn::Chr,
+        Primitive::Sum(sum_fn) => PrimFn::Sum(sum_fn),
+        Primitive::Assert => PrimFn::Assert,
+        Primitive::AssertEq => PrimFn::AssertEq,
+    }
+}
+
+/// Lowers the method used by the term `id`.
+///
+/// Methods of built-in instances are primitive functions. Methods of other instances are selected
+/// from the dictionary chosen for such use.
+fn lower_method(id: TermId, ctx: &mut Context) -> TermKind {
+    match ctx.get_selection(id).unwrap() {
+        Selection::Builtin(BuiltinMethod::Prim(prim)) => TermKind::PrimApp(prim_fn(prim), vec![]),
+        Selection::Builtin(BuiltinMethod::BinOp(op)) => {
+            TermKind::PrimApp(PrimFn::BinOp(op), vec![])
+        }
+        Selection::Dict { dict, index, .. } => {
+            let loc = ctx.get_location(id).unwrap();
+            let term_id: TermId = ctx.new_id();
+            ctx.insert_location(term_id, loc);
+
+            let dict = Term {
+                id: term_id,
+                kind: TermKind::Var(dict),
+            };
+            TermKind::Select(Box::new(dict), index)
+        }
+    }
+}
+
 pub(crate) fn lower_term(term: &HirTerm, ctx: &mut Context) -> Term {
     let kind = match &term.kind {
-        HirTermKind::Lit(lit) => TermKind::Lit(*lit),
+        HirTermKind::Lit(lit) => TermKind::Lit(lit.clone()),
         HirTermKind::Var(local) => TermKind::Var(*local),
-        HirTermKind::PrimFn(prim) => {
-            let prim = match prim {
-                Primitive::Print => PrimFn::Print,
-            };
-            TermKind::PrimApp(prim, vec![])
-        }
+        HirTermKind::PrimFn(prim) => match ctx.get_selection(term.id) {
+            // `assert_eq` uses the `eq` method but it is not implemented by it.
+            Some(Selection::Dict { .. }) if *prim != Primitive::AssertEq => {
+                lower_method(term.id, ctx)
+            }
+            _ => TermKind::PrimApp(prim_fn(*prim), vec![]),
+        },
+        HirTermKind::Method(_) => lower_method(term.id, ctx),
+        HirTermKind::Hole => unreachable!("holes are rejected during type-checking"),
         HirTermKind::UnaryOp(op, term) => {
             TermKind::PrimApp(PrimFn::UnOp(*op), vec![lower_term(term, ctx)])
         }
-        HirTermKind::BinaryOp(op, t1, t2) => TermKind::PrimApp(
-            PrimFn::BinOp(*op),
-            vec![lower_term(t1, ctx), lower_term(t2, ctx)],
-        ),
+        HirTermKind::BinaryOp(op, t1, t2) => {
+            let args = vec![lower_term(t1, ctx), lower_term(t2, ctx)];
+            // Comparisons implemented by a user-defined instance are calls to its method.
+            if let Some(Selection::Dict { .. }) = ctx.get_selection(term.id) {
+                let loc = ctx.get_location(term.id).unwrap();
+                let operand = ctx.get_type_info(t1.id).unwrap().ty.clone();
+                let ty = Ty::Arrow(
+                    Box::new(operand.clone()),
+                    Box::new(Ty::Arrow(Box::new(operand), Box::new(Ty::Bool))),
+                );
+
+                let term_id: TermId = ctx.new_id();
+                ctx.insert_location(term_id, loc);
+                ctx.insert_type_info(term_id, TypeInfo { ty, loc });
+
+                let func = Term {
+                    id: term_id,
+                    kind: lower_method(term.id, ctx),
+                };
+                TermKind::App(Box::new(func), args)
+            } else {
+                TermKind::PrimApp(PrimFn::BinOp(*op), args)
+            }
+        }
         HirTermKind::Abs(arg, body) => {
             let mut args = vec![*arg];
             let mut body = body.as_ref();
@@ -89,13 +153,13 @@ pub(crate) fn lower_term(term: &HirTerm, ctx: &mut Context) -> Term {
                 });
             }
 
-            let kind = if let HirTermKind::PrimFn(prim) = &func.kind {
-                let prim = match prim {
-                    Primitive::Print => PrimFn::Print,
-                };
-                TermKind::PrimApp(prim, args)
-            } else {
-                TermKind::App(Box::new(lower_term(func, ctx)), args)
+            // Primitive functions, including the methods implemented by them, are applied directly.
+            let kind = match lower_term(func, ctx) {
+                Term {
+                    kind: TermKind::PrimApp(prim, prim_args),
+                    ..
+                } if prim_args.is_empty() => TermKind::PrimApp(prim, args),
+                func => TermKind::App(Box::new(func), args),
             };
 
             if new_args.len() > 0 {
@@ -124,6 +188,32 @@ pub(crate) fn lower_term(term: &HirTerm, ctx: &mut Context) -> Term {
                 Box::new(lower_term(tail.as_ref(), ctx)),
             )
         }
+        HirTermKind::LetGroup(binds, tail) => TermKind::LetGroup(
+            binds
+                .iter()
+                .map(|(lhs, rhs)| (*lhs, lower_term(rhs, ctx)))
+                .collect(),
+            Box::new(lower_term(tail.as_ref(), ctx)),
+        ),
+        // The dictionary of an instance is recursive so its methods can use each other. The
+        // locals of the methods are not bound, the methods are only selected from the dictionary.
+        HirTermKind::Instance(dict, binds, tail) => {
+            let loc = ctx.get_location(term.id).unwrap();
+            let methods = binds.iter().map(|(_, rhs)| lower_term(rhs, ctx)).collect();
+
+            let term_id: TermId = ctx.new_id();
+            ctx.insert_location(term_id, loc);
+
+            TermKind::Let(
+                BindKind::Rec,
+                *dict,
+                Box::new(Term {
+                    id: term_id,
+                    kind: TermKind::Dict(methods),
+                }),
+                Box::new(lower_term(tail.as_ref(), ctx)),
+            )
+        }
     };
     Term { id: term.id, kind }
 }
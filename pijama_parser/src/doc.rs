@@ -0,0 +1,29 @@
+//! Attachment of doc comments to the statements they document.
+use pijama_ast::node::Doc;
+use pijama_common::location::Located;
+
+/// Returns the doc comment written right before `start`, if any.
+///
+/// A doc comment is a sequence of `##` comments. Only whitespace can separate its lines from each
+/// other and from the documented statement.
+pub(crate) fn doc_before<'a>(
+    input: &str,
+    docs: &[Located<&'a str>],
+    start: usize,
+) -> Option<Doc<'a>> {
+    let end = docs.partition_point(|doc| doc.loc.end <= start);
+    let mut begin = end;
+    let mut pos = start;
+
+    while begin > 0 && input[docs[begin - 1].loc.end..pos].trim().is_empty() {
+        begin -= 1;
+        pos = docs[begin].loc.start;
+    }
+
+    if begin == end {
+        None
+    } else {
+        let lines = docs[begin..end].iter().map(|doc| doc.content).collect();
+        Some(Doc { lines })
+    }
+}
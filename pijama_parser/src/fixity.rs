@@ -0,0 +1,283 @@
+//! Nesting of operation sequences according to the precedence of their operators.
+//!
+//! The grammar parses every sequence of operations as a flat `syntax::Expression::Operation`
+//! because the precedence of the operators declared by the user is only known once their
+//! declarations are parsed. This module walks the syntax tree keeping track of the declarations in
+//! scope and turns it into the AST, replacing each sequence by nested operations using precedence
+//! climbing.
+use pijama_ast::{
+    node::{Assoc, Block, Branch, Expression, Fixity, InfixOp, Method, Node, Statement},
+    ty::TyAnnotation,
+};
+use pijama_common::{location::Located, BinOp};
+
+use crate::{syntax, ParsingError, ParsingErrorKind, ParsingResult};
+
+/// Turns `block` into an AST block, nesting every operation sequence inside it.
+pub(crate) fn resolve_operations(block: syntax::Block<'_>) -> ParsingResult<Block<'_>> {
+    Resolver::default().resolve_block(block)
+}
+
+/// Returns the fixity of a built-in binary operator.
+///
+/// Every built-in operator is left associative. Their precedences leave room for user operators
+/// binding looser than `&&` or tighter than `*`.
+fn builtin_fixity(op: BinOp) -> Fixity {
+    let prec = match op {
+        BinOp::And | BinOp::Or => 3,
+        BinOp::Lt | BinOp::Lte | BinOp::Gt | BinOp::Gte | BinOp::Eq | BinOp::Neq => 4,
+        BinOp::BitAnd | BinOp::BitOr | BinOp::BitXor | BinOp::Shr | BinOp::Shl => 5,
+        BinOp::Add | BinOp::Sub => 6,
+        BinOp::Mul | BinOp::Div | BinOp::Rem => 7,
+    };
+
+    Fixity {
+        assoc: Assoc::Left,
+        prec,
+    }
+}
+
+#[derive(Default)]
+struct Resolver<'a> {
+    /// Operators declared by the user that are in scope, the last ones shadow the first ones.
+    fixities: Vec<(&'a str, Fixity)>,
+}
+
+impl<'a> Resolver<'a> {
+    /// Returns the fixity of an operator, failing if it has not been declared.
+    fn fixity(&self, op: &Located<InfixOp<'a>>) -> ParsingResult<Fixity> {
+        match op.content {
+            InfixOp::BinOp(bin_op) => Ok(builtin_fixity(bin_op)),
+            InfixOp::Custom(name) => self
+                .fixities
+                .iter()
+                .rev()
+                .find(|(other, _)| *other == name)
+                .map(|(_, fixity)| *fixity)
+                .ok_or_else(|| {
+                    ParsingError::new(ParsingErrorKind::UndefinedOperator(name.to_owned()), op.loc)
+                }),
+        }
+    }
+
+    /// Declarations inside a block are only visible until the end of the block.
+    fn resolve_block(&mut self, block: syntax::Block<'a>) -> ParsingResult<Block<'a>> {
+        let len = self.fixities.len();
+
+        let nodes = block
+            .nodes
+            .into_iter()
+            .map(|node| match node {
+                syntax::Node::Stat(stat) => self.resolve_statement(stat).map(Node::Stat),
+                syntax::Node::Expr(expr) => self.resolve_expression(expr).map(Node::Expr),
+            })
+            .collect::<ParsingResult<_>>()?;
+        let expr = Box::new(self.resolve_expression(*block.expr)?);
+
+        self.fixities.truncate(len);
+        Ok(Block { nodes, expr })
+    }
+
+    fn resolve_body(
+        &mut self,
+        body: TyAnnotation<syntax::Block<'a>>,
+    ) -> ParsingResult<TyAnnotation<Block<'a>>> {
+        Ok(TyAnnotation {
+            item: self.resolve_block(body.item)?,
+            ty: body.ty,
+        })
+    }
+
+    fn resolve_branch(&mut self, branch: syntax::Branch<'a>) -> ParsingResult<Branch<'a>> {
+        Ok(Branch {
+            cond: self.resolve_block(branch.cond)?,
+            body: self.resolve_block(branch.body)?,
+        })
+    }
+
+    fn resolve_statement(
+        &mut self,
+        stat: Located<syntax::Statement<'a>>,
+    ) -> ParsingResult<Located<Statement<'a>>> {
+        stat.map_res(|stat| {
+            Ok(match stat {
+                syntax::Statement::Assign(lhs, rhs, doc) => {
+                    Statement::Assign(lhs, self.resolve_expression(rhs)?, doc)
+                }
+                syntax::Statement::FnDef(name, args, body, doc) => {
+                    Statement::FnDef(name, args, self.resolve_body(body)?, doc)
+                }
+                syntax::Statement::Test(name, body) => {
+                    Statement::Test(name, self.resolve_block(body)?)
+                }
+                syntax::Statement::TyAlias(name, ty) => Statement::TyAlias(name, ty),
+                syntax::Statement::Class(name, sigs) => Statement::Class(name, sigs),
+                syntax::Statement::Instance(class, ty, methods) => {
+                    let methods = methods
+                        .into_iter()
+                        .map(|method| {
+                            method.map_res(|method| {
+                                Ok(Method {
+                                    name: method.name,
+                                    args: method.args,
+                                    body: self.resolve_body(method.body)?,
+                                })
+                            })
+                        })
+                        .collect::<ParsingResult<_>>()?;
+                    Statement::Instance(class, ty, methods)
+                }
+                // An operator is not in scope inside its own declaration.
+                syntax::Statement::Infix(fixity, op, expr) => {
+                    let expr = self.resolve_expression(expr)?;
+                    self.fixities.push((op.content, fixity));
+                    Statement::Infix(fixity, op, expr)
+                }
+            })
+        })
+    }
+
+    fn resolve_expression(
+        &mut self,
+        expr: Located<syntax::Expression<'a>>,
+    ) -> ParsingResult<Located<Expression<'a>>> {
+        let loc = expr.loc;
+        let content = match expr.content {
+            syntax::Expression::Operation(operands, ops) => {
+                let operands = operands
+                    .into_iter()
+                    .map(|operand| self.resolve_expression(operand))
+                    .collect::<ParsingResult<_>>()?;
+                self.nest(operands, ops)?.content
+            }
+            syntax::Expression::Section(op, lhs, rhs) => {
+                self.fixity(&op)?;
+                Expression::Section(op, self.resolve_operand(lhs)?, self.resolve_operand(rhs)?)
+            }
+            syntax::Expression::UnaryOp(op, expr) => {
+                Expression::UnaryOp(op, self.resolve_boxed(*expr)?)
+            }
+            syntax::Expression::Cond(if_branch, branches, else_block) => Expression::Cond(
+                self.resolve_branch(if_branch)?,
+                branches
+                    .into_iter()
+                    .map(|branch| self.resolve_branch(branch))
+                    .collect::<ParsingResult<_>>()?,
+                self.resolve_block(else_block)?,
+            ),
+            syntax::Expression::AnonFn(args, body) => {
+                Expression::AnonFn(args, self.resolve_body(body)?)
+            }
+            syntax::Expression::Call(func, args) => Expression::Call(
+                self.resolve_boxed(*func)?,
+                args.into_iter()
+                    .map(|arg| self.resolve_expression(arg))
+                    .collect::<ParsingResult<_>>()?,
+            ),
+            syntax::Expression::Let(lhs, rhs, body) => {
+                Expression::Let(lhs, self.resolve_boxed(*rhs)?, self.resolve_boxed(*body)?)
+            }
+            syntax::Expression::Pipe(arg, func) => {
+                Expression::Pipe(self.resolve_boxed(*arg)?, self.resolve_boxed(*func)?)
+            }
+            syntax::Expression::Ascription(expr, ty) => {
+                Expression::Ascription(self.resolve_boxed(*expr)?, ty)
+            }
+            syntax::Expression::Literal(lit) => Expression::Literal(lit),
+            syntax::Expression::Local(local) => Expression::Local(local),
+            syntax::Expression::PrimFn(prim) => Expression::PrimFn(prim),
+            syntax::Expression::Hole => Expression::Hole,
+        };
+
+        Ok(loc.with_content(content))
+    }
+
+    fn resolve_boxed(
+        &mut self,
+        expr: Located<syntax::Expression<'a>>,
+    ) -> ParsingResult<Box<Located<Expression<'a>>>> {
+        self.resolve_expression(expr).map(Box::new)
+    }
+
+    fn resolve_operand(
+        &mut self,
+        expr: Option<Box<Located<syntax::Expression<'a>>>>,
+    ) -> ParsingResult<Option<Box<Located<Expression<'a>>>>> {
+        expr.map(|expr| self.resolve_boxed(*expr)).transpose()
+    }
+
+    /// Nests a sequence of operations using precedence climbing.
+    ///
+    /// Operators with the same precedence can only be chained if they are all left associative or
+    /// all right associative.
+    fn nest(
+        &self,
+        operands: Vec<Located<Expression<'a>>>,
+        ops: Vec<Located<InfixOp<'a>>>,
+    ) -> ParsingResult<Located<Expression<'a>>> {
+        let mut operands = operands.into_iter();
+        let mut output = operands.next().into_iter().collect::<Vec<_>>();
+        let mut pending: Vec<(Located<InfixOp<'a>>, Fixity)> = Vec::new();
+
+        for (op, operand) in ops.into_iter().zip(operands) {
+            let fixity = self.fixity(&op)?;
+
+            while let Some((top, top_fixity)) = pending.last() {
+                if top_fixity.prec < fixity.prec {
+                    break;
+                }
+                if top_fixity.prec == fixity.prec {
+                    match (top_fixity.assoc, fixity.assoc) {
+                        (Assoc::Left, Assoc::Left) => (),
+                        (Assoc::Right, Assoc::Right) => break,
+                        _ => return Err(conflict(top, &op)),
+                    }
+                }
+                let (top, _) = pending.pop().unwrap();
+                apply(&mut output, top);
+            }
+
+            pending.push((op, fixity));
+            output.push(operand);
+        }
+
+        while let Some((op, _)) = pending.pop() {
+            apply(&mut output, op);
+        }
+
+        Ok(output.pop().unwrap())
+    }
+}
+
+/// Replaces the last two operands by the application of `op` to them.
+fn apply<'a>(output: &mut Vec<Located<Expression<'a>>>, op: Located<InfixOp<'a>>) {
+    let expr2 = Box::new(output.pop().unwrap());
+    let expr1 = Box::new(output.pop().unwrap());
+    let loc = expr1.loc + expr2.loc;
+
+    let content = match op.content {
+        InfixOp::BinOp(bin_op) => Expression::BinaryOp(bin_op, expr1, expr2),
+        InfixOp::Custom(name) => Expression::Infix(op.loc.with_content(name), expr1, expr2),
+    };
+
+    output.push(loc.with_content(content));
+}
+
+fn conflict(first: &Located<InfixOp<'_>>, second: &Located<InfixOp<'_>>) -> ParsingError {
+    ParsingError::new(
+        ParsingErrorKind::ConflictingOperators(op_name(second)),
+        second.loc,
+    )
+    .with_label(
+        first.loc,
+        format!("`{}` has the same precedence", op_name(first)),
+    )
+    .with_note("use parentheses to group the operations")
+}
+
+fn op_name(op: &Located<InfixOp<'_>>) -> String {
+    match op.content {
+        InfixOp::BinOp(bin_op) => bin_op.to_string(),
+        InfixOp::Custom(name) => name.to_owned(),
+    }
+}
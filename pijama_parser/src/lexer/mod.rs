@@ -1,11 +1,16 @@
 use logos::{Logos, SpannedIter};
 
+use num_bigint::BigInt;
+
 use std::{
     convert::TryFrom,
     fmt::{Display, Formatter, Result as FmtResult},
 };
 
-use pijama_common::location::{Located, Location};
+use pijama_common::{
+    location::{Located, Location},
+    IntTy,
+};
 
 mod raw;
 
@@ -16,16 +21,20 @@ use raw::RawToken;
 pub enum LexError {
     Internal,
     Custom(&'static str),
+    IntOutOfRange,
 }
 
 pub struct Lexer<'a> {
     inner: SpannedIter<'a, RawToken<'a>>,
+    /// Doc comments found so far, in the same order as in the input.
+    pub docs: Vec<Located<&'a str>>,
 }
 
 impl<'a> Lexer<'a> {
     pub fn from_input(input: &'a str) -> Self {
         Lexer {
             inner: RawToken::lexer(input).spanned(),
+            docs: Vec::new(),
         }
     }
 }
@@ -34,7 +43,17 @@ impl<'a> Iterator for Lexer<'a> {
     type Item = Result<(usize, Token<'a>, usize), Located<LexError>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let (raw, span) = self.inner.next()?;
+        let (raw, span) = loop {
+            match self.inner.next()? {
+                (RawToken::BlockComment(true), _) => continue,
+                (RawToken::DocComment(doc), span) => {
+                    let loc = Location::new(span.start, span.end);
+                    self.docs.push(loc.with_content(doc));
+                    break (RawToken::Newline, span);
+                }
+                token => break token,
+            }
+        };
         Some(
             Token::try_from(raw)
                 .map(|token| (span.start, token, span.end))
@@ -47,10 +66,16 @@ impl<'a> Iterator for Lexer<'a> {
 pub enum Token<'a> {
     Newline,
     Int(i64),
+    FixedInt(i64, IntTy),
+    BigInt(BigInt),
+    Float(u64),
+    Char(char),
+    Str(&'a str),
     Ident(&'a str),
     Kword(Keyword),
     Op(Operator),
     Sym(Symbol),
+    InfixOp(&'a str),
 }
 
 impl<'a> Display for Token<'a> {
@@ -58,10 +83,16 @@ impl<'a> Display for Token<'a> {
         match self {
             Token::Newline => write!(f, "\\n"),
             Token::Int(int) => write!(f, "{}", int),
+            Token::FixedInt(bits, ty) => write!(f, "{}{}", ty.value(*bits), ty.suffix()),
+            Token::BigInt(int) => write!(f, "{}n", int),
+            Token::Float(bits) => write!(f, "{:?}", f64::from_bits(*bits)),
+            Token::Char(c) => write!(f, "{:?}", c),
+            Token::Str(s) => write!(f, "{:?}", s),
             Token::Ident(ident) => write!(f, "{}", ident),
             Token::Kword(kw) => write!(f, "{}", kw),
             Token::Op(op) => write!(f, "{}", op),
             Token::Sym(sym) => write!(f, "{}", sym),
+            Token::InfixOp(op) => write!(f, "{}", op),
         }
     }
 }
@@ -72,7 +103,13 @@ impl<'a> TryFrom<RawToken<'a>> for Token<'a> {
     fn try_from(raw: RawToken<'a>) -> Result<Self, Self::Error> {
         match raw {
             RawToken::Newline => Ok(Token::Newline),
-            RawToken::Int(int) => Ok(Token::Int(int)),
+            RawToken::Int(Some(int)) => Ok(Token::Int(int)),
+            RawToken::Int(None) => Err(LexError::IntOutOfRange),
+            RawToken::FixedInt((bits, ty)) => Ok(Token::FixedInt(bits, ty)),
+            RawToken::BigInt(int) => Ok(Token::BigInt(int)),
+            RawToken::Float(bits) => Ok(Token::Float(bits)),
+            RawToken::Char(c) => Ok(Token::Char(c)),
+            RawToken::Str(s) => Ok(Token::Str(s)),
             RawToken::Ident(ident) => Ok(Token::Ident(ident)),
             RawToken::Fn => Ok(Token::Kword(Keyword::Fn)),
             RawToken::If => Ok(Token::Kword(Keyword::If)),
@@ -86,7 +123,23 @@ impl<'a> TryFrom<RawToken<'a>> for Token<'a> {
             RawToken::IntTy => Ok(Token::Kword(Keyword::IntTy)),
             RawToken::BoolTy => Ok(Token::Kword(Keyword::BoolTy)),
             RawToken::UnitTy => Ok(Token::Kword(Keyword::UnitTy)),
+            RawToken::BigIntTy => Ok(Token::Kword(Keyword::BigIntTy)),
+            RawToken::FloatTy => Ok(Token::Kword(Keyword::FloatTy)),
+            RawToken::CharTy => Ok(Token::Kword(Keyword::CharTy)),
+            RawToken::OptionTy => Ok(Token::Kword(Keyword::OptionTy)),
+            RawToken::ResultTy => Ok(Token::Kword(Keyword::ResultTy)),
+            RawToken::FixedTy(ty) => Ok(Token::Kword(Keyword::FixedTy(ty))),
             RawToken::Print => Ok(Token::Kword(Keyword::Print)),
+            RawToken::Type => Ok(Token::Kword(Keyword::Type)),
+            RawToken::Class => Ok(Token::Kword(Keyword::Class)),
+            RawToken::Instance => Ok(Token::Kword(Keyword::Instance)),
+            RawToken::For => Ok(Token::Kword(Keyword::For)),
+            RawToken::Let => Ok(Token::Kword(Keyword::Let)),
+            RawToken::In => Ok(Token::Kword(Keyword::In)),
+            RawToken::Where => Ok(Token::Kword(Keyword::Where)),
+            RawToken::Infixl => Ok(Token::Kword(Keyword::Infixl)),
+            RawToken::Infixr => Ok(Token::Kword(Keyword::Infixr)),
+            RawToken::Infix => Ok(Token::Kword(Keyword::Infix)),
             RawToken::Add => Ok(Token::Op(Operator::Add)),
             RawToken::Sub => Ok(Token::Op(Operator::Sub)),
             RawToken::Mul => Ok(Token::Op(Operator::Mul)),
@@ -100,6 +153,7 @@ impl<'a> TryFrom<RawToken<'a>> for Token<'a> {
             RawToken::Not => Ok(Token::Op(Operator::Not)),
             RawToken::And => Ok(Token::Op(Operator::And)),
             RawToken::Or => Ok(Token::Op(Operator::Or)),
+            RawToken::Pipe => Ok(Token::Op(Operator::Pipe)),
             RawToken::Eq => Ok(Token::Op(Operator::Eq)),
             RawToken::Neq => Ok(Token::Op(Operator::Neq)),
             RawToken::Gt => Ok(Token::Op(Operator::Gt)),
@@ -111,7 +165,14 @@ impl<'a> TryFrom<RawToken<'a>> for Token<'a> {
             RawToken::Arrow => Ok(Token::Op(Operator::Arrow)),
             RawToken::LParen => Ok(Token::Sym(Symbol::LParen)),
             RawToken::RParen => Ok(Token::Sym(Symbol::RParen)),
+            RawToken::LBracket => Ok(Token::Sym(Symbol::LBracket)),
+            RawToken::RBracket => Ok(Token::Sym(Symbol::RBracket)),
             RawToken::Comma => Ok(Token::Sym(Symbol::Comma)),
+            RawToken::Underscore => Ok(Token::Sym(Symbol::Underscore)),
+            RawToken::Question => Ok(Token::Sym(Symbol::Question)),
+            RawToken::DocComment(_) => Ok(Token::Newline),
+            RawToken::BlockComment(_) => Err(LexError::Custom("Unterminated block comment")),
+            RawToken::InfixOp(op) => Ok(Token::InfixOp(op)),
             RawToken::Error => Err(LexError::Internal),
         }
     }
@@ -131,7 +192,23 @@ pub enum Keyword {
     IntTy,
     BoolTy,
     UnitTy,
+    BigIntTy,
+    FloatTy,
+    CharTy,
+    OptionTy,
+    ResultTy,
+    FixedTy(IntTy),
     Print,
+    Type,
+    Class,
+    Instance,
+    For,
+    Let,
+    In,
+    Where,
+    Infixl,
+    Infixr,
+    Infix,
 }
 
 impl Display for Keyword {
@@ -149,7 +226,23 @@ impl Display for Keyword {
             Keyword::IntTy => write!(f, "Int"),
             Keyword::BoolTy => write!(f, "Bool"),
             Keyword::UnitTy => write!(f, "Unit"),
+            Keyword::BigIntTy => write!(f, "BigInt"),
+            Keyword::FloatTy => write!(f, "Float"),
+            Keyword::CharTy => write!(f, "Char"),
+            Keyword::OptionTy => write!(f, "Option"),
+            Keyword::ResultTy => write!(f, "Result"),
+            Keyword::FixedTy(ty) => write!(f, "{}", ty),
             Keyword::Print => write!(f, "print"),
+            Keyword::Type => write!(f, "type"),
+            Keyword::Class => write!(f, "class"),
+            Keyword::Instance => write!(f, "instance"),
+            Keyword::For => write!(f, "for"),
+            Keyword::Let => write!(f, "let"),
+            Keyword::In => write!(f, "in"),
+            Keyword::Where => write!(f, "where"),
+            Keyword::Infixl => write!(f, "infixl"),
+            Keyword::Infixr => write!(f, "infixr"),
+            Keyword::Infix => write!(f, "infix"),
         }
     }
 }
@@ -169,6 +262,7 @@ pub enum Operator {
     Not,
     And,
     Or,
+    Pipe,
     Eq,
     Neq,
     Gt,
@@ -196,6 +290,7 @@ impl Display for Operator {
             Operator::Not => write!(f, "!"),
             Operator::And => write!(f, "&&"),
             Operator::Or => write!(f, "||"),
+            Operator::Pipe => write!(f, "|>"),
             Operator::Eq => write!(f, "=="),
             Operator::Neq => write!(f, "!="),
             Operator::Gt => write!(f, ">"),
@@ -213,7 +308,11 @@ impl Display for Operator {
 pub enum Symbol {
     LParen,
     RParen,
+    LBracket,
+    RBracket,
     Comma,
+    Underscore,
+    Question,
 }
 
 impl Display for Symbol {
@@ -221,7 +320,11 @@ impl Display for Symbol {
         match self {
             Symbol::LParen => write!(f, "("),
             Symbol::RParen => write!(f, ")"),
+            Symbol::LBracket => write!(f, "["),
+            Symbol::RBracket => write!(f, "]"),
             Symbol::Comma => write!(f, ","),
+            Symbol::Underscore => write!(f, "_"),
+            Symbol::Question => write!(f, "?"),
         }
     }
 }
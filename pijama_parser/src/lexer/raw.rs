@@ -2,11 +2,25 @@ use logos::Logos;
 
 use std::borrow::Cow;
 
+use num_bigint::BigInt;
+
+use pijama_common::IntTy;
+
 #[derive(Logos, Debug, PartialEq)]
 pub(super) enum RawToken<'a> {
     #[regex("(\n[ \t]*)")]
-    #[regex(r"(#[^\n]*\n)")]
+    #[regex(r"(#\n)")]
+    #[regex(r"(#[^\[#\n][^\n]*\n)")]
+    #[regex(r"(###[^\n]*\n)")]
     Newline,
+    // Doc comments end their line like any other comment.
+    #[regex(r"(##\n)", |lex| lex_doc_comment(lex.slice()))]
+    #[regex(r"(##[^#\n][^\n]*\n)", |lex| lex_doc_comment(lex.slice()))]
+    DocComment(&'a str),
+    // Stores whether the comment was terminated.
+    #[token("#[", lex_block_comment)]
+    BlockComment(bool),
+    // Stores the value of the integer, or `None` if it does not fit in an `Int`.
     #[regex(r"[0-9]+", |lex| lex_integer(lex.slice(), 10, false))]
     #[regex(r"-[0-9]+", |lex| lex_integer(lex.slice(), 10, true))]
     #[regex(r"0b[0-1]+", |lex| lex_integer(lex.slice(), 2, false))]
@@ -15,7 +29,27 @@ pub(super) enum RawToken<'a> {
     #[regex(r"-0o[0-7]+", |lex| lex_integer(lex.slice(), 8, true))]
     #[regex(r"0x[0-9a-f]+", |lex| lex_integer(lex.slice(), 16, false))]
     #[regex(r"-0x[0-9a-f]+", |lex| lex_integer(lex.slice(), 16, true))]
-    Int(i64),
+    Int(Option<i64>),
+    #[regex(r"-?[0-9]+[iu](8|16|32|64)", |lex| lex_fixed_integer(lex.slice(), 10))]
+    #[regex(r"-?0b[0-1]+[iu](8|16|32|64)", |lex| lex_fixed_integer(lex.slice(), 2))]
+    #[regex(r"-?0o[0-7]+[iu](8|16|32|64)", |lex| lex_fixed_integer(lex.slice(), 8))]
+    #[regex(r"-?0x[0-9a-f]+[iu](8|16|32|64)", |lex| lex_fixed_integer(lex.slice(), 16))]
+    FixedInt((i64, IntTy)),
+    #[regex(r"-?[0-9]+n", |lex| lex_big_integer(lex.slice(), 10))]
+    #[regex(r"-?0b[0-1]+n", |lex| lex_big_integer(lex.slice(), 2))]
+    #[regex(r"-?0o[0-7]+n", |lex| lex_big_integer(lex.slice(), 8))]
+    #[regex(r"-?0x[0-9a-f]+n", |lex| lex_big_integer(lex.slice(), 16))]
+    BigInt(BigInt),
+    #[regex(r"-?[0-9]+\.[0-9]+([eE][+-]?[0-9]+)?", |lex| lex_float(lex.slice()))]
+    #[regex(r"-?[0-9]+[eE][+-]?[0-9]+", |lex| lex_float(lex.slice()))]
+    Float(u64),
+    // The negated class only matches ASCII characters, so the other characters are matched by an
+    // explicit range.
+    #[regex(r#"'([^'\\\n]|[\u{80}-\u{10FFFF}]|\\[nrt0\\'"]|\\u\{[0-9a-fA-F]+\})'"#, |lex| lex_char(lex.slice()))]
+    Char(char),
+    // Strings are only used to name tests, so they cannot contain escapes or span several lines.
+    #[regex(r#""[^"\n]*""#, |lex| lex_str(lex.slice()))]
+    Str(&'a str),
     #[regex(r"[a-zA-Z][a-zA-Z0-9_]*")]
     Ident(&'a str),
     #[token("fn")]
@@ -42,8 +76,47 @@ pub(super) enum RawToken<'a> {
     BoolTy,
     #[token("Unit")]
     UnitTy,
+    #[token("BigInt")]
+    BigIntTy,
+    #[token("Float")]
+    FloatTy,
+    #[token("Char")]
+    CharTy,
+    #[token("Option")]
+    OptionTy,
+    #[token("Result")]
+    ResultTy,
+    #[token("I8", |_| IntTy::I8)]
+    #[token("I16", |_| IntTy::I16)]
+    #[token("I32", |_| IntTy::I32)]
+    #[token("I64", |_| IntTy::I64)]
+    #[token("U8", |_| IntTy::U8)]
+    #[token("U16", |_| IntTy::U16)]
+    #[token("U32", |_| IntTy::U32)]
+    #[token("U64", |_| IntTy::U64)]
+    FixedTy(IntTy),
     #[token("print")]
     Print,
+    #[token("type")]
+    Type,
+    #[token("class")]
+    Class,
+    #[token("instance")]
+    Instance,
+    #[token("for")]
+    For,
+    #[token("let")]
+    Let,
+    #[token("in")]
+    In,
+    #[token("where")]
+    Where,
+    #[token("infixl")]
+    Infixl,
+    #[token("infixr")]
+    Infixr,
+    #[token("infix")]
+    Infix,
     #[token("+")]
     Add,
     #[token("-")]
@@ -70,6 +143,10 @@ pub(super) enum RawToken<'a> {
     And,
     #[token("||")]
     Or,
+    // A pipe can start a new line to chain calls over several lines.
+    #[token("|>")]
+    #[regex(r"\n[ \t]*\|>")]
+    Pipe,
     #[token("==")]
     Eq,
     #[token("!=")]
@@ -86,6 +163,10 @@ pub(super) enum RawToken<'a> {
     LParen,
     #[token(")")]
     RParen,
+    #[token("[")]
+    LBracket,
+    #[token("]")]
+    RBracket,
     #[token("=")]
     Assign,
     #[token(":")]
@@ -94,6 +175,14 @@ pub(super) enum RawToken<'a> {
     Arrow,
     #[token(",")]
     Comma,
+    #[token("_")]
+    Underscore,
+    #[token("?")]
+    Question,
+    // Any other sequence of symbols is an operator declared by the user. `!` and `-` are left out
+    // so they can still be used as prefix operators right after another operator.
+    #[regex(r"[$%&*+./<=>@^|~]+")]
+    InfixOp(&'a str),
     #[error]
     #[regex(r"[ \t]+", logos::skip)]
     Error,
@@ -123,3 +212,113 @@ fn lex_integer(mut input: &str, radix: u32, is_neg: bool) -> Option<i64> {
 
     i64::from_str_radix(&digits, radix).ok()
 }
+
+/// Lexes an integer literal with the suffix of integers of arbitrary precision.
+fn lex_big_integer(input: &str, radix: u32) -> Option<BigInt> {
+    let input = input.strip_suffix('n')?;
+    let (is_neg, input) = match input.strip_prefix('-') {
+        Some(input) => (true, input),
+        None => (false, input),
+    };
+    // Remove the prefix of the radix to keep just the digits.
+    let digits = if radix == 10 { input } else { &input[2..] };
+
+    let value = BigInt::parse_bytes(digits.as_bytes(), radix)?;
+    Some(if is_neg { -value } else { value })
+}
+
+/// Lexes an integer literal with a suffix stating its type.
+///
+/// Returns `None` if the integer is out of the range of such type.
+fn lex_fixed_integer(input: &str, radix: u32) -> Option<(i64, IntTy)> {
+    let suffix_start = input.rfind(&['i', 'u'][..])?;
+    let (input, suffix) = input.split_at(suffix_start);
+    let ty = *IntTy::ALL.iter().find(|ty| ty.suffix() == suffix)?;
+
+    let (is_neg, input) = match input.strip_prefix('-') {
+        Some(input) => (true, input),
+        None => (false, input),
+    };
+    // Remove the prefix of the radix to keep just the digits.
+    let digits = if radix == 10 { input } else { &input[2..] };
+
+    let mut value = i128::from_str_radix(digits, radix).ok()?;
+    if is_neg {
+        value = -value;
+    }
+
+    if ty.fits(value) {
+        Some((ty.wrap(value), ty))
+    } else {
+        None
+    }
+}
+
+/// Lexes a floating-point literal, returning its bits.
+fn lex_float(input: &str) -> Option<u64> {
+    input.parse::<f64>().ok().map(f64::to_bits)
+}
+
+/// Returns the text of a doc comment, without the leading `##`, the space following it and the
+/// trailing newline.
+fn lex_doc_comment(input: &str) -> &str {
+    let text = &input[2..input.len() - 1];
+    text.strip_prefix(' ').unwrap_or(text)
+}
+
+/// Lexes a string by removing its quotes.
+fn lex_str(input: &str) -> &str {
+    &input[1..input.len() - 1]
+}
+
+/// Skips a block comment, which can contain other block comments.
+///
+/// Returns whether the comment was terminated. Otherwise the rest of the input is skipped.
+fn lex_block_comment<'a>(lex: &mut logos::Lexer<'a, RawToken<'a>>) -> bool {
+    let rest = lex.remainder().as_bytes();
+    let mut depth = 1;
+    let mut i = 0;
+
+    while i < rest.len() {
+        if rest[i..].starts_with(b"#[") {
+            depth += 1;
+            i += 2;
+        } else if rest[i..].starts_with(b"]#") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                lex.bump(i);
+                return true;
+            }
+        } else {
+            i += 1;
+        }
+    }
+
+    lex.bump(rest.len());
+    false
+}
+
+/// Lexes a character literal, replacing its escape sequence if it has one.
+///
+/// Returns `None` if the escaped code point is not a Unicode scalar value.
+fn lex_char(input: &str) -> Option<char> {
+    // Remove the quotes.
+    let input = &input[1..input.len() - 1];
+
+    match input.strip_prefix('\\') {
+        None => input.chars().next(),
+        Some("n") => Some('\n'),
+        Some("r") => Some('\r'),
+        Some("t") => Some('\t'),
+        Some("0") => Some('\0'),
+        Some(escaped) => match escaped.strip_prefix("u{") {
+            Some(code) => {
+                let code = u32::from_str_radix(code.strip_suffix('}')?, 16).ok()?;
+                std::char::from_u32(code)
+            }
+            // The escaped character is a quote or a backslash.
+            None => escaped.chars().next(),
+        },
+    }
+}
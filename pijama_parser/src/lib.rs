@@ -1,4 +1,7 @@
+mod doc;
+mod fixity;
 mod lexer;
+mod syntax;
 lalrpop_mod!(
     #[allow(unused_imports)]
     parser
@@ -30,14 +33,47 @@ pub enum ParsingErrorKind {
     ExtraToken,
     #[error("{0}")]
     Custom(&'static str),
+    #[error("Operator `{0}` has not been declared")]
+    UndefinedOperator(String),
+    #[error("Operator `{0}` cannot be chained with an operator of the same precedence")]
+    ConflictingOperators(String),
+    #[error("Integer literal does not fit in an `Int`")]
+    IntOutOfRange,
+}
+
+impl ParsingErrorKind {
+    /// Returns the stable code identifying this kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParsingErrorKind::UnexpectedToken { .. } => "E0001",
+            ParsingErrorKind::InvalidToken => "E0002",
+            ParsingErrorKind::ExtraToken => "E0003",
+            ParsingErrorKind::Custom(_) => "E0004",
+            ParsingErrorKind::UndefinedOperator(_) => "E0005",
+            ParsingErrorKind::ConflictingOperators(..) => "E0006",
+            ParsingErrorKind::IntOutOfRange => "E0007",
+        }
+    }
+
+    /// Returns a hint on how to fix this error, if there is one.
+    pub fn help(&self) -> Option<String> {
+        match self {
+            ParsingErrorKind::IntOutOfRange => {
+                Some("consider adding the `n` suffix to make it a `BigInt`".to_owned())
+            }
+            _ => None,
+        }
+    }
 }
 
 pub fn parse(input: &str) -> ParsingResult<Block> {
-    let lexer = Lexer::from_input(input);
-    let result = ProgParser::new().parse(input, lexer);
+    // The whole input is lexed first so the doc comments can be attached while parsing.
+    let mut lexer = Lexer::from_input(input);
+    let tokens = lexer.by_ref().collect::<Vec<_>>();
+    let result = ProgParser::new().parse(input, &lexer.docs, tokens);
 
     match result {
-        Ok(block) => Ok(block),
+        Ok(block) => fixity::resolve_operations(block),
         Err(err) => Err(match err {
             ParseError::InvalidToken { location } => ParsingError::new(
                 ParsingErrorKind::InvalidToken,
@@ -64,11 +100,12 @@ pub fn parse(input: &str) -> ParsingResult<Block> {
                 token: (start, _, end),
             } => ParsingError::new(ParsingErrorKind::ExtraToken, Location::new(start, end)),
             ParseError::User { error } => {
-                let msg = match error.content {
-                    LexError::Internal => "Unrecognized token",
-                    LexError::Custom(msg) => msg,
+                let kind = match error.content {
+                    LexError::Internal => ParsingErrorKind::Custom("Unrecognized token"),
+                    LexError::Custom(msg) => ParsingErrorKind::Custom(msg),
+                    LexError::IntOutOfRange => ParsingErrorKind::IntOutOfRange,
                 };
-                ParsingError::new(ParsingErrorKind::Custom(msg), error.loc)
+                ParsingError::new(kind, error.loc)
             }
         }),
     }
@@ -0,0 +1,87 @@
+//! Syntax tree produced by the grammar.
+//!
+//! These types mirror the ones in `pijama_ast::node`, except that operations are kept as flat
+//! sequences because the precedence of the operators declared by the user is only known once
+//! their declarations are parsed. The `fixity` module nests these sequences and turns this tree
+//! into the AST.
+use pijama_ast::{
+    node::{Doc, Fixity, InfixOp, MethodSig},
+    ty::{Ty, TyAnnotation},
+};
+use pijama_common::{location::Located, Literal, Local, Primitive, UnOp};
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Block<'a> {
+    pub nodes: Vec<Node<'a>>,
+    pub expr: Box<Located<Expression<'a>>>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Node<'a> {
+    Stat(Located<Statement<'a>>),
+    Expr(Located<Expression<'a>>),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Statement<'a> {
+    Assign(
+        TyAnnotation<Located<Local<'a>>>,
+        Located<Expression<'a>>,
+        Option<Doc<'a>>,
+    ),
+    FnDef(
+        Located<Local<'a>>,
+        Vec<TyAnnotation<Located<Local<'a>>>>,
+        TyAnnotation<Block<'a>>,
+        Option<Doc<'a>>,
+    ),
+    TyAlias(Located<&'a str>, Located<Ty>),
+    Class(Located<&'a str>, Vec<MethodSig<'a>>),
+    Instance(Located<&'a str>, Located<Ty>, Vec<Located<Method<'a>>>),
+    Infix(Fixity, Located<&'a str>, Located<Expression<'a>>),
+    Test(Located<&'a str>, Block<'a>),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Expression<'a> {
+    /// A sequence of operands separated by infix operators.
+    ///
+    /// There is always one operand more than operators.
+    Operation(Vec<Located<Expression<'a>>>, Vec<Located<InfixOp<'a>>>),
+    Section(
+        Located<InfixOp<'a>>,
+        Option<Box<Located<Expression<'a>>>>,
+        Option<Box<Located<Expression<'a>>>>,
+    ),
+    UnaryOp(UnOp, Box<Located<Expression<'a>>>),
+    Cond(Branch<'a>, Vec<Branch<'a>>, Block<'a>),
+    AnonFn(
+        Vec<TyAnnotation<Located<Local<'a>>>>,
+        TyAnnotation<Block<'a>>,
+    ),
+    Call(Box<Located<Expression<'a>>>, Vec<Located<Expression<'a>>>),
+    Let(
+        TyAnnotation<Located<Local<'a>>>,
+        Box<Located<Expression<'a>>>,
+        Box<Located<Expression<'a>>>,
+    ),
+    Pipe(Box<Located<Expression<'a>>>, Box<Located<Expression<'a>>>),
+    Literal(Literal),
+    Local(Local<'a>),
+    PrimFn(Primitive),
+    Ascription(Box<Located<Expression<'a>>>, Located<Ty>),
+    Hole,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Branch<'a> {
+    pub cond: Block<'a>,
+    pub body: Block<'a>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Method<'a> {
+    pub name: Located<Local<'a>>,
+    pub args: Vec<TyAnnotation<Located<Local<'a>>>>,
+    pub body: TyAnnotation<Block<'a>>,
+}
@@ -4,6 +4,8 @@
 //! type-checker.
 use std::fmt;
 
+use pijama_common::IntTy;
+
 /// A type used by the type-checker.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Ty {
@@ -11,10 +13,22 @@ pub enum Ty {
     Bool,
     /// The type of (signed) integers.
     Int,
+    /// The type of integers with a fixed width.
+    Fixed(IntTy),
+    /// The type of integers of arbitrary precision.
+    BigInt,
+    /// The type of double-precision floating-point numbers.
+    Float,
+    /// The type of Unicode scalar values.
+    Char,
     /// The [unit type](https://en.wikipedia.org/wiki/Unit_type).
     Unit,
     /// The type of functions between two types.
     Arrow(Box<Ty>, Box<Ty>),
+    /// The type of values that may be missing.
+    Option(Box<Ty>),
+    /// The type of values that are either successful or an error.
+    Result(Box<Ty>, Box<Ty>),
     /// Type variable, used for unification.
     Var(usize),
 }
@@ -23,27 +37,120 @@ impl Ty {
     /// Checks if the index of a `Ty::Var` is contained inside the type.
     pub fn contains(&self, index: usize) -> bool {
         match self {
-            Ty::Bool | Ty::Int | Ty::Unit => false,
-            Ty::Arrow(ty1, ty2) => ty1.contains(index) || ty2.contains(index),
+            Ty::Bool | Ty::Int | Ty::Fixed(_) | Ty::BigInt | Ty::Float | Ty::Char | Ty::Unit => {
+                false
+            }
+            Ty::Option(ty) => ty.contains(index),
+            Ty::Arrow(ty1, ty2) | Ty::Result(ty1, ty2) => {
+                ty1.contains(index) || ty2.contains(index)
+            }
             Ty::Var(inner) => *inner == index,
         }
     }
 
+    /// Returns the index of the leftmost `Ty::Var` contained inside the type, if there is one.
+    pub fn first_var(&self) -> Option<usize> {
+        match self {
+            Ty::Bool | Ty::Int | Ty::Fixed(_) | Ty::BigInt | Ty::Float | Ty::Char | Ty::Unit => {
+                None
+            }
+            Ty::Option(ty) => ty.first_var(),
+            Ty::Arrow(ty1, ty2) | Ty::Result(ty1, ty2) => {
+                ty1.first_var().or_else(|| ty2.first_var())
+            }
+            Ty::Var(index) => Some(*index),
+        }
+    }
+
+    /// Returns a copy of the type where every `Ty::Var` with the given index is replaced by `ty`.
+    pub fn substitute(&self, index: usize, ty: &Ty) -> Ty {
+        match self {
+            Ty::Bool | Ty::Int | Ty::Fixed(_) | Ty::BigInt | Ty::Float | Ty::Char | Ty::Unit => {
+                self.clone()
+            }
+            Ty::Arrow(ty1, ty2) => Ty::Arrow(
+                Box::new(ty1.substitute(index, ty)),
+                Box::new(ty2.substitute(index, ty)),
+            ),
+            Ty::Option(ty1) => Ty::Option(Box::new(ty1.substitute(index, ty))),
+            Ty::Result(ty1, ty2) => Ty::Result(
+                Box::new(ty1.substitute(index, ty)),
+                Box::new(ty2.substitute(index, ty)),
+            ),
+            Ty::Var(inner) if *inner == index => ty.clone(),
+            Ty::Var(_) => self.clone(),
+        }
+    }
+
+    /// Checks if `ty` has the shape of this type, where each `Ty::Var` of this type matches any
+    /// type.
+    ///
+    /// The type matched by each variable is stored in `bindings`, so every occurrence of the same
+    /// variable must match the same type. Returns `None` if this cannot be decided yet because
+    /// `ty` has type variables that could be replaced by types that match or not.
+    pub fn matches(&self, ty: &Ty, bindings: &mut Vec<(usize, Ty)>) -> Option<bool> {
+        match (self, ty) {
+            (Ty::Var(index), ty) => match bindings.iter().find(|(other, _)| other == index) {
+                None => {
+                    bindings.push((*index, ty.clone()));
+                    Some(true)
+                }
+                Some((_, bound)) if bound == ty => Some(true),
+                Some((_, bound)) if bound.is_concrete() && ty.is_concrete() => Some(false),
+                Some(_) => None,
+            },
+            (_, Ty::Var(_)) => None,
+            (Ty::Option(ty1), Ty::Option(ty2)) => ty1.matches(ty2, bindings),
+            (Ty::Arrow(ty1, ty2), Ty::Arrow(ty3, ty4))
+            | (Ty::Result(ty1, ty2), Ty::Result(ty3, ty4)) => {
+                match (ty1.matches(ty3, bindings), ty2.matches(ty4, bindings)) {
+                    (Some(false), _) | (_, Some(false)) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                }
+            }
+            (ty1, ty2) => Some(ty1 == ty2),
+        }
+    }
+
+    /// Adds the index of every `Ty::Var` inside the type to `vars`, without repetitions.
+    fn collect_vars(&self, vars: &mut Vec<usize>) {
+        match self {
+            Ty::Bool | Ty::Int | Ty::Fixed(_) | Ty::BigInt | Ty::Float | Ty::Char | Ty::Unit => (),
+            Ty::Option(ty) => ty.collect_vars(vars),
+            Ty::Arrow(ty1, ty2) | Ty::Result(ty1, ty2) => {
+                ty1.collect_vars(vars);
+                ty2.collect_vars(vars);
+            }
+            Ty::Var(index) => {
+                if !vars.contains(index) {
+                    vars.push(*index);
+                }
+            }
+        }
+    }
+
     pub fn is_concrete(&self) -> bool {
         match self {
-            Ty::Bool | Ty::Int | Ty::Unit => true,
-            Ty::Arrow(ty1, ty2) => ty1.is_concrete() && ty2.is_concrete(),
+            Ty::Bool | Ty::Int | Ty::Fixed(_) | Ty::BigInt | Ty::Float | Ty::Char | Ty::Unit => {
+                true
+            }
+            Ty::Option(ty) => ty.is_concrete(),
+            Ty::Arrow(ty1, ty2) | Ty::Result(ty1, ty2) => ty1.is_concrete() && ty2.is_concrete(),
             Ty::Var(_) => false,
         }
     }
 
     pub fn arity(&self) -> Option<usize> {
         match self {
-            Ty::Bool | Ty::Int | Ty::Unit => Some(0),
+            Ty::Bool | Ty::Int | Ty::Fixed(_) | Ty::BigInt | Ty::Float | Ty::Char | Ty::Unit => {
+                Some(0)
+            }
             Ty::Arrow(ty1, ty2) => {
                 ty1.arity()?;
                 Some(ty2.arity()? + 1)
             }
+            Ty::Option(_) | Ty::Result(_, _) => Some(0),
             Ty::Var(_) => None,
         }
     }
@@ -59,6 +166,10 @@ impl fmt::Display for Ty {
         match self {
             Bool => write!(f, "Bool"),
             Int => write!(f, "Int"),
+            Fixed(ty) => write!(f, "{}", ty),
+            BigInt => write!(f, "BigInt"),
+            Float => write!(f, "Float"),
+            Char => write!(f, "Char"),
             Unit => write!(f, "Unit"),
             Arrow(t1, t2) => {
                 if let Arrow(_, _) = t1.as_ref() {
@@ -67,11 +178,42 @@ impl fmt::Display for Ty {
                     write!(f, "{} -> {}", t1, t2)
                 }
             }
+            Option(t) => write!(f, "Option[{}]", t),
+            Result(t1, t2) => write!(f, "Result[{}, {}]", t1, t2),
             Var(index) => write!(f, "?X{}", index),
         }
     }
 }
 
+/// A type where some type variables are quantified, such as the type of an instance of a type
+/// class.
+///
+/// Every use of a scheme must instantiate its quantified variables with new type variables, so
+/// different uses do not constrain each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scheme {
+    /// Indices of the quantified type variables.
+    pub vars: Vec<usize>,
+    pub ty: Ty,
+}
+
+impl Scheme {
+    /// Creates a new scheme quantifying every type variable inside `ty`.
+    pub fn new(ty: Ty) -> Self {
+        let mut vars = Vec::new();
+        ty.collect_vars(&mut vars);
+        Scheme { vars, ty }
+    }
+
+    /// Returns the type of the scheme where each quantified variable is replaced by the type
+    /// returned by `new_ty`.
+    pub fn instantiate(&self, mut new_ty: impl FnMut() -> Ty) -> Ty {
+        self.vars.iter().fold(self.ty.clone(), |ty, &index| {
+            ty.substitute(index, &new_ty())
+        })
+    }
+}
+
 struct TyIter<'ty> {
     ty: Option<&'ty Ty>,
 }
@@ -81,12 +223,21 @@ impl<'ty> Iterator for TyIter<'ty> {
 
     fn next(&mut self) -> Option<Self::Item> {
         let ty = self.ty.take()?;
-        match ty  {
+        match ty {
             Ty::Arrow(t1, t2) => {
                 self.ty = Some(t2.as_ref());
                 Some(t1.as_ref())
             }
-            Ty::Int | Ty::Bool | Ty::Unit | Ty::Var(_) => Some(ty)
+            Ty::Int
+            | Ty::Fixed(_)
+            | Ty::BigInt
+            | Ty::Float
+            | Ty::Char
+            | Ty::Bool
+            | Ty::Unit
+            | Ty::Option(_)
+            | Ty::Result(_, _)
+            | Ty::Var(_) => Some(ty),
         }
     }
 }
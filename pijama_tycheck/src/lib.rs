@@ -6,14 +6,14 @@
 //!
 //! The entry-point for this module is the `ty_check` method which does the type checking of a
 //! whole program. However, most of the heavy lifting is done by the `Analyzer` and `Unifier` types.
-use std::collections::VecDeque;
+use std::{collections::VecDeque, fmt::Debug, hash::Hash};
 
 use pijama_common::{
     location::{Located, Location},
-    BinOp, Literal, Primitive, UnOp,
+    BinOp, Literal, Local, Primitive, SumFn, UnOp,
 };
 
-use pijama_ctx::{Context, ContextExt, LocalId, TypeInfo};
+use pijama_ctx::{Context, ContextExt, Dict, LocalId, TermId, TypeInfo};
 use pijama_hir::{BindKind, Term, TermKind};
 use pijama_ty::Ty;
 
@@ -21,7 +21,7 @@ mod result;
 mod unify;
 
 pub use result::{TyError, TyErrorKind, TyResult};
-use unify::{Constraint, Unifier};
+use unify::{Constraint, Origin, Unifier};
 
 /// Function that type-checks a term and returns its type.
 ///
@@ -32,40 +32,185 @@ pub fn ty_check(term: &Term, ctx: &mut Context) -> TyResult<Located<Ty>> {
     let mut analyzer = Analyzer::new(ctx);
     // Obtain typing constraints and the type of `term`.
     let mut ty = analyzer.type_of(&term)?;
+    // Require an instance for the type standing for `Self` in every use of a method.
+    analyzer.add_instance_constraints();
     // Solve the constraints using unification.
     let unif = Unifier::new(analyzer.constraints)?;
     // Apply the substitutions found during unification over the type of `term`.
     unif.replace(&mut ty.content);
 
-    let mut id = None;
+    // Apply the substitutions over the types of every local and term. If some of these types is
+    // not concrete, the error points to the earliest offending local in the source code or to the
+    // earliest offending term if every local has a concrete type. Locations, and the ids for the
+    // ones with the same location, are compared to make the reported error independent of the
+    // iteration order of the context.
+    let mut local_ids = Vec::new();
     for (local_id, ty) in ctx.iter_mut_local_types() {
         unif.replace(ty);
         if !ty.is_concrete() {
-            id = Some(local_id);
-            break;
+            local_ids.push(local_id);
         }
     }
-    if let Some(id) = id {
-        let loc = ctx.get_location(id).unwrap();
-        return Err(TyError::new(TyErrorKind::NotConcrete, loc));
-    }
-
-    let mut id = None;
+    let mut term_ids = Vec::new();
     for (term_id, ty) in ctx.iter_mut_term_types() {
         unif.replace(ty);
         if !ty.is_concrete() {
-            id = Some(term_id);
-            break;
+            term_ids.push(term_id);
         }
     }
-    if let Some(id) = id {
-        let loc = ctx.get_location(id).unwrap();
-        return Err(TyError::new(TyErrorKind::NotConcrete, loc));
+
+    // Holes are reported before any other error about types that could not be inferred because
+    // the type of a hole is usually what is missing to infer them.
+    if let Some(id) = earliest(ctx, ctx.hole_ids()) {
+        return Err(hole(ctx, id));
+    }
+
+    if let Some(id) = earliest(ctx, local_ids) {
+        let name = match ctx.get_local(id) {
+            Some(Local::Name(name)) => Some(name.to_owned()),
+            _ => None,
+        };
+        return Err(not_concrete(ctx, id, name));
+    }
+
+    if let Some(id) = earliest(ctx, term_ids) {
+        return Err(not_concrete(ctx, id, None));
+    }
+
+    // Save the dictionary of every use of a method of a type class. The uses are resolved in the
+    // same order as they appear in the source code to report the earliest error.
+    for id in method_use_ids(ctx) {
+        let dict = resolve_dict(ctx, &unif, id)?;
+        ctx.save_dict(id, dict);
     }
 
     Ok(ty)
 }
 
+/// Returns the ids of the terms using a method of a type class, in the same order as they appear
+/// in the source code.
+fn method_use_ids(ctx: &Context) -> Vec<TermId> {
+    let mut ids = ctx.method_use_ids();
+    ids.sort_by_key(|&id| {
+        let loc = ctx.get_location(id).unwrap();
+        (loc.start, loc.end, id)
+    });
+    ids
+}
+
+/// Returns the dictionary the method used by the term `id` is selected from.
+///
+/// This is the dictionary of the instance chosen during unification. If no instance was chosen,
+/// either the type standing for `Self` in such use is not concrete or no instance matches it.
+fn resolve_dict(ctx: &Context, unif: &Unifier, id: TermId) -> TyResult<Dict> {
+    let loc = ctx.get_location(id).unwrap();
+    let method_use = ctx.get_method_use(id).unwrap();
+
+    if let Some(index) = unif.instance(id) {
+        return Ok(method_use.instances[index].1.clone());
+    }
+
+    let mut self_ty = method_use.self_ty.clone();
+    unif.replace(&mut self_ty);
+
+    if !self_ty.is_concrete() {
+        let note = format!(
+            "the type of `Self` for the class `{}` could not be inferred",
+            method_use.class
+        );
+        return Err(TyError::new(
+            TyErrorKind::NotConcrete {
+                ty: self_ty,
+                name: None,
+            },
+            loc,
+        )
+        .with_note(note));
+    }
+
+    Err(TyError::new(
+        TyErrorKind::NoInstance {
+            class: method_use.class.clone(),
+            ty: self_ty,
+        },
+        loc,
+    ))
+}
+
+/// Returns a `Hole` error for the hole `id`.
+///
+/// The error has a note listing the locals in scope whose types are compatible with the type of
+/// the hole. This function must be called after applying the substitutions over every type.
+fn hole(ctx: &Context, id: TermId) -> TyError {
+    let loc = ctx.get_location(id).unwrap();
+    let ty = ctx.get_type_info(id).unwrap().ty.clone();
+
+    let candidates = ctx
+        .get_hole(id)
+        .unwrap()
+        .iter()
+        .filter_map(|&local_id| {
+            let local_ty = &ctx.get_type_info(local_id)?.ty;
+            if Unifier::unifies(&ty, local_ty, loc) {
+                Some(format!("`{}: {}`", ctx.get_local(local_id)?, local_ty))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let note = if candidates.is_empty() {
+        "there are no locals in scope with a compatible type".to_owned()
+    } else {
+        format!(
+            "these locals in scope have a compatible type: {}",
+            candidates.join(", ")
+        )
+    };
+
+    TyError::new(TyErrorKind::Hole(ty), loc).with_note(note)
+}
+
+/// Returns the id with the earliest location in the source code.
+///
+/// If several ids have the earliest location, the one generated first is returned.
+fn earliest<Id>(ctx: &Context, ids: Vec<Id>) -> Option<Id>
+where
+    Id: Debug + Hash + Ord + Copy,
+    for<'ast> Context<'ast>: ContextExt<Id>,
+{
+    ids.into_iter().min_by_key(|&id| {
+        let loc = ctx.get_location(id).unwrap();
+        (loc.start, loc.end, id)
+    })
+}
+
+/// Returns a `NotConcrete` error for the type of `id`.
+///
+/// The error has a note with the first type variable that could not be inferred.
+fn not_concrete<Id>(ctx: &Context, id: Id, name: Option<String>) -> TyError
+where
+    Id: Debug + Hash + Eq + Copy,
+    for<'ast> Context<'ast>: ContextExt<Id>,
+{
+    let loc = ctx.get_location(id).unwrap();
+    let ty = ctx.get_type_info(id).unwrap().ty.clone();
+    let mut error = TyError::new(
+        TyErrorKind::NotConcrete {
+            ty: ty.clone(),
+            name,
+        },
+        loc,
+    );
+    if let Some(index) = ty.first_var() {
+        error = error.with_note(format!(
+            "the type variable `{}` could not be inferred",
+            Ty::Var(index)
+        ));
+    }
+    error
+}
+
 /// A typing analyzer.
 ///
 /// This structure traverses the HIR of a term and generates a set of constraints that must be
@@ -99,9 +244,9 @@ impl<'ast, 'ctx> Analyzer<'ast, 'ctx> {
     /// Calling this method will not enforce the rule instantly. It only stores the restriction
     /// inside the `Analyzer` to be solved by the `Unifier` in a posterior stage. This constraint
     /// has a location that will be used as the location of the error if the constraint is
-    /// impossible to satisfy.
-    pub fn add_constraint(&mut self, expected: Ty, found: Ty, loc: Location) {
-        let constr = Constraint::new(expected, found);
+    /// impossible to satisfy and an origin used to explain why the `expected` type was expected.
+    pub fn add_constraint(&mut self, expected: Ty, found: Ty, loc: Location, origin: Origin) {
+        let constr = Constraint::new(expected, found, origin);
         // New constraints are front-pushed because the `Unifier` processes constraints by popping
         // them from the back. If we just back-push the constraints, we end up taking care of the
         // newer constraints first, which are more complex and can end up in less readable type
@@ -109,6 +254,62 @@ impl<'ast, 'ctx> Analyzer<'ast, 'ctx> {
         self.constraints.push_front(Located::new(constr, loc))
     }
 
+    /// Adds a new `Constraint` stating that `ty` must be an integer type.
+    ///
+    /// If the type cannot be decided by the other constraints, it defaults to `Int`.
+    fn add_integer_constraint(&mut self, ty: Ty, loc: Location, origin: Origin) {
+        let constr = Constraint::integer(ty, origin);
+        self.constraints.push_front(Located::new(constr, loc))
+    }
+
+    /// Adds a new `Constraint` stating that `ty` must be a numeric type, i.e., an integer type or
+    /// `Float`.
+    ///
+    /// If the type cannot be decided by the other constraints, it defaults to `Int`.
+    fn add_numeric_constraint(&mut self, ty: Ty, loc: Location, origin: Origin) {
+        let constr = Constraint::numeric(ty, origin);
+        self.constraints.push_front(Located::new(constr, loc))
+    }
+
+    /// Adds a constraint for each use of a method of a type class stating that the type standing
+    /// for `Self` in such use must match the type of an instance of the class.
+    ///
+    /// The types of the instances are instantiated with new type variables for each use.
+    fn add_instance_constraints(&mut self) {
+        for id in method_use_ids(self.ctx) {
+            let loc = self.ctx.get_location(id).unwrap();
+            let method_use = self.ctx.get_method_use(id).unwrap();
+            let self_ty = method_use.self_ty.clone();
+            let origin = Origin::Instance(method_use.class.clone());
+            let schemes: Vec<_> = method_use
+                .instances
+                .iter()
+                .map(|(scheme, _)| scheme.clone())
+                .collect();
+            let heads = schemes
+                .iter()
+                .map(|scheme| scheme.instantiate(|| self.new_ty()))
+                .collect();
+            let constr = Constraint::instance(id, self_ty, heads, origin);
+            self.constraints.push_front(Located::new(constr, loc));
+        }
+    }
+
+    /// Returns the origin of a constraint coming from the type annotation at `loc` with type `ty`.
+    ///
+    /// If there is no annotation at `loc`, the constraint comes from the type of the binding at
+    /// `loc` instead.
+    fn annotation(&self, loc: Location, ty: &Ty) -> Origin {
+        if !self.ctx.is_annotation(loc) {
+            return Origin::Binding(loc);
+        }
+        let alias = self
+            .ctx
+            .get_ty_alias(loc)
+            .map(|name| (name.to_owned(), ty.clone()));
+        Origin::Annotation { loc, alias }
+    }
+
     /// Returns the type of a term.
     ///
     /// The location of the type returned by this function is such that showing a type error
@@ -124,18 +325,27 @@ impl<'ast, 'ctx> Analyzer<'ast, 'ctx> {
             TermKind::Var(name) => self.type_of_var(*name),
             TermKind::Abs(name, body) => self.type_of_abs(*name, body.as_ref()),
             TermKind::UnaryOp(op, term) => self.type_of_unary_op(*op, term.as_ref()),
-            TermKind::BinaryOp(op, t1, t2) => self.type_of_binary_op(*op, t1.as_ref(), t2.as_ref()),
-            TermKind::App(t1, t2) => self.type_of_app(t1.as_ref(), t2.as_ref()),
+            TermKind::BinaryOp(op, t1, t2) => {
+                self.type_of_binary_op(term.id, *op, t1.as_ref(), t2.as_ref())
+            }
+            TermKind::App(t1, t2) => self.type_of_app(term.id, t1.as_ref(), t2.as_ref()),
             TermKind::Let(kind, name, t1, t2) => {
                 self.type_of_let(*kind, *name, t1.as_ref(), t2.as_ref())
             }
+            TermKind::LetGroup(binds, tail) | TermKind::Instance(_, binds, tail) => {
+                self.type_of_let_group(binds, tail.as_ref())
+            }
             TermKind::Cond(t1, t2, t3) => self.type_of_cond(t1.as_ref(), t2.as_ref(), t3.as_ref()),
-            TermKind::PrimFn(prim) => self.type_of_prim_fn(*prim),
+            TermKind::PrimFn(prim) => self.type_of_prim_fn(term.id, *prim),
+            TermKind::Method(_) => self.type_of_method(term.id),
+            TermKind::Hole => Ok(self.new_ty()),
         }?;
 
         if let Some(info) = self.ctx.get_type_info(term.id) {
             let info_ty = info.ty.clone();
-            self.add_constraint(info_ty, ty.clone(), loc);
+            let info_loc = info.loc;
+            let origin = self.annotation(info_loc, &info_ty);
+            self.add_constraint(info_ty, ty.clone(), loc, origin);
         } else {
             self.ctx.insert_type_info(
                 term.id,
@@ -158,6 +368,10 @@ impl<'ast, 'ctx> Analyzer<'ast, 'ctx> {
             Literal::Unit => Ty::Unit,
             Literal::Bool(_) => Ty::Bool,
             Literal::Number(_) => Ty::Int,
+            Literal::Fixed(_, ty) => Ty::Fixed(*ty),
+            Literal::Big(_) => Ty::BigInt,
+            Literal::Float(_) => Ty::Float,
+            Literal::Char(_) => Ty::Char,
         };
         Ok(ty)
     }
@@ -205,61 +419,76 @@ impl<'ast, 'ctx> Analyzer<'ast, 'ctx> {
     /// Returns the type of an unary operation.
     ///
     /// The type of an unary operation depends on its operator:
-    /// - If it is a negation, the operand must have type `Int`.
+    /// - If it is a negation, the operand must have a numeric type.
     /// - If it is a logical not, the operand must have type `Bool`.
     ///
     /// This rule adds a constraint stating that the type of the operand must match one of the
     /// types stated above. The returned type is the same type as the operand.
     fn type_of_unary_op(&mut self, op: UnOp, term: &Term) -> TyResult {
         let ty = self.type_of(term)?;
-        let expected = match op {
-            UnOp::Neg => Ty::Int,
-            UnOp::Not => Ty::Bool,
-        };
-        self.add_constraint(expected, ty.content.clone(), ty.loc);
+        match op {
+            UnOp::Neg => {
+                let origin = Origin::NumOperand(op.to_string());
+                self.add_numeric_constraint(ty.content.clone(), ty.loc, origin);
+            }
+            UnOp::Not => {
+                let origin = Origin::Operand {
+                    op: op.to_string(),
+                    other: None,
+                };
+                self.add_constraint(Ty::Bool, ty.content.clone(), ty.loc, origin);
+            }
+        }
         Ok(ty.content)
     }
 
     /// Returns the type of an binary operation.
     ///
     /// The type of a binary operation depends on its operator:
-    /// - If it is an arithmetic operator, the operands must have type `Int`.
+    /// - If it is an arithmetic operator, the operands must have the same numeric type.
+    /// - If it is a bitwise operator, the operands must have the same integer type.
     /// - If it is a logic operator, the operands must have type `Bool`.
-    /// - If it is `Eq` or `Neq`, the operands must have the same type.
-    /// - If it is any other comparison operator, the operands must have type `Bool`.
+    /// - If it is a comparison operator, the operands must have the same type.
     ///
     /// This rule adds one of the constraints stated above. The returned type is `Bool`, unless the
-    /// operation is an arithmetic operation, which has type `Int`.
-    fn type_of_binary_op(&mut self, op: BinOp, t1: &Term, t2: &Term) -> TyResult {
+    /// operation is an arithmetic or bitwise operation, which has the type of its operands.
+    ///
+    /// Comparison operators are methods of the `Eq` and `Ord` classes. The type of the operands is
+    /// the type standing for `Self` in the use of the method and a constraint is added for it.
+    fn type_of_binary_op(&mut self, id: TermId, op: BinOp, t1: &Term, t2: &Term) -> TyResult {
         let ty1 = self.type_of(t1)?;
         let ty2 = self.type_of(t2)?;
+        let origin = Origin::Operand {
+            op: op.to_string(),
+            other: None,
+        };
         let ty = match op {
-            BinOp::Add
-            | BinOp::Sub
-            | BinOp::Mul
-            | BinOp::Div
-            | BinOp::Rem
-            | BinOp::BitAnd
-            | BinOp::BitOr
-            | BinOp::BitXor
-            | BinOp::Shr
-            | BinOp::Shl => {
-                self.add_constraint(Ty::Int, ty1.content, ty1.loc);
-                self.add_constraint(Ty::Int, ty2.content, ty2.loc);
-                Ty::Int
+            BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Rem => {
+                let num_origin = Origin::NumOperand(op.to_string());
+                self.add_numeric_constraint(ty1.content.clone(), ty1.loc, num_origin);
+                self.add_constraint(ty1.content.clone(), ty2.content, ty2.loc, origin);
+                ty1.content
             }
-            BinOp::Or | BinOp::And => {
-                self.add_constraint(Ty::Bool, ty1.content, ty1.loc);
-                self.add_constraint(Ty::Bool, ty2.content, ty2.loc);
-                Ty::Bool
+            BinOp::BitAnd | BinOp::BitOr | BinOp::BitXor | BinOp::Shr | BinOp::Shl => {
+                let int_origin = Origin::IntOperand(op.to_string());
+                self.add_integer_constraint(ty1.content.clone(), ty1.loc, int_origin);
+                self.add_constraint(ty1.content.clone(), ty2.content, ty2.loc, origin);
+                ty1.content
             }
-            BinOp::Lt | BinOp::Gt | BinOp::Lte | BinOp::Gte => {
-                self.add_constraint(Ty::Int, ty1.content, ty1.loc);
-                self.add_constraint(Ty::Int, ty2.content, ty2.loc);
+            BinOp::Or | BinOp::And => {
+                self.add_constraint(Ty::Bool, ty1.content, ty1.loc, origin.clone());
+                self.add_constraint(Ty::Bool, ty2.content, ty2.loc, origin);
                 Ty::Bool
             }
-            BinOp::Eq | BinOp::Neq => {
-                self.add_constraint(ty1.content, ty2.content, ty2.loc);
+            BinOp::Lt | BinOp::Gt | BinOp::Lte | BinOp::Gte | BinOp::Eq | BinOp::Neq => {
+                let self_ty = self.ctx.get_method_use(id).unwrap().self_ty.clone();
+                self.add_constraint(self_ty, ty1.content.clone(), ty1.loc, origin);
+
+                let origin = Origin::Operand {
+                    op: op.to_string(),
+                    other: Some(ty1.loc),
+                };
+                self.add_constraint(ty1.content, ty2.content, ty2.loc, origin);
                 Ty::Bool
             }
         };
@@ -272,15 +501,32 @@ impl<'ast, 'ctx> Analyzer<'ast, 'ctx> {
     ///
     /// This method introduces a new type variable `X` and adds the constraint `T1 = T2 -> X` where
     /// `T1` is `t1`'s type and `T2` is `t2`'s type. The returned type is `X`.
-    fn type_of_app(&mut self, t1: &Term, t2: &Term) -> TyResult {
+    ///
+    /// The origin of this constraint is the function at the head of the application. For example,
+    /// if the application is `f(a, b)`, the origin is `f` for both arguments. If the application
+    /// comes from a pipe, the origin is the right side of the pipe instead.
+    fn type_of_app(&mut self, id: TermId, t1: &Term, t2: &Term) -> TyResult {
         let ty1 = self.type_of(t1)?.content;
         let ty2 = self.type_of(t2)?;
         let ty = self.new_ty();
 
+        let mut head = t1;
+        while let TermKind::App(func, _) = &head.kind {
+            head = func.as_ref();
+        }
+        let func = self.ctx.get_location(head.id).unwrap();
+        let func_ty = self.ctx.get_type_info(head.id).unwrap().ty.clone();
+        let origin = if self.ctx.is_pipe(id) {
+            Origin::Pipe { func, func_ty }
+        } else {
+            Origin::Argument { func, func_ty }
+        };
+
         self.add_constraint(
             ty1,
             Ty::Arrow(Box::new(ty2.content), Box::new(ty.clone())),
             ty2.loc,
+            origin,
         );
 
         Ok(ty)
@@ -307,16 +553,20 @@ impl<'ast, 'ctx> Analyzer<'ast, 'ctx> {
             BindKind::NonRec => {
                 let rhs_ty = self.type_of(rhs)?;
 
-                let lhs_ty = self.ctx.get_type_info(lhs).unwrap().ty.clone();
+                let lhs_info = self.ctx.get_type_info(lhs).unwrap();
+                let (lhs_ty, lhs_loc) = (lhs_info.ty.clone(), lhs_info.loc);
 
-                self.add_constraint(lhs_ty, rhs_ty.content.clone(), rhs_ty.loc);
+                let origin = self.annotation(lhs_loc, &lhs_ty);
+                self.add_constraint(lhs_ty, rhs_ty.content.clone(), rhs_ty.loc, origin);
             }
             BindKind::Rec => {
-                let lhs_ty = self.ctx.get_type_info(lhs).unwrap().ty.clone();
+                let lhs_info = self.ctx.get_type_info(lhs).unwrap();
+                let (lhs_ty, lhs_loc) = (lhs_info.ty.clone(), lhs_info.loc);
 
                 let rhs_ty = self.type_of(rhs)?;
 
-                self.add_constraint(lhs_ty, rhs_ty.content.clone(), rhs_ty.loc);
+                let origin = self.annotation(lhs_loc, &lhs_ty);
+                self.add_constraint(lhs_ty, rhs_ty.content.clone(), rhs_ty.loc, origin);
             }
         };
 
@@ -324,6 +574,27 @@ impl<'ast, 'ctx> Analyzer<'ast, 'ctx> {
         Ok(tail_ty)
     }
 
+    /// Returns the type of a group of mutually recursive let bindings.
+    ///
+    /// The whole group is typed as a single recursive binding: each name is already binded to the
+    /// type provided by its annotation so every term can use any name of the group. The inferred
+    /// type of each term must coincide with the type of its name and a constraint is added for
+    /// each one of them. The type of the group is the same as the type of the tail.
+    fn type_of_let_group(&mut self, binds: &[(LocalId, Term)], tail: &Term) -> TyResult {
+        for (lhs, rhs) in binds {
+            let lhs_info = self.ctx.get_type_info(*lhs).unwrap();
+            let (lhs_ty, lhs_loc) = (lhs_info.ty.clone(), lhs_info.loc);
+
+            let rhs_ty = self.type_of(rhs)?;
+
+            let origin = self.annotation(lhs_loc, &lhs_ty);
+            self.add_constraint(lhs_ty, rhs_ty.content.clone(), rhs_ty.loc, origin);
+        }
+
+        let tail_ty = self.type_of(tail)?.content;
+        Ok(tail_ty)
+    }
+
     /// Returns the type of a conditional.
     ///
     /// Typing a conditional requires that the condition has type `Bool` and that both branches
@@ -331,28 +602,140 @@ impl<'ast, 'ctx> Analyzer<'ast, 'ctx> {
     /// the first branch.
     fn type_of_cond(&mut self, t1: &Term, t2: &Term, t3: &Term) -> TyResult {
         let ty1 = self.type_of(t1)?;
-        let ty2 = self.type_of(t2)?.content;
+        let ty2 = self.type_of(t2)?;
         let ty3 = self.type_of(t3)?;
 
-        self.add_constraint(Ty::Bool, ty1.content, ty1.loc);
-        self.add_constraint(ty2.clone(), ty3.content, ty3.loc);
+        self.add_constraint(Ty::Bool, ty1.content, ty1.loc, Origin::Condition);
+        self.add_constraint(
+            ty2.content.clone(),
+            ty3.content,
+            ty3.loc,
+            Origin::Branch(ty2.loc),
+        );
 
-        Ok(ty2)
+        Ok(ty2.content)
     }
 
     /// Returns the type of a primitive function.
     ///
     /// The typing rules for each primitive are the following:
     ///
-    /// - The `print` function has type `X -> Unit` for any `X`. Thus, a new variable is added to
-    /// the typing context to represent this `X`.
-    fn type_of_prim_fn(&mut self, prim: Primitive) -> TyResult {
+    /// - The `print` function is the method of the `Show` class and has type `X -> Unit` where `X`
+    ///   is the type standing for `Self` in this use of the method.
+    /// - The integer conversion functions have type `X -> T` where `X` must be an integer type and
+    ///   `T` is the integer type they convert to.
+    /// - The `to_float` function has type `X -> Float` where `X` must be a numeric type.
+    /// - The `truncate` function has type `Float -> Int`.
+    /// - The `ord` function has type `Char -> Int` and the `chr` function has type `Int -> Char`.
+    /// - The primitives over `Option` and `Result` values are typed by `type_of_sum_fn`.
+    /// - The `assert` function has type `Bool -> Unit`.
+    /// - The `assert_eq` function uses the `eq` method of the `Eq` class and has type `X -> X ->
+    ///   Unit` where `X` is the type standing for `Self` in this use of the method.
+    fn type_of_prim_fn(&mut self, id: TermId, prim: Primitive) -> TyResult {
         let ty = match prim {
             Primitive::Print => {
-                let ty = self.new_ty();
+                let ty = self.ctx.get_method_use(id).unwrap().self_ty.clone();
                 Ty::Arrow(Box::new(ty), Box::new(Ty::Unit))
             }
+            Primitive::ToInt | Primitive::ToFixed(_) | Primitive::ToBigInt => {
+                let loc = self.ctx.get_location(id).unwrap();
+                let ty = self.new_ty();
+                let origin = Origin::IntArgument(prim.to_string());
+                self.add_integer_constraint(ty.clone(), loc, origin);
+                let target = match prim {
+                    Primitive::ToFixed(int_ty) => Ty::Fixed(int_ty),
+                    Primitive::ToBigInt => Ty::BigInt,
+                    _ => Ty::Int,
+                };
+                Ty::Arrow(Box::new(ty), Box::new(target))
+            }
+            Primitive::ToFloat => {
+                let loc = self.ctx.get_location(id).unwrap();
+                let ty = self.new_ty();
+                let origin = Origin::NumArgument(prim.to_string());
+                self.add_numeric_constraint(ty.clone(), loc, origin);
+                Ty::Arrow(Box::new(ty), Box::new(Ty::Float))
+            }
+            Primitive::Truncate => Ty::Arrow(Box::new(Ty::Float), Box::new(Ty::Int)),
+            Primitive::Ord => Ty::Arrow(Box::new(Ty::Char), Box::new(Ty::Int)),
+            Primitive::Chr => Ty::Arrow(Box::new(Ty::Int), Box::new(Ty::Char)),
+            Primitive::Sum(sum_fn) => self.type_of_sum_fn(id, sum_fn),
+            Primitive::Assert => Ty::Arrow(Box::new(Ty::Bool), Box::new(Ty::Unit)),
+            Primitive::AssertEq => {
+                let ty = self.ctx.get_method_use(id).unwrap().self_ty.clone();
+                Ty::Arrow(
+                    Box::new(ty.clone()),
+                    Box::new(Ty::Arrow(Box::new(ty), Box::new(Ty::Unit))),
+                )
+            }
         };
         Ok(ty)
     }
+
+    /// Returns the type of a primitive over `Option` and `Result` values used by the term `id`.
+    ///
+    /// Each use of these primitives gets new type variables `X`, `E` and `Y`. Their types are the
+    /// following:
+    ///
+    /// - `some: X -> Option[X]` and `none: Option[X]`.
+    /// - `ok: X -> Result[X, E]` and `err: E -> Result[X, E]`.
+    /// - `is_some: Option[X] -> Bool` and `is_ok: Result[X, E] -> Bool`.
+    ///
+    /// The `unwrap_or`, `map` and `and_then` functions have a typing rule for each kind of value,
+    /// chosen by a constraint once the type of their first argument is known:
+    ///
+    /// - `unwrap_or: Option[X] -> X -> X` and `unwrap_or: Result[X, E] -> X -> X`.
+    /// - `map: Option[X] -> (X -> Y) -> Option[Y]` and
+    ///   `map: Result[X, E] -> (X -> Y) -> Result[Y, E]`.
+    /// - `and_then: Option[X] -> (X -> Option[Y]) -> Option[Y]` and
+    ///   `and_then: Result[X, E] -> (X -> Result[Y, E]) -> Result[Y, E]`.
+    fn type_of_sum_fn(&mut self, id: TermId, sum_fn: SumFn) -> Ty {
+        let x = self.new_ty();
+        let e = self.new_ty();
+        let option = |ty: &Ty| Ty::Option(Box::new(ty.clone()));
+        let result = |ty: &Ty| Ty::Result(Box::new(ty.clone()), Box::new(e.clone()));
+        let arrow = |ty1: Ty, ty2: Ty| Ty::Arrow(Box::new(ty1), Box::new(ty2));
+
+        let (option_ty, result_ty) = match sum_fn {
+            SumFn::Some => return arrow(x.clone(), option(&x)),
+            SumFn::None => return option(&x),
+            SumFn::Ok => return arrow(x.clone(), result(&x)),
+            SumFn::Err => return arrow(e.clone(), result(&x)),
+            SumFn::IsSome => return arrow(option(&x), Ty::Bool),
+            SumFn::IsOk => return arrow(result(&x), Ty::Bool),
+            SumFn::UnwrapOr => (
+                arrow(option(&x), arrow(x.clone(), x.clone())),
+                arrow(result(&x), arrow(x.clone(), x.clone())),
+            ),
+            SumFn::Map => {
+                let y = self.new_ty();
+                (
+                    arrow(option(&x), arrow(arrow(x.clone(), y.clone()), option(&y))),
+                    arrow(result(&x), arrow(arrow(x.clone(), y.clone()), result(&y))),
+                )
+            }
+            SumFn::AndThen => {
+                let y = self.new_ty();
+                (
+                    arrow(option(&x), arrow(arrow(x.clone(), option(&y)), option(&y))),
+                    arrow(result(&x), arrow(arrow(x.clone(), result(&y)), result(&y))),
+                )
+            }
+        };
+
+        let loc = self.ctx.get_location(id).unwrap();
+        let ty = arrow(self.new_ty(), self.new_ty());
+        let origin = Origin::SumArgument(sum_fn.to_string());
+        let constr = Constraint::sum(ty.clone(), option_ty, result_ty, origin);
+        self.constraints.push_front(Located::new(constr, loc));
+        ty
+    }
+
+    /// Returns the type of a method of a type class.
+    ///
+    /// This rule does not add new constraints because the type of the method was decided when it
+    /// was lowered, using its signature with a new type variable standing for `Self`.
+    fn type_of_method(&mut self, id: TermId) -> TyResult {
+        Ok(self.ctx.get_type_info(id).unwrap().ty.clone())
+    }
 }
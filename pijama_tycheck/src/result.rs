@@ -21,8 +21,47 @@ pub enum TyErrorKind {
     #[error("Local `{0}` is not bounded")]
     Unbounded(String),
     /// Variant used when a type still has type variables in it.
-    #[error("Type cannot be reconstructed")]
-    NotConcrete,
+    ///
+    /// The `name` field holds the name of the offending local, if the type belongs to one.
+    #[error("Type cannot be reconstructed, found `{ty}`")]
+    NotConcrete { ty: Ty, name: Option<String> },
+    /// Variant used when a method of a type class is used with a type that has no instance.
+    #[error("No instance of class `{class}` for type `{ty}`")]
+    NoInstance { class: String, ty: Ty },
+    /// Variant used when a hole is found in place of a term.
+    #[error("Found hole of type `{0}`")]
+    Hole(Ty),
+}
+
+impl TyErrorKind {
+    /// Returns the stable code identifying this kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            TyErrorKind::Mismatch { .. } => "E0201",
+            TyErrorKind::Unbounded(_) => "E0202",
+            TyErrorKind::NotConcrete { .. } => "E0203",
+            TyErrorKind::NoInstance { .. } => "E0204",
+            TyErrorKind::Hole(_) => "E0205",
+        }
+    }
+
+    /// Returns a hint on how to fix this error, if there is one.
+    pub fn help(&self) -> Option<String> {
+        match self {
+            TyErrorKind::NotConcrete {
+                name: Some(name), ..
+            } => Some(format!("consider adding a type annotation to `{}`", name)),
+            TyErrorKind::NotConcrete { name: None, .. } => {
+                Some("consider adding a type annotation".to_owned())
+            }
+            TyErrorKind::NoInstance { class, ty } => Some(format!(
+                "consider declaring an instance of `{}` for `{}`",
+                class, ty
+            )),
+            TyErrorKind::Hole(_) => Some("replace the hole with a term of this type".to_owned()),
+            _ => None,
+        }
+    }
 }
 
 /// A typing error.
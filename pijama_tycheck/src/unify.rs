@@ -6,9 +6,10 @@
 //!
 //! This algorithm is based on Chapter 22 of the _Types and Programming Languages_ book by Benjamin
 //! Pierce.
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 
-use pijama_common::location::Located;
+use pijama_common::location::{Located, Location};
+use pijama_ctx::TermId;
 use pijama_ty::Ty;
 
 use crate::{TyError, TyErrorKind, TyResult};
@@ -23,6 +24,16 @@ pub struct Unifier {
     substitutions: Vec<Substitution>,
     /// Typing constraints of the program.
     constraints: VecDeque<Located<Constraint>>,
+    /// Integer, numeric, sum and instance constraints that could not be solved when they were
+    /// processed because their type was not known enough.
+    ///
+    /// Sum and instance constraints are processed again as soon as their type is known. Otherwise,
+    /// these constraints are processed again once every other constraint has been solved: integer
+    /// and numeric types default to `Int`, sum types default to `Option` and instance
+    /// constraints use the only instance that could match, if there is one.
+    pending: VecDeque<Located<Constraint>>,
+    /// Index of the instance chosen for each use of a method of a type class.
+    instances: HashMap<TermId, usize>,
 }
 
 impl Unifier {
@@ -35,11 +46,33 @@ impl Unifier {
         let mut unif = Unifier {
             substitutions: Default::default(),
             constraints,
+            pending: Default::default(),
+            instances: Default::default(),
         };
         unif.unify()?;
         Ok(unif)
     }
 
+    /// Returns whether `expected` and `found` can be made equal by replacing their type
+    /// variables.
+    ///
+    /// The location of the term that introduced this question is used as the location of the
+    /// constraint between them.
+    pub(super) fn unifies(expected: &Ty, found: &Ty, loc: Location) -> bool {
+        let constr = Constraint::new(
+            expected.clone(),
+            found.clone(),
+            Origin::Annotation { loc, alias: None },
+        );
+        Unifier::new(VecDeque::from(vec![Located::new(constr, loc)])).is_ok()
+    }
+
+    /// Returns the index of the instance chosen for the use of a method by the term `id`, if
+    /// any.
+    pub(super) fn instance(&self, id: TermId) -> Option<usize> {
+        self.instances.get(&id).copied()
+    }
+
     /// Replaces the type variables inside a type.
     ///
     /// This uses the `substitutions` field to replace type variables.
@@ -54,10 +87,26 @@ impl Unifier {
     /// This method applies `subst` over both sides of the `Constraint`s in the `constraints`
     /// field.
     fn apply_substitution(&mut self, subst: &Substitution) {
-        for constr in self.constraints.iter_mut() {
-            let Constraint { lhs, rhs } = &mut constr.content;
+        for constr in self.constraints.iter_mut().chain(self.pending.iter_mut()) {
+            let Constraint {
+                lhs,
+                rhs,
+                origin,
+                outer,
+                kind,
+            } = &mut constr.content;
             subst.apply(lhs);
             subst.apply(rhs);
+            if let ConstraintKind::Sum(result) = kind {
+                subst.apply(result);
+            }
+            if let Origin::Argument { func_ty, .. } = origin {
+                subst.apply(func_ty);
+            }
+            if let Some((outer_lhs, outer_rhs)) = outer {
+                subst.apply(outer_lhs);
+                subst.apply(outer_rhs);
+            }
         }
     }
 
@@ -78,9 +127,22 @@ impl Unifier {
     /// `replace` type variables and the program can be assumed to be well-typed.
     fn unify(&mut self) -> TyResult<()> {
         // If there are constraints to be solved, take one.
-        if let Some(constr) = self.constraints.pop_back() {
+        if let Some(constr) = self.next_constraint() {
+            match constr.content.kind {
+                ConstraintKind::Equal => (),
+                ConstraintKind::Sum(_) => return self.unify_sum(constr, false),
+                ConstraintKind::Instance { .. } => return self.unify_instance(constr, false),
+                _ => return self.unify_numeric(constr, false),
+            }
+
             let loc = constr.loc;
-            let Constraint { lhs, rhs } = constr.content;
+            let Constraint {
+                lhs,
+                rhs,
+                origin,
+                outer,
+                ..
+            } = constr.content;
 
             match (lhs, rhs) {
                 // If both sides of the constraint are equal, nothing needs to be done. We can skip
@@ -107,32 +169,307 @@ impl Unifier {
                     self.add_substitution(subst);
                 }
 
-                // If both sides are arrow types, we add new constraints matching each side of the
-                // arrows with their counterpart. This constraints are pushed at the back to
-                // prioritize them.
-                (Ty::Arrow(s1, s2), Ty::Arrow(t1, t2)) => {
-                    self.constraints
-                        .push_back(Located::new(Constraint::new(*s1, *t1), loc));
-                    self.constraints
-                        .push_back(Located::new(Constraint::new(*s2, *t2), loc));
+                // If both sides are arrow types, both are result types or both are option types,
+                // we add new constraints matching each component with its counterpart. This
+                // constraints are pushed at the back to prioritize them.
+                (lhs @ Ty::Arrow(_, _), rhs @ Ty::Arrow(_, _))
+                | (lhs @ Ty::Result(_, _), rhs @ Ty::Result(_, _))
+                | (lhs @ Ty::Option(_), rhs @ Ty::Option(_)) => {
+                    self.push_components(lhs, rhs, origin, outer, loc);
                     self.unify()?;
                 }
 
                 // Otherwise, this constraint cannot be satisfied and we raise an error.
-                (lhs, rhs) => {
-                    return Err(TyError::new(
-                        TyErrorKind::Mismatch {
-                            expected: lhs,
-                            found: rhs,
-                        },
-                        loc,
-                    ));
-                }
+                (lhs, rhs) => return Err(mismatch(lhs, rhs, origin, outer, loc)),
             }
+        } else if let Some(constr) = self.next_pending() {
+            // If there are no more constraints but some constraints are pending, their types can
+            // take a default.
+            return match constr.content.kind {
+                ConstraintKind::Sum(_) => self.unify_sum(constr, true),
+                ConstraintKind::Instance { .. } => self.unify_instance(constr, true),
+                _ => self.unify_numeric(constr, true),
+            };
         }
         // If there are no more constrains, we are done.
         Ok(())
     }
+
+    /// Takes the next constraint to be solved.
+    ///
+    /// Pending sum and instance constraints are taken as soon as their type is known enough to
+    /// solve them, so the remaining constraints are solved using the typing rule or the instance
+    /// they choose.
+    fn next_constraint(&mut self) -> Option<Located<Constraint>> {
+        let ready = self.pending.iter().position(|constr| {
+            let Constraint { rhs, kind, .. } = &constr.content;
+            match (kind, rhs) {
+                (ConstraintKind::Sum(_), Ty::Arrow(arg, _)) => !matches!(arg.as_ref(), Ty::Var(_)),
+                (ConstraintKind::Instance { heads, .. }, ty) => {
+                    !matches!(choose_instance(heads, ty), Choice::Unknown)
+                }
+                _ => false,
+            }
+        });
+        match ready {
+            Some(index) => self.pending.remove(index),
+            None => self.constraints.pop_back(),
+        }
+    }
+
+    /// Takes the next pending constraint to be solved using its default once every other
+    /// constraint has been solved.
+    ///
+    /// Instance constraints are taken after every other kind of constraint, and only if a single
+    /// instance could match their type.
+    fn next_pending(&mut self) -> Option<Located<Constraint>> {
+        let index = self
+            .pending
+            .iter()
+            .position(|constr| !matches!(constr.content.kind, ConstraintKind::Instance { .. }))
+            .or_else(|| {
+                self.pending
+                    .iter()
+                    .position(|constr| match &constr.content.kind {
+                        ConstraintKind::Instance { heads, .. } => {
+                            only_candidate(heads, &constr.content.rhs).is_some()
+                        }
+                        _ => false,
+                    })
+            })?;
+        self.pending.remove(index)
+    }
+
+    /// Adds constraints matching each component of `lhs` with its counterpart in `rhs`.
+    ///
+    /// The new constraints keep the origin of the constraint between `lhs` and `rhs` and the
+    /// outermost types being unified, so errors can show the full types instead of just the
+    /// components that did not match.
+    ///
+    /// This is kept apart from `Unifier::unify` to avoid growing the stack frame of that recursive
+    /// method.
+    fn push_components(
+        &mut self,
+        lhs: Ty,
+        rhs: Ty,
+        origin: Origin,
+        outer: Option<(Ty, Ty)>,
+        loc: Location,
+    ) {
+        let outer = outer.or_else(|| Some((lhs.clone(), rhs.clone())));
+        let components = match (lhs, rhs) {
+            (Ty::Arrow(s1, s2), Ty::Arrow(t1, t2)) | (Ty::Result(s1, s2), Ty::Result(t1, t2)) => {
+                vec![(*s1, *t1), (*s2, *t2)]
+            }
+            (Ty::Option(s), Ty::Option(t)) => vec![(*s, *t)],
+            _ => unreachable!(),
+        };
+        for (lhs, rhs) in components {
+            self.constraints.push_back(Located::new(
+                Constraint {
+                    lhs,
+                    rhs,
+                    origin: origin.clone(),
+                    outer: outer.clone(),
+                    kind: ConstraintKind::Equal,
+                },
+                loc,
+            ));
+        }
+    }
+
+    /// Solves a constraint stating that its right-hand side is an integer or numeric type.
+    ///
+    /// If the right-hand side is a type variable, the constraint is left pending unless
+    /// `default` is true, in which case the variable is replaced by `Int`.
+    fn unify_numeric(&mut self, constr: Located<Constraint>, default: bool) -> TyResult<()> {
+        match &constr.content.rhs {
+            Ty::Int | Ty::Fixed(_) | Ty::BigInt => self.unify(),
+            Ty::Float if constr.content.kind == ConstraintKind::Numeric => self.unify(),
+            Ty::Var(index) if default => {
+                let subst = Substitution::new(Ty::Var(*index), Ty::Int);
+                self.apply_substitution(&subst);
+                self.unify()?;
+                self.add_substitution(subst);
+                Ok(())
+            }
+            Ty::Var(_) => {
+                self.pending.push_back(constr);
+                self.unify()
+            }
+            rhs => {
+                let error = TyError::new(
+                    TyErrorKind::Mismatch {
+                        expected: constr.content.lhs.clone(),
+                        found: rhs.clone(),
+                    },
+                    constr.loc,
+                );
+                Err(constr.content.origin.add_context(error, &Ty::Int))
+            }
+        }
+    }
+
+    /// Solves a constraint stating that there is an instance of a type class for its right-hand
+    /// side.
+    ///
+    /// Once an instance is chosen, a new constraint states that the right-hand side is equal to
+    /// the type of the instance. If no instance matches, the constraint is dropped and the
+    /// type-checker reports the error after unification. If the type is not known enough, the
+    /// constraint is left pending unless `default` is true, in which case the only instance that
+    /// could match is chosen.
+    fn unify_instance(&mut self, constr: Located<Constraint>, default: bool) -> TyResult<()> {
+        let loc = constr.loc;
+        let (id, heads) = match &constr.content.kind {
+            ConstraintKind::Instance { id, heads } => (*id, heads),
+            _ => unreachable!(),
+        };
+
+        let index = match choose_instance(heads, &constr.content.rhs) {
+            Choice::Instance(index) => index,
+            Choice::NoInstance => return self.unify(),
+            Choice::Unknown => match only_candidate(heads, &constr.content.rhs) {
+                Some(index) if default => index,
+                _ => {
+                    self.pending.push_back(constr);
+                    return self.unify();
+                }
+            },
+        };
+
+        self.instances.insert(id, index);
+        let Constraint {
+            rhs, origin, kind, ..
+        } = constr.content;
+        let head = match kind {
+            ConstraintKind::Instance { mut heads, .. } => heads.swap_remove(index),
+            _ => unreachable!(),
+        };
+        self.constraints
+            .push_back(Located::new(Constraint::new(head, rhs, origin), loc));
+        self.unify()
+    }
+
+    /// Solves a constraint stating that its right-hand side is the type of a function over
+    /// `Option` or `Result` values.
+    ///
+    /// The right-hand side is an arrow type whose argument decides the typing rule of the
+    /// function: the left-hand side if the argument is an `Option` or the type stored in the kind
+    /// of the constraint if it is a `Result`. If the argument is a type variable, the constraint
+    /// is left pending unless `default` is true, in which case the `Option` rule is used.
+    fn unify_sum(&mut self, constr: Located<Constraint>, default: bool) -> TyResult<()> {
+        let loc = constr.loc;
+        let Constraint {
+            lhs,
+            rhs,
+            origin,
+            kind,
+            ..
+        } = constr.content;
+
+        let arg = match &rhs {
+            Ty::Arrow(arg, _) => arg.as_ref(),
+            _ => unreachable!("sum constraints are only added for function types"),
+        };
+
+        let expected = match (arg, kind) {
+            (Ty::Option(_), _) => lhs,
+            (Ty::Var(_), _) if default => lhs,
+            (Ty::Result(_, _), ConstraintKind::Sum(result)) => result,
+            (Ty::Var(_), kind) => {
+                let constr = Constraint {
+                    lhs,
+                    rhs,
+                    origin,
+                    outer: None,
+                    kind,
+                };
+                self.pending.push_back(Located::new(constr, loc));
+                return self.unify();
+            }
+            (arg, _) => {
+                let expected = match lhs {
+                    Ty::Arrow(expected, _) => *expected,
+                    _ => unreachable!("sum constraints are only added for function types"),
+                };
+                let error = TyError::new(
+                    TyErrorKind::Mismatch {
+                        expected: expected.clone(),
+                        found: arg.clone(),
+                    },
+                    loc,
+                );
+                return Err(origin.add_context(error, &expected));
+            }
+        };
+
+        self.constraints
+            .push_back(Located::new(Constraint::new(expected, rhs, origin), loc));
+        self.unify()
+    }
+}
+
+/// The instance chosen by an instance constraint.
+enum Choice {
+    /// The instance with the given index matches the type.
+    Instance(usize),
+    /// No instance matches the type.
+    NoInstance,
+    /// The type is not known enough to choose an instance.
+    Unknown,
+}
+
+/// Chooses the innermost instance whose type matches `ty` out of the instances with types `heads`.
+///
+/// No instance can be chosen while the type is not known enough to decide if an inner instance
+/// matches it.
+fn choose_instance(heads: &[Ty], ty: &Ty) -> Choice {
+    for (index, head) in heads.iter().enumerate().rev() {
+        match head.matches(ty, &mut Vec::new()) {
+            Some(true) => return Choice::Instance(index),
+            Some(false) => (),
+            None => return Choice::Unknown,
+        }
+    }
+    Choice::NoInstance
+}
+
+/// Returns the index of the only instance, out of the instances with types `heads`, that could
+/// match `ty` once it is known, if there is exactly one.
+fn only_candidate(heads: &[Ty], ty: &Ty) -> Option<usize> {
+    let mut candidates = heads
+        .iter()
+        .enumerate()
+        .filter(|(_, head)| head.matches(ty, &mut Vec::new()) != Some(false));
+    match (candidates.next(), candidates.next()) {
+        (Some((index, _)), None) => Some(index),
+        _ => None,
+    }
+}
+
+/// Returns the error for a constraint between the types `lhs` and `rhs` that cannot be satisfied.
+///
+/// This is kept apart from `Unifier::unify` to avoid growing the stack frame of that recursive
+/// method.
+fn mismatch(lhs: Ty, rhs: Ty, origin: Origin, outer: Option<(Ty, Ty)>, loc: Location) -> TyError {
+    let mut error = TyError::new(
+        TyErrorKind::Mismatch {
+            expected: lhs.clone(),
+            found: rhs,
+        },
+        loc,
+    );
+
+    error = origin.add_context(error, &lhs);
+
+    if let Some((outer_lhs, outer_rhs)) = outer {
+        error = error.with_note(format!(
+            "expected type `{}`\n   found type `{}`",
+            outer_lhs, outer_rhs
+        ));
+    }
+
+    error
 }
 
 /// Represents a substitution rule over types.
@@ -154,13 +491,137 @@ impl Substitution {
     pub fn apply(&self, ty: &mut Ty) {
         if *ty == self.old {
             *ty = self.new.clone();
-        } else if let Ty::Arrow(ty1, ty2) = ty {
+        } else if let Ty::Option(ty) = ty {
+            self.apply(ty);
+        } else if let Ty::Arrow(ty1, ty2) | Ty::Result(ty1, ty2) = ty {
             self.apply(ty1);
             self.apply(ty2);
         }
     }
 }
 
+/// The reason why a constraint was introduced.
+///
+/// This is used to give more context when a constraint cannot be satisfied.
+#[derive(Debug, Clone)]
+pub enum Origin {
+    /// The constraint comes from a type annotation at the given location. If the annotation was
+    /// written using a type alias, it stores the name of the alias and its type.
+    Annotation {
+        loc: Location,
+        alias: Option<(String, Ty)>,
+    },
+    /// The constraint comes from the type of a binding without annotation. It stores the location
+    /// of the binding.
+    Binding(Location),
+    /// The constraint comes from passing an argument to a function. It stores the location and
+    /// type of the function being called.
+    Argument { func: Location, func_ty: Ty },
+    /// The constraint comes from passing the left side of a pipe to its right side. It stores the
+    /// location and type of the function at the right side.
+    Pipe { func: Location, func_ty: Ty },
+    /// The constraint comes from the condition of a conditional.
+    Condition,
+    /// The constraint comes from the branches of a conditional having the same type. It stores
+    /// the location of the first branch.
+    Branch(Location),
+    /// The constraint comes from the operand of an operator. It stores the representation of the
+    /// operator and the location of the other operand when both operands must have the same type.
+    Operand { op: String, other: Option<Location> },
+    /// The constraint comes from the operand of an operator that requires an integer type. It
+    /// stores the representation of the operator.
+    IntOperand(String),
+    /// The constraint comes from the argument of a primitive function that requires an integer
+    /// type. It stores the name of the function.
+    IntArgument(String),
+    /// The constraint comes from the operand of an operator that requires a numeric type. It
+    /// stores the representation of the operator.
+    NumOperand(String),
+    /// The constraint comes from the argument of a primitive function that requires a numeric
+    /// type. It stores the name of the function.
+    NumArgument(String),
+    /// The constraint comes from the argument of a primitive function that requires an `Option`
+    /// or a `Result`. It stores the name of the function.
+    SumArgument(String),
+    /// The constraint comes from the instance of a type class chosen for the type standing for
+    /// `Self` in a use of one of its methods. It stores the name of the class.
+    Instance(String),
+}
+
+impl Origin {
+    /// Adds labels and notes to a mismatch error explaining where the `expected` type came from.
+    fn add_context(self, error: TyError, expected: &Ty) -> TyError {
+        match self {
+            Origin::Annotation { loc, alias: None } => {
+                error.with_label(loc, "expected due to this")
+            }
+            Origin::Annotation {
+                loc,
+                alias: Some((name, ty)),
+            } => error
+                .with_label(loc, format!("expected `{}` due to this", name))
+                .with_note(format!("`{}` is an alias for `{}`", name, ty)),
+            Origin::Binding(loc) => {
+                error.with_label(loc, "expected due to the type of this binding")
+            }
+            Origin::Argument { func, func_ty } => {
+                error.with_label(func, format!("this function has type `{}`", func_ty))
+            }
+            Origin::Pipe { func, func_ty } => error
+                .with_label(
+                    func,
+                    format!("the right side of this pipe has type `{}`", func_ty),
+                )
+                .with_note(
+                    "the left side of a pipe is passed as the first argument of its right side",
+                ),
+            Origin::Condition => error.with_note("conditions must have type `Bool`"),
+            Origin::Branch(loc) => error.with_label(
+                loc,
+                format!("expected because this branch has type `{}`", expected),
+            ),
+            Origin::Operand { op, other: None } => error.with_note(format!(
+                "the operator `{}` requires operands of type `{}`",
+                op, expected
+            )),
+            Origin::Operand {
+                op,
+                other: Some(loc),
+            } => error.with_label(
+                loc,
+                format!(
+                    "expected because the other operand of `{}` has type `{}`",
+                    op, expected
+                ),
+            ),
+            Origin::IntOperand(op) => error.with_note(format!(
+                "the operator `{}` requires operands of an integer type",
+                op
+            )),
+            Origin::IntArgument(name) => error.with_note(format!(
+                "the function `{}` requires an argument of an integer type",
+                name
+            )),
+            Origin::NumOperand(op) => error.with_note(format!(
+                "the operator `{}` requires operands of a numeric type",
+                op
+            )),
+            Origin::NumArgument(name) => error.with_note(format!(
+                "the function `{}` requires an argument of a numeric type",
+                name
+            )),
+            Origin::Instance(class) => error.with_note(format!(
+                "expected because of the instance of the class `{}` for `{}`",
+                class, expected
+            )),
+            Origin::SumArgument(name) => error.with_note(format!(
+                "the function `{}` requires an argument of type `Option` or `Result`",
+                name
+            )),
+        }
+    }
+}
+
 /// Represents a constraint between types.
 #[derive(Debug)]
 pub struct Constraint {
@@ -172,11 +633,91 @@ pub struct Constraint {
     ///
     /// It usually represents the type found when creating a constraint.
     rhs: Ty,
+    /// The reason why this constraint was introduced.
+    origin: Origin,
+    /// The outermost types this constraint comes from, if it was introduced while unifying two
+    /// arrow types.
+    outer: Option<(Ty, Ty)>,
+    /// Whether the right-hand side must be equal to the left-hand side or belong to a set of
+    /// types.
+    kind: ConstraintKind,
 }
 
 impl Constraint {
     /// Creates a new constraint.
-    pub fn new(lhs: Ty, rhs: Ty) -> Self {
-        Constraint { lhs, rhs }
+    pub fn new(lhs: Ty, rhs: Ty, origin: Origin) -> Self {
+        Constraint {
+            lhs,
+            rhs,
+            origin,
+            outer: None,
+            kind: ConstraintKind::Equal,
+        }
+    }
+
+    /// Creates a new constraint stating that `ty` must be an integer type.
+    pub fn integer(ty: Ty, origin: Origin) -> Self {
+        Constraint {
+            lhs: Ty::Int,
+            rhs: ty,
+            origin,
+            outer: None,
+            kind: ConstraintKind::Integer,
+        }
+    }
+
+    /// Creates a new constraint stating that the type `ty` of a function over `Option` or
+    /// `Result` values must be `option` if its argument is an `Option`, or `result` if its
+    /// argument is a `Result`.
+    pub fn sum(ty: Ty, option: Ty, result: Ty, origin: Origin) -> Self {
+        Constraint {
+            lhs: option,
+            rhs: ty,
+            origin,
+            outer: None,
+            kind: ConstraintKind::Sum(result),
+        }
+    }
+
+    /// Creates a new constraint stating that the type `ty` standing for `Self` in the use of a
+    /// method by the term `id` must match one of the types `heads` of the instances of its class.
+    pub fn instance(id: TermId, ty: Ty, heads: Vec<Ty>, origin: Origin) -> Self {
+        Constraint {
+            lhs: ty.clone(),
+            rhs: ty,
+            origin,
+            outer: None,
+            kind: ConstraintKind::Instance { id, heads },
+        }
+    }
+
+    /// Creates a new constraint stating that `ty` must be a numeric type.
+    pub fn numeric(ty: Ty, origin: Origin) -> Self {
+        Constraint {
+            lhs: Ty::Int,
+            rhs: ty,
+            origin,
+            outer: None,
+            kind: ConstraintKind::Numeric,
+        }
     }
 }
+
+/// The kinds of constraints.
+#[derive(Debug, PartialEq, Eq)]
+enum ConstraintKind {
+    /// Both sides of the constraint must be equal.
+    Equal,
+    /// The right-hand side must be an integer type. The left-hand side is `Int`.
+    Integer,
+    /// The right-hand side must be an integer type or `Float`. The left-hand side is `Int`.
+    Numeric,
+    /// The right-hand side must be the type of a function over `Option` or `Result` values. The
+    /// left-hand side is its type when used over an `Option` and this kind stores its type when
+    /// used over a `Result`.
+    Sum(Ty),
+    /// The right-hand side must match the type of an instance of a type class for the use of a
+    /// method by the term `id`. The left-hand side is the same type and `heads` are the types of
+    /// the instances in scope, from outermost to innermost, instantiated for this use.
+    Instance { id: TermId, heads: Vec<Ty> },
+}